@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .add_systems(Update, toggle_playback)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let spinner = asset_server.load("spinner.gif");
+    let style = WoodpeckerStyle {
+        width: Units::Pixels(128.0),
+        height: Units::Pixels(128.0),
+        margin: Edge::all(10.0),
+        ..Default::default()
+    };
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                padding: Edge::all(20.0),
+                ..Default::default()
+            },
+            WidgetChildren::default()
+                // Press space to pause and resume every animation.
+                .with_child::<Element>((Element, style, AnimatedImagePlayer::new(spinner.clone())))
+                .with_child::<Element>((
+                    Element,
+                    style,
+                    AnimatedImagePlayer::new(spinner.clone()).with_speed(2.0),
+                ))
+                .with_child::<Element>((
+                    Element,
+                    style,
+                    AnimatedImagePlayer::new(spinner).with_loop_count(3),
+                )),
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
+
+fn toggle_playback(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut players: Query<&mut AnimatedImagePlayer>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for mut player in players.iter_mut() {
+        if player.is_finished() {
+            player.restart();
+        } else if player.playing {
+            player.pause();
+        } else {
+            player.play();
+        }
+    }
+}
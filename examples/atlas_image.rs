@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let woodpecker = asset_server.load("woodpecker.jpg");
+    let slice = asset_server.load("slice.png");
+    let slice_layout = atlas_layouts.add(TextureAtlasLayout::from_grid(
+        UVec2::splat(397),
+        1,
+        1,
+        None,
+        None,
+    ));
+
+    let mut children = WidgetChildren::default();
+
+    // Each quarter of the image drawn from the same sheet.
+    let mut quarters = WidgetChildren::default();
+    for (x, y) in [(0.0, 0.0), (0.5, 0.0), (0.0, 0.5), (0.5, 0.5)] {
+        quarters.add::<Element>((
+            Element,
+            WoodpeckerStyle {
+                width: 150.0.into(),
+                height: 150.0.into(),
+                margin: Edge::all(5.0),
+                ..Default::default()
+            },
+            WidgetRender::AtlasImage {
+                handle: woodpecker.clone(),
+                region: ImageRegion::Uv(Rect::new(x, y, x + 0.5, y + 0.5)),
+                nine_patch: None,
+            },
+        ));
+    }
+    children.add::<Element>((
+        Element,
+        WoodpeckerStyle {
+            width: Units::Percentage(100.0),
+            ..Default::default()
+        },
+        quarters,
+    ));
+
+    // Buttons of different sizes sharing one sliced atlas sprite.
+    let mut buttons = WidgetChildren::default();
+    for size in [
+        Vec2::new(120.0, 60.0),
+        Vec2::new(240.0, 80.0),
+        Vec2::new(80.0, 160.0),
+    ] {
+        buttons.add::<Element>((
+            Element,
+            WoodpeckerStyle {
+                width: size.x.into(),
+                height: size.y.into(),
+                margin: Edge::all(5.0),
+                // Nearest filtering to avoid texture bleeding.
+                image_quality: ImageQuality::Low,
+                ..Default::default()
+            },
+            WidgetRender::AtlasImage {
+                handle: slice.clone(),
+                region: ImageRegion::atlas(slice_layout.clone(), 0),
+                nine_patch: Some(TextureSlicer {
+                    border: BorderRect::all(135.0),
+                    max_corner_scale: 0.1,
+                    ..default()
+                }),
+            },
+        ));
+    }
+    children.add::<Element>((
+        Element,
+        WoodpeckerStyle {
+            width: Units::Percentage(100.0),
+            ..Default::default()
+        },
+        buttons,
+    ));
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                flex_direction: WidgetFlexDirection::Column,
+                ..Default::default()
+            },
+            children,
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
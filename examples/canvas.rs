@@ -0,0 +1,136 @@
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .add_systems(Update, update_cooldown)
+        .run();
+}
+
+#[derive(Component)]
+struct Cooldown;
+
+fn cooldown_canvas(progress: f32) -> Canvas {
+    let center = Vec2::splat(50.0);
+    Canvas::new()
+        .with_shape(
+            Shape::circle(center, 45.0)
+                .with_fill(Srgba::hex("1e1e2e").unwrap())
+                .with_stroke(ShapeStroke::new(2.0, Srgba::hex("45475a").unwrap())),
+        )
+        .with_shape(
+            Shape::arc(center, 36.0, -FRAC_PI_2, TAU * progress).with_stroke(
+                ShapeStroke::new(8.0, Srgba::hex("89b4fa").unwrap())
+                    .with_cap(vello::kurbo::Cap::Round),
+            ),
+        )
+}
+
+fn startup(mut commands: Commands, mut ui_context: ResMut<WoodpeckerContext>) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let canvas_style = WoodpeckerStyle {
+        margin: Edge::all(20.0),
+        ..Default::default()
+    };
+
+    // A graph line with a gradient area below it.
+    let values = [0.2, 0.5, 0.35, 0.8, 0.6, 0.9, 0.7];
+    let points = values
+        .iter()
+        .enumerate()
+        .map(|(i, value)| Vec2::new(i as f32 * 50.0, 150.0 - value * 150.0))
+        .collect::<Vec<_>>();
+    let area = points
+        .iter()
+        .copied()
+        .chain([Vec2::new(300.0, 150.0), Vec2::new(0.0, 150.0)]);
+    let graph = Canvas::new()
+        .with_shape(Shape::polygon(area).with_fill(ShapeBrush::LinearGradient {
+            start: Vec2::ZERO,
+            end: Vec2::new(0.0, 150.0),
+            stops: vec![
+                (0.0, Srgba::hex("a6e3a1").unwrap().with_alpha(0.6).into()),
+                (1.0, Srgba::hex("a6e3a1").unwrap().with_alpha(0.0).into()),
+            ],
+        }))
+        .with_shape(
+            Shape::polyline(points.iter().copied()).with_stroke(
+                ShapeStroke::new(3.0, Srgba::hex("a6e3a1").unwrap())
+                    .with_join(vello::kurbo::Join::Round),
+            ),
+        );
+
+    // A gauge with a needle.
+    let needle_angle = PI + PI * 0.7;
+    let gauge = Canvas::new()
+        .with_shape(
+            Shape::arc(Vec2::new(75.0, 80.0), 65.0, PI, PI)
+                .with_stroke(ShapeStroke::new(12.0, Srgba::hex("313244").unwrap())),
+        )
+        .with_shape(
+            Shape::arc(Vec2::new(75.0, 80.0), 65.0, PI, PI * 0.7)
+                .with_stroke(ShapeStroke::new(12.0, Srgba::hex("f9e2af").unwrap())),
+        )
+        .with_shape(
+            Shape::line(
+                Vec2::new(75.0, 80.0),
+                Vec2::new(75.0, 80.0) + Vec2::from_angle(needle_angle) * 55.0,
+            )
+            .with_stroke(ShapeStroke::new(4.0, Color::WHITE).with_cap(vello::kurbo::Cap::Round)),
+        )
+        .with_shape(Shape::circle(Vec2::new(75.0, 80.0), 6.0).with_fill(Color::WHITE))
+        .with_shape(
+            Shape::bezier(
+                Vec2::new(10.0, 100.0),
+                Vec2::new(50.0, 130.0),
+                Vec2::new(100.0, 70.0),
+                Vec2::new(140.0, 100.0),
+            )
+            .with_stroke(ShapeStroke::new(2.0, Srgba::hex("f38ba8").unwrap())),
+        );
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WidgetChildren::default()
+                .with_child::<Canvas>((
+                    Cooldown,
+                    cooldown_canvas(0.0),
+                    WoodpeckerStyle {
+                        width: 100.0.into(),
+                        height: 100.0.into(),
+                        ..canvas_style
+                    },
+                ))
+                .with_child::<Canvas>((
+                    graph,
+                    WoodpeckerStyle {
+                        width: 300.0.into(),
+                        height: 150.0.into(),
+                        ..canvas_style
+                    },
+                ))
+                .with_child::<Canvas>((
+                    gauge,
+                    WoodpeckerStyle {
+                        width: 150.0.into(),
+                        height: 110.0.into(),
+                        ..canvas_style
+                    },
+                )),
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
+
+fn update_cooldown(time: Res<Time>, mut query: Query<&mut Canvas, With<Cooldown>>) {
+    for mut canvas in query.iter_mut() {
+        *canvas = cooldown_canvas(time.elapsed_secs() / 3.0 % 1.0);
+    }
+}
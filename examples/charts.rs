@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .add_systems(Update, update_data)
+        .run();
+}
+
+const DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Some made up data which changes every few seconds.
+fn values(seed: u32, offset: f32) -> Vec<f32> {
+    (0..DAYS.len())
+        .map(|day| {
+            let x = (seed * 7 + day as u32) as f32;
+            ((x * 1.7 + offset).sin() * 0.5 + 0.5) * 80.0 + 10.0
+        })
+        .map(f32::round)
+        .collect()
+}
+
+fn line_chart(seed: u32) -> LineChart {
+    LineChart::new(DAYS)
+        .with_series(ChartSeries::new(
+            "Visitors",
+            Srgba::hex("89b4fa").unwrap(),
+            values(seed, 0.0),
+        ))
+        .with_series(ChartSeries::new(
+            "Signups",
+            Srgba::hex("a6e3a1").unwrap(),
+            values(seed, 2.0).into_iter().map(|value| value / 2.0),
+        ))
+}
+
+fn bar_chart(seed: u32) -> BarChart {
+    BarChart::new(DAYS)
+        .with_series(ChartSeries::new(
+            "Sales",
+            Srgba::hex("f9e2af").unwrap(),
+            values(seed, 1.0),
+        ))
+        .with_series(ChartSeries::new(
+            "Returns",
+            Srgba::hex("f38ba8").unwrap(),
+            values(seed, 3.0).into_iter().map(|value| value / 4.0),
+        ))
+}
+
+fn pie_chart(seed: u32) -> PieChart {
+    let values = values(seed, 4.0);
+    PieChart::new()
+        .with_inner_radius(0.5)
+        .with_slice(PieSlice::new(
+            "Desktop",
+            values[0],
+            Srgba::hex("cba6f7").unwrap(),
+        ))
+        .with_slice(PieSlice::new(
+            "Mobile",
+            values[1],
+            Srgba::hex("94e2d5").unwrap(),
+        ))
+        .with_slice(PieSlice::new(
+            "Tablet",
+            values[2],
+            Srgba::hex("fab387").unwrap(),
+        ))
+}
+
+fn startup(mut commands: Commands, mut ui_context: ResMut<WoodpeckerContext>) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let chart_style = WoodpeckerStyle {
+        width: Units::Percentage(45.0),
+        height: 300.0.into(),
+        margin: Edge::all(20.0),
+        font_size: 14.0,
+        ..Default::default()
+    };
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                flex_wrap: WidgetFlexWrap::Wrap,
+                ..Default::default()
+            },
+            WidgetChildren::default()
+                .with_child::<LineChart>((line_chart(0), chart_style))
+                .with_child::<BarChart>((bar_chart(0), chart_style))
+                .with_child::<PieChart>((pie_chart(0), chart_style)),
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
+
+fn update_data(
+    time: Res<Time>,
+    mut seed: Local<u32>,
+    mut line_charts: Query<&mut LineChart, Without<PreviousWidget>>,
+    mut bar_charts: Query<&mut BarChart, Without<PreviousWidget>>,
+    mut pie_charts: Query<&mut PieChart, Without<PreviousWidget>>,
+) {
+    let next_seed = (time.elapsed_secs() / 3.0) as u32;
+    if next_seed == *seed {
+        return;
+    }
+    *seed = next_seed;
+    for mut chart in line_charts.iter_mut() {
+        *chart = line_chart(*seed);
+    }
+    for mut chart in bar_charts.iter_mut() {
+        *chart = bar_chart(*seed);
+    }
+    for mut chart in pie_charts.iter_mut() {
+        *chart = pie_chart(*seed);
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Use `FontManager::new(false)` to only use fonts loaded as assets.
+        .insert_resource(FontManager::new(true))
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut font_manager: ResMut<FontManager>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let font = asset_server.load("Outfit/static/Outfit-Regular.ttf");
+    font_manager.add(&font);
+
+    // Glyphs missing from Outfit are looked up in these system fonts if they are installed.
+    font_manager.set_locale(Some("ja"));
+    font_manager.add_script_fallbacks("Hani", Some("ja"), &["Noto Sans CJK JP", "MS Gothic"]);
+    font_manager.add_script_fallbacks("Arab", None, &["Noto Sans Arabic", "Arial"]);
+    font_manager.add_emoji_fallbacks(&["Noto Color Emoji", "Segoe UI Emoji", "Apple Color Emoji"]);
+
+    let text_style = WoodpeckerStyle {
+        font_size: 40.0,
+        margin: Edge::all(10.0),
+        font: Some(font.id()),
+        ..Default::default()
+    };
+
+    let mut children = WidgetChildren::default();
+    for content in ["Hello 日本語", "مرحبا بالعالم", "Emoji 🐦🌲🎨"] {
+        children.add::<Element>((
+            Element,
+            text_style,
+            WidgetRender::Text {
+                content: content.into(),
+            },
+        ));
+    }
+
+    // Fonts can also be used by their family name.
+    if let Some(serif) = font_manager.font("DejaVu Serif") {
+        children.add::<Element>((
+            Element,
+            WoodpeckerStyle {
+                font: Some(serif),
+                ..text_style
+            },
+            WidgetRender::Text {
+                content: "A system serif font".into(),
+            },
+        ));
+    }
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                flex_direction: WidgetFlexDirection::Column,
+                ..Default::default()
+            },
+            children,
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
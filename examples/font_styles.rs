@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut font_manager: ResMut<FontManager>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    // Every face of a family that is loaded can be selected by weight, style and stretch.
+    let font = asset_server.load("Outfit/static/Outfit-Regular.ttf");
+    font_manager.add(&font);
+    font_manager.add(&asset_server.load("Outfit/static/Outfit-Bold.ttf"));
+    font_manager.add(&asset_server.load("Outfit/static/Outfit-Thin.ttf"));
+
+    let text_style = WoodpeckerStyle {
+        font_size: 40.0,
+        margin: Edge::all(10.0),
+        font: Some(font.id()),
+        ..Default::default()
+    };
+
+    let mut children = WidgetChildren::default();
+    for (content, style) in [
+        (
+            "Thin",
+            WoodpeckerStyle {
+                font_weight: FontWeight::THIN,
+                ..text_style
+            },
+        ),
+        (
+            "Bold",
+            WoodpeckerStyle {
+                font_weight: FontWeight::BOLD,
+                ..text_style
+            },
+        ),
+        (
+            "Italic",
+            WoodpeckerStyle {
+                font_style: FontStyle::Italic,
+                ..text_style
+            },
+        ),
+        (
+            "Score: 1111 / 9999",
+            WoodpeckerStyle {
+                font_features: FontFeatures::NONE.with(FontFeature::TABULAR_NUMBERS),
+                ..text_style
+            },
+        ),
+    ] {
+        children.add::<Element>((
+            Element,
+            style,
+            WidgetRender::Text {
+                content: content.into(),
+            },
+        ));
+    }
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                flex_direction: WidgetFlexDirection::Column,
+                ..Default::default()
+            },
+            children,
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
@@ -87,6 +87,23 @@ fn render(
                             handle: asset_server.load("woodpecker.jpg"),
                         },
                     ))
+                    // A square thumbnail which crops the image instead of letting it shrink.
+                    .with_child::<Element>((
+                        Element,
+                        WoodpeckerStyle {
+                            width: Units::Pixels(150.0),
+                            height: Units::Pixels(150.0),
+                            margin: Edge::all(0.0).bottom(10.0),
+                            border_radius: Corner::all(Units::Pixels(75.0)),
+                            object_fit: ObjectFit::Cover,
+                            object_position: ObjectPosition::CENTER,
+                            image_tint: Some(Srgba::rgb(1.0, 0.8, 0.6).into()),
+                            ..Default::default()
+                        },
+                        WidgetRender::Image {
+                            handle: asset_server.load("woodpecker.jpg"),
+                        },
+                    ))
                     .with_child::<Element>((
                         Element,
                         WoodpeckerStyle {
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .add_systems(Update, controls)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let pulse = asset_server.load("pulse.lottie.json");
+    let lottie_style = WoodpeckerStyle {
+        width: 120.0.into(),
+        height: 120.0.into(),
+        margin: Edge::all(20.0),
+        ..Default::default()
+    };
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WidgetChildren::default()
+                .with_child::<Element>((Element, lottie_style, LottiePlayer::new(pulse.clone())))
+                .with_child::<Element>((
+                    Element,
+                    lottie_style,
+                    LottiePlayer::new(pulse.clone())
+                        .with_speed(0.5)
+                        .with_color("Dot", Srgba::hex("f38ba8").unwrap())
+                        .with_color("Ring", Srgba::hex("f9e2af").unwrap()),
+                ))
+                .with_child::<Element>((
+                    Element,
+                    lottie_style,
+                    LottiePlayer::new(pulse)
+                        .with_segment(LottieSegment::Marker("grow".into()))
+                        .with_loop_count(1),
+                ))
+                .with_child::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        font_size: 16.0,
+                        margin: Edge::all(20.0),
+                        ..Default::default()
+                    },
+                    WidgetRender::Text {
+                        content: "Space: play/pause, R: restart, G/S: play the grow/shrink marker"
+                            .into(),
+                    },
+                )),
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
+
+fn controls(keyboard: Res<ButtonInput<KeyCode>>, mut players: Query<&mut LottiePlayer>) {
+    for mut player in players.iter_mut() {
+        if keyboard.just_pressed(KeyCode::Space) {
+            if player.playing {
+                player.pause();
+            } else {
+                player.play();
+            }
+        }
+        if keyboard.just_pressed(KeyCode::KeyR) {
+            player.restart();
+        }
+        if keyboard.just_pressed(KeyCode::KeyG) {
+            player.play_segment(LottieSegment::Marker("grow".into()));
+        }
+        if keyboard.just_pressed(KeyCode::KeyS) {
+            player.play_segment(LottieSegment::Marker("shrink".into()));
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+const CONTENT: &str = r#"# Woodpecker UI
+
+Markdown is displayed with **bold**, *italic* and ~~struck through~~ text, `inline code` and
+[links](https://github.com/StarArawn/woodpecker_ui).
+
+## Lists
+
+- Headings
+- Block quotes
+- Code blocks
+
+1. First
+2. Second
+
+> A block quote with *emphasis*.
+
+---
+
+```rust
+fn main() {
+    println!("Hello world!");
+}
+```
+"#;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut font_manager: ResMut<FontManager>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let font = asset_server.load("Outfit/static/Outfit-Regular.ttf");
+    font_manager.add(&font);
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                padding: Edge::all(20.0),
+                ..Default::default()
+            },
+            WidgetChildren::default().with_child::<Markdown>((
+                Markdown::new(CONTENT),
+                MarkdownTheme {
+                    font: Some(font.id()),
+                    ..Default::default()
+                },
+            )),
+        ))
+        .observe(|trigger: Trigger<TextLinkClick>| {
+            info!("Clicked {}", trigger.link.payload);
+        })
+        .id();
+    ui_context.set_root_widget(root);
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut font_manager: ResMut<FontManager>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let font = asset_server.load("Outfit/static/Outfit-Regular.ttf");
+    font_manager.add(&font);
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                flex_direction: WidgetFlexDirection::Column,
+                padding: Edge::all(10.0),
+                ..Default::default()
+            },
+            WidgetChildren::default()
+                .with_child::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        font_size: 30.0,
+                        font: Some(font.id()),
+                        ..Default::default()
+                    },
+                    WidgetRender::Text {
+                        content: "Server ID: 7f3a-91c2-d4e8 (drag to select, Ctrl+C to copy)"
+                            .into(),
+                    },
+                    SelectableText::default(),
+                ))
+                .with_child::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        font_size: 30.0,
+                        font: Some(font.id()),
+                        ..Default::default()
+                    },
+                    WidgetRender::RichText {
+                        content: RichText::new()
+                            .with_text("Rich text is ")
+                            .with_bold_text("selectable")
+                            .with_text(" too."),
+                    },
+                    SelectableText {
+                        selection_color: Srgba::rgba_u8(80, 120, 220, 150).into(),
+                    },
+                )),
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
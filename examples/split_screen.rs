@@ -0,0 +1,116 @@
+use bevy::{
+    prelude::*, render::camera::Viewport, render::view::RenderLayers, window::WindowResized,
+};
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .add_systems(Update, set_camera_viewports)
+        .run();
+}
+
+#[derive(Component)]
+struct Player(u32);
+
+fn startup(mut commands: Commands, mut ui_context: ResMut<WoodpeckerContext>) {
+    // The left camera draws the main root.
+    commands.spawn((Camera2d, WoodpeckerView, Player(0)));
+    // The right camera draws its own root. It must not see the main UI canvas on layer 0.
+    let right_camera = commands
+        .spawn((
+            Camera2d,
+            Camera {
+                order: 1,
+                ..Default::default()
+            },
+            WoodpeckerView,
+            RenderLayers::layer(1),
+            Player(1),
+        ))
+        .id();
+
+    let left_root = spawn_player_ui(&mut commands, "Player One", colors::PRIMARY);
+    ui_context.set_root_widget(left_root);
+
+    let right_root = spawn_player_ui(
+        &mut commands,
+        "Player Two",
+        Srgba::rgb(0.48, 0.72, 0.87).into(),
+    );
+    commands
+        .entity(right_root)
+        .insert(WoodpeckerViewRoot::new(right_camera));
+}
+
+fn spawn_player_ui(commands: &mut Commands, name: &'static str, color: Color) -> Entity {
+    let root = commands.spawn_empty().id();
+    commands.entity(root).insert((
+        WoodpeckerApp,
+        WoodpeckerStyle {
+            width: Units::Percentage(100.0),
+            height: Units::Percentage(100.0),
+            flex_direction: WidgetFlexDirection::Column,
+            align_items: Some(WidgetAlignItems::Center),
+            justify_content: Some(WidgetAlignContent::Center),
+            gap: (Units::Pixels(20.0), Units::Pixels(20.0)),
+            border_color: color,
+            border: Edge::all(4.0),
+            ..Default::default()
+        },
+        WidgetRender::Quad,
+        WidgetChildren::default()
+            .with_child::<Element>((
+                Element,
+                WoodpeckerStyle {
+                    font_size: 40.0,
+                    color,
+                    ..Default::default()
+                },
+                WidgetRender::Text {
+                    content: name.into(),
+                },
+            ))
+            .with_child::<WButton>((
+                WButton,
+                WidgetChildren::default().with_child::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        font_size: 20.0,
+                        margin: Edge::all(10.0),
+                        ..Default::default()
+                    },
+                    WidgetRender::Text {
+                        content: "Ready".into(),
+                    },
+                )),
+            ))
+            .with_observe(CurrentWidget(root), move |_: Trigger<Pointer<Click>>| {
+                info!("{name} is ready!");
+            }),
+    ));
+    root
+}
+
+/// Splits the window between the two cameras whenever it is resized.
+fn set_camera_viewports(
+    windows: Query<&Window>,
+    mut resize_events: EventReader<WindowResized>,
+    mut cameras: Query<(&Player, &mut Camera)>,
+) {
+    for resize_event in resize_events.read() {
+        let Ok(window) = windows.get(resize_event.window) else {
+            continue;
+        };
+        let size = window.physical_size() / UVec2::new(2, 1);
+        for (player, mut camera) in cameras.iter_mut() {
+            camera.viewport = Some(Viewport {
+                physical_position: UVec2::new(player.0 * size.x, 0),
+                physical_size: size,
+                ..Default::default()
+            });
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+/// A procedurally generated star icon with a body and an outline.
+fn star_icon(points: usize) -> String {
+    let path = (0..points * 2)
+        .map(|index| {
+            let radius = if index % 2 == 0 { 45.0 } else { 20.0 };
+            let angle = index as f32 * std::f32::consts::PI / points as f32;
+            let (sin, cos) = angle.sin_cos();
+            format!("{:.2},{:.2}", 50.0 + sin * radius, 50.0 - cos * radius)
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+            <circle class="secondary" cx="50" cy="50" r="48" fill="none" stroke="black" stroke-width="2"/>
+            <polygon id="star" class="primary" points="{path}" fill="black"/>
+        </svg>"#
+    )
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut svg_assets: ResMut<Assets<SvgAsset>>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let icon_style = WoodpeckerStyle {
+        width: 100.0.into(),
+        height: 100.0.into(),
+        margin: Edge::all(20.0),
+        ..Default::default()
+    };
+
+    let mut children = WidgetChildren::default();
+    for points in [4, 5, 6] {
+        let icon = svg_assets.add(star_icon(points).parse::<SvgAsset>().unwrap());
+        children.add::<Element>((
+            Element,
+            icon_style,
+            WidgetRender::Svg {
+                handle: icon.clone(),
+                color: None,
+            },
+            SvgElementColors::default()
+                .with(SvgSelector::Class("primary".into()), colors::PRIMARY)
+                .with(
+                    SvgSelector::Class("secondary".into()),
+                    colors::PRIMARY_LIGHT,
+                ),
+        ));
+        // Selecting by id works the same, later selectors win over earlier ones.
+        children.add::<Element>((
+            Element,
+            icon_style,
+            WidgetRender::Svg {
+                handle: icon,
+                color: Some(colors::BACKGROUND_LIGHT),
+            },
+            SvgElementColors::default().with(SvgSelector::Id("star".into()), colors::PRIMARY),
+        ));
+    }
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                flex_wrap: WidgetFlexWrap::Wrap,
+                ..Default::default()
+            },
+            children,
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut font_manager: ResMut<FontManager>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let font = asset_server.load("Outfit/static/Outfit-Regular.ttf");
+    font_manager.add(&font);
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                flex_direction: WidgetFlexDirection::Column,
+                ..Default::default()
+            },
+            WidgetChildren::default()
+                .with_child::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        font_size: 40.0,
+                        margin: Edge::all(10.0),
+                        font: Some(font.id()),
+                        text_decoration: TextDecoration::NONE
+                            .with_underline(
+                                TextDecorationLine::DEFAULT.with_color(Srgba::RED.into()),
+                            )
+                            .with_highlight(Srgba::rgb(0.2, 0.2, 0.4).into()),
+                        ..Default::default()
+                    },
+                    WidgetRender::Text {
+                        content: "Underlined and highlighted".into(),
+                    },
+                ))
+                .with_child::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        font_size: 40.0,
+                        margin: Edge::all(10.0),
+                        font: Some(font.id()),
+                        ..Default::default()
+                    },
+                    WidgetRender::RichText {
+                        content: RichText::new()
+                            .with_decorated_text(
+                                "Struck through, ",
+                                Srgba::WHITE.into(),
+                                TextDecoration::NONE
+                                    .with_strikethrough(TextDecorationLine::DEFAULT),
+                            )
+                            .with_decorated_text(
+                                "overlined ",
+                                Srgba::GREEN.into(),
+                                TextDecoration::NONE
+                                    .with_overline(TextDecorationLine::DEFAULT.with_thickness(3.0)),
+                            )
+                            .with_color_text("and plain.", Srgba::WHITE.into()),
+                    },
+                )),
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(ClearColor(Color::srgb(0.9, 0.85, 0.6)))
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut font_manager: ResMut<FontManager>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let font = asset_server.load("Outfit/static/Outfit-Bold.ttf");
+    font_manager.add(&font);
+
+    let text_style = WoodpeckerStyle {
+        font_size: 48.0,
+        margin: Edge::all(10.0),
+        font: Some(font.id()),
+        color: Color::WHITE,
+        ..Default::default()
+    };
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                flex_direction: WidgetFlexDirection::Column,
+                ..Default::default()
+            },
+            WidgetChildren::default()
+                .with_child::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        text_stroke: Some(TextStroke::new(3.0, Color::BLACK)),
+                        ..text_style
+                    },
+                    WidgetRender::Text {
+                        content: "Outlined HUD text".into(),
+                    },
+                ))
+                .with_child::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        text_shadow: Some(TextDropShadow::new(
+                            Vec2::new(3.0, 3.0),
+                            0.0,
+                            Color::BLACK.with_alpha(0.8),
+                        )),
+                        ..text_style
+                    },
+                    WidgetRender::Text {
+                        content: "Hard shadow".into(),
+                    },
+                ))
+                .with_child::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        text_stroke: Some(TextStroke::new(2.0, Srgba::rgb(0.1, 0.1, 0.3).into())),
+                        text_shadow: Some(TextDropShadow::new(
+                            Vec2::new(0.0, 4.0),
+                            6.0,
+                            Color::BLACK.with_alpha(0.6),
+                        )),
+                        ..text_style
+                    },
+                    WidgetRender::RichText {
+                        content: RichText::new()
+                            .with_text("Outline and ")
+                            .with_styled_text(
+                                "blurred",
+                                TextSpanStyle::default()
+                                    .with_color(Srgba::rgb(1.0, 0.8, 0.2).into()),
+                            )
+                            .with_text(" shadow"),
+                    },
+                )),
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
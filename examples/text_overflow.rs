@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+const TEXT: &str =
+    "Sword of a Thousand Truths, a legendary blade forged in the depths of the mountain.";
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .add_systems(Update, log_truncated)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut font_manager: ResMut<FontManager>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let font = asset_server.load("Outfit/static/Outfit-Regular.ttf");
+    font_manager.add(&font);
+
+    let mut children = WidgetChildren::default();
+    for (text_overflow, max_lines) in [
+        (TextOverflow::Clip, Some(1)),
+        (TextOverflow::Ellipsis, Some(1)),
+        (TextOverflow::Ellipsis, Some(2)),
+        (TextOverflow::Fade, Some(1)),
+    ] {
+        children.add::<Element>((
+            Element,
+            WoodpeckerStyle {
+                width: 300.0.into(),
+                margin: Edge::all(10.0),
+                font_size: 24.0,
+                font: Some(font.id()),
+                text_overflow,
+                max_lines,
+                ..Default::default()
+            },
+            WidgetRender::Text {
+                content: TEXT.into(),
+            },
+        ));
+    }
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                flex_direction: WidgetFlexDirection::Column,
+                ..Default::default()
+            },
+            children,
+        ))
+        .id();
+    ui_context.set_root_widget(root);
+}
+
+fn log_truncated(query: Query<&TruncatedText, Added<TruncatedText>>) {
+    for truncated in query.iter() {
+        info!("Truncated: {}", truncated.full_text);
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .add_systems(Startup, startup)
+        .run();
+}
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut font_manager: ResMut<FontManager>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.spawn((Camera2d, WoodpeckerView));
+
+    let font = asset_server.load("Outfit/static/Outfit-Regular.ttf");
+    font_manager.add(&font);
+
+    let root = commands
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                padding: Edge::all(40.0),
+                ..Default::default()
+            },
+            WidgetChildren::default().with_child::<Element>((
+                Element,
+                WoodpeckerStyle {
+                    width: Units::Pixels(600.0),
+                    padding: Edge::all(20.0),
+                    background_color: Srgba::hex("1e1e2e").unwrap().into(),
+                    border_radius: Corner::all(Units::Pixels(10.0)),
+                    ..Default::default()
+                },
+                WidgetRender::Quad,
+                WidgetChildren::default().with_child::<Typewriter>((
+                    Typewriter {
+                        effect: TypewriterEffect::Bounce { height: 6.0 },
+                        ..Typewriter::new(
+                            RichText::new()
+                                .with_text("Halt, traveler! ")
+                                .with_styled_text(
+                                    "The bridge",
+                                    TextSpanStyle::BOLD.with_color(Srgba::RED.into()),
+                                )
+                                .with_text(
+                                    " is closed until morning. Click to skip, and come back \
+                                     when the sun is up.",
+                                ),
+                        )
+                    },
+                    WoodpeckerStyle {
+                        font: Some(font.id()),
+                        font_size: 28.0,
+                        width: Units::Percentage(100.0),
+                        ..Default::default()
+                    },
+                )),
+            )),
+        ))
+        .observe(|trigger: Trigger<TypewriterCharacter>| {
+            if !trigger.character.is_whitespace() {
+                info!("Blip {}", trigger.character);
+            }
+        })
+        .observe(|_trigger: Trigger<TypewriterFinished>| {
+            info!("Dialogue finished");
+        })
+        .id();
+    ui_context.set_root_widget(root);
+}
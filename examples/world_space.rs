@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use woodpecker_ui::prelude::*;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(WoodpeckerUIPlugin::default())
+        .init_resource::<Spinning>()
+        .add_systems(Startup, startup)
+        .add_systems(Update, spin)
+        .run();
+}
+
+/// Whether the panel spins, toggled from the UI on the panel itself.
+#[derive(Resource, Default)]
+struct Spinning(bool);
+
+#[derive(Component)]
+struct Panel;
+
+fn startup(
+    mut commands: Commands,
+    mut ui_context: ResMut<WoodpeckerContext>,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 0.5, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(2.0, 4.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(6.0, 6.0))),
+        MeshMaterial3d(materials.add(Color::from(colors::BACKGROUND_LIGHT))),
+        Transform::from_xyz(0.0, -1.0, 0.0),
+    ));
+
+    // The UI is laid out at 400x300 and rendered at twice the resolution to stay sharp.
+    let image = WoodpeckerTextureTarget::create_image(&mut images, UVec2::new(800, 600));
+    let root = commands.spawn_empty().id();
+    commands.entity(root).insert((
+        WoodpeckerApp,
+        WoodpeckerTextureTarget::new(image.clone()).with_scale(2.0),
+        WoodpeckerStyle {
+            width: Units::Percentage(100.0),
+            height: Units::Percentage(100.0),
+            flex_direction: WidgetFlexDirection::Column,
+            align_items: Some(WidgetAlignItems::Center),
+            justify_content: Some(WidgetAlignContent::SpaceEvenly),
+            background_color: colors::BACKGROUND,
+            border_radius: Corner::all(20.0),
+            ..Default::default()
+        },
+        WidgetRender::Quad,
+        WidgetChildren::default()
+            .with_child::<Element>((
+                Element,
+                WoodpeckerStyle {
+                    font_size: 32.0,
+                    ..Default::default()
+                },
+                WidgetRender::Text {
+                    content: "Control Panel".into(),
+                },
+            ))
+            .with_child::<Element>((
+                Element,
+                WoodpeckerStyle {
+                    align_items: Some(WidgetAlignItems::Center),
+                    gap: (Units::Pixels(10.0), Units::Pixels(10.0)),
+                    ..Default::default()
+                },
+                WidgetChildren::default()
+                    .with_child::<Element>((
+                        Element,
+                        WidgetRender::Text {
+                            content: "Spin".into(),
+                        },
+                    ))
+                    .with_child::<Toggle>(Toggle)
+                    .with_observe(
+                        CurrentWidget(root),
+                        |trigger: Trigger<Change<ToggleChanged>>,
+                         mut spinning: ResMut<Spinning>| {
+                            spinning.0 = trigger.data.checked;
+                        },
+                    ),
+            ))
+            .with_child::<Checkbox>(Checkbox),
+    ));
+    ui_context.set_root_widget(root);
+
+    // Pointer hits on this mesh are forwarded to the UI through the UV coordinates of the hit.
+    commands.spawn((
+        Panel,
+        Mesh3d(meshes.add(Rectangle::new(2.0, 1.5))),
+        MeshMaterial3d(materials.add(StandardMaterial {
+            base_color_texture: Some(image),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            cull_mode: None,
+            ..Default::default()
+        })),
+        WoodpeckerTextureMesh { root },
+    ));
+}
+
+fn spin(time: Res<Time>, spinning: Res<Spinning>, mut panels: Query<&mut Transform, With<Panel>>) {
+    if !spinning.0 {
+        return;
+    }
+    for mut transform in panels.iter_mut() {
+        transform.rotate_y(time.delta_secs() * 0.5);
+    }
+}
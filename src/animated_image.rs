@@ -0,0 +1,243 @@
+use std::{io::Cursor, time::Duration};
+
+use ::image::{codecs, AnimationDecoder, Frames};
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext, RenderAssetUsages},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+use thiserror::Error;
+
+use crate::{hook_helper::PreviousWidget, render::WidgetRender};
+
+/// A single frame of an [`AnimatedImage`].
+#[derive(Debug, Clone, Reflect)]
+pub struct AnimatedImageFrame {
+    /// The image of the frame, the size of the whole animation.
+    pub image: Handle<Image>,
+    /// How long the frame is shown.
+    pub delay: Duration,
+}
+
+/// An animated GIF, APNG or WebP image.
+///
+/// Loaded from `.gif`, `.webp` and `.apng` files, APNG files need the `.apng` extension so they
+/// aren't loaded as static png images. Every frame is its own [`Image`] asset so frames are
+/// cached by the image manager like other images. Play it with an [`AnimatedImagePlayer`].
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct AnimatedImage {
+    /// The frames in playback order.
+    pub frames: Vec<AnimatedImageFrame>,
+}
+
+/// Errors while loading an [`AnimatedImage`].
+#[derive(Error, Debug)]
+pub enum AnimatedImageError {
+    /// The file couldn't be read.
+    #[error("Failed to read animated image: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file couldn't be decoded.
+    #[error("Failed to decode animated image: {0}")]
+    Image(#[from] ::image::ImageError),
+    /// The file extension isn't a supported animation format.
+    #[error("Unsupported animated image extension: '{0}'")]
+    UnsupportedExtension(String),
+}
+
+#[derive(Default)]
+pub(crate) struct AnimatedImageLoader;
+
+impl AssetLoader for AnimatedImageLoader {
+    type Asset = AnimatedImage;
+
+    type Settings = ();
+
+    type Error = AnimatedImageError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let ext = load_context
+            .path()
+            .extension()
+            .and_then(std::ffi::OsStr::to_str)
+            .unwrap_or_default()
+            .to_lowercase();
+
+        let cursor = Cursor::new(bytes);
+        let frames: Frames = match ext.as_str() {
+            "gif" => codecs::gif::GifDecoder::new(cursor)?.into_frames(),
+            "webp" => codecs::webp::WebPDecoder::new(cursor)?.into_frames(),
+            "apng" => codecs::png::PngDecoder::new(cursor)?.apng().into_frames(),
+            ext => return Err(AnimatedImageError::UnsupportedExtension(ext.to_string())),
+        };
+
+        let mut animated_image = AnimatedImage { frames: vec![] };
+        for (index, frame) in frames.collect_frames()?.into_iter().enumerate() {
+            let delay = Duration::from(frame.delay());
+            let buffer = frame.into_buffer();
+            let image = Image::new(
+                Extent3d {
+                    width: buffer.width(),
+                    height: buffer.height(),
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                buffer.into_raw(),
+                TextureFormat::Rgba8UnormSrgb,
+                // Frames are only drawn by vello which reads them on the cpu.
+                RenderAssetUsages::MAIN_WORLD,
+            );
+            animated_image.frames.push(AnimatedImageFrame {
+                image: load_context.add_labeled_asset(format!("frame{index}"), image),
+                // Like browsers, very short delays are treated as unset.
+                delay: if delay < Duration::from_millis(10) {
+                    Duration::from_millis(100)
+                } else {
+                    delay
+                },
+            });
+        }
+        Ok(animated_image)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["gif", "webp", "apng"]
+    }
+}
+
+/// Plays an [`AnimatedImage`] by showing its current frame as the [`WidgetRender::Image`] of
+/// the widget.
+///
+/// ```ignore
+/// commands.spawn((
+///     Element,
+///     WoodpeckerStyle::default(),
+///     AnimatedImagePlayer::new(asset_server.load("spinner.gif")).with_speed(2.0),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[require(WidgetRender = WidgetRender::Image { handle: Handle::default() })]
+pub struct AnimatedImagePlayer {
+    /// The animation which is played.
+    pub handle: Handle<AnimatedImage>,
+    /// Is the animation playing?
+    pub playing: bool,
+    /// How fast the animation plays, 1.0 is the speed of the file.
+    pub speed: f32,
+    /// How often the animation plays before it stops on its last frame, `None` repeats forever.
+    pub loop_count: Option<u32>,
+    frame: usize,
+    frame_time: Duration,
+    loops: u32,
+}
+
+impl AnimatedImagePlayer {
+    /// Creates a new player which repeats the animation forever.
+    pub fn new(handle: Handle<AnimatedImage>) -> Self {
+        Self {
+            handle,
+            playing: true,
+            speed: 1.0,
+            loop_count: None,
+            frame: 0,
+            frame_time: Duration::ZERO,
+            loops: 0,
+        }
+    }
+
+    /// Sets how fast the animation plays.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Sets how often the animation plays before it stops.
+    pub fn with_loop_count(mut self, loop_count: u32) -> Self {
+        self.loop_count = Some(loop_count);
+        self
+    }
+
+    /// Resumes the animation.
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    /// Pauses the animation on the current frame.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Plays the animation from its first frame.
+    pub fn restart(&mut self) {
+        self.frame = 0;
+        self.frame_time = Duration::ZERO;
+        self.loops = 0;
+        self.playing = true;
+    }
+
+    /// The index of the frame which is shown.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns true if the animation played `loop_count` times.
+    pub fn is_finished(&self) -> bool {
+        self.loop_count.is_some_and(|count| self.loops >= count)
+    }
+
+    /// Moves the animation forward by the given time.
+    fn advance(&mut self, delta: Duration, animation: &AnimatedImage) {
+        self.frame_time += delta.mul_f32(self.speed.max(0.0));
+        while let Some(frame) = animation.frames.get(self.frame) {
+            if self.frame_time < frame.delay {
+                break;
+            }
+            self.frame_time -= frame.delay;
+            if self.frame + 1 < animation.frames.len() {
+                self.frame += 1;
+                continue;
+            }
+            self.loops += 1;
+            if self.is_finished() {
+                self.frame_time = Duration::ZERO;
+                self.playing = false;
+                break;
+            }
+            self.frame = 0;
+        }
+    }
+}
+
+/// Advances animated image players and shows their current frame.
+pub(crate) fn update_animated_images(
+    time: Res<Time>,
+    animated_images: Res<Assets<AnimatedImage>>,
+    mut query: Query<(&mut AnimatedImagePlayer, &mut WidgetRender), Without<PreviousWidget>>,
+) {
+    for (mut player, mut widget_render) in query.iter_mut() {
+        let Some(animation) = animated_images.get(&player.handle) else {
+            continue;
+        };
+        if player.playing && !player.is_finished() {
+            player.advance(time.delta(), animation);
+        }
+        let Some(frame) = animation
+            .frames
+            .get(player.frame)
+            .or(animation.frames.last())
+        else {
+            continue;
+        };
+        if !matches!(&*widget_render, WidgetRender::Image { handle } if *handle == frame.image) {
+            *widget_render = WidgetRender::Image {
+                handle: frame.image.clone(),
+            };
+        }
+    }
+}
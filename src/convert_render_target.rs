@@ -26,7 +26,9 @@ impl Plugin for ConvertRenderTargetPlugin {
             .add_plugins(ExtractResourcePlugin::<RenderTargetImages>::default())
             .add_systems(Update, create_shaders);
 
-        let render_app = app.sub_app_mut(RenderApp);
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
 
         render_app.init_resource::<ConvertPipeline>().add_systems(
             Render,
@@ -0,0 +1,440 @@
+use bevy::prelude::*;
+use bevy_vello::{
+    vello::{
+        self,
+        kurbo::{self, Affine, BezPath},
+        peniko::{self, Brush},
+    },
+    VelloScene,
+};
+use skrifa::{
+    instance::{LocationRef, Size},
+    outline::{DrawSettings, OutlinePen},
+    raw::types::F2Dot14,
+    FontRef, GlyphId, MetadataProvider,
+};
+
+use crate::{
+    lottie::{LottieAsset, LottieManager},
+    prelude::{WidgetLayout, WidgetRenderCustom, WoodpeckerStyle},
+    svg::{SvgAsset, SvgManager, SvgSelector},
+};
+
+/// A single backend neutral draw operation.
+///
+/// All coordinates are in physical pixels of the render target.
+#[derive(Clone, Debug)]
+pub enum DrawOp {
+    /// Fills a rectangle.
+    Rect {
+        /// The rectangle to fill.
+        rect: kurbo::Rect,
+        /// The fill color.
+        color: Color,
+    },
+    /// Fills a rounded rectangle.
+    RoundedRect {
+        /// The rounded rectangle to fill.
+        rect: kurbo::RoundedRect,
+        /// The fill color.
+        color: Color,
+    },
+    /// The border of a quad.
+    ///
+    /// Like the rest of Woodpecker UI a border fills its whole `outer` shape, the background of
+    /// the quad is drawn on top of it inside of `inner`.
+    Border {
+        /// The outer edge of the border.
+        outer: kurbo::RoundedRect,
+        /// The inner edge of the border.
+        inner: kurbo::RoundedRect,
+        /// The border color.
+        color: Color,
+    },
+    /// A run of glyphs sharing a font, size and brush.
+    GlyphRun(GlyphRun),
+    /// Fills or strokes a path.
+    Path {
+        /// The path in local coordinates.
+        path: BezPath,
+        /// Maps the path into the render target.
+        transform: Affine,
+        /// The brush the path is painted with, in local coordinates.
+        brush: Brush,
+        /// Strokes the path instead of filling it.
+        stroke: Option<kurbo::Stroke>,
+    },
+    /// Draws an image.
+    Image {
+        /// The image to draw.
+        image: peniko::Image,
+        /// Maps image pixels into the render target.
+        transform: Affine,
+        /// A color the image is multiplied with.
+        tint: Option<Color>,
+    },
+    /// Draws an svg asset.
+    Svg {
+        /// The svg asset.
+        handle: Handle<SvgAsset>,
+        /// An optional color that replaces paths and fills within the svg.
+        color: Option<Color>,
+        /// Colors which replace the paths and fills of selected elements.
+        element_colors: Vec<(SvgSelector, Color)>,
+        /// Maps svg units into the render target.
+        transform: Affine,
+    },
+    /// Draws a frame of a Lottie animation. Only backends which render with vello can draw these.
+    Lottie {
+        /// The Lottie asset.
+        handle: Handle<LottieAsset>,
+        /// The frame which is drawn.
+        frame: f64,
+        /// Replaces the fill and stroke colors of layers by their name.
+        colors: Vec<(String, Color)>,
+        /// Maps animation units into the render target.
+        transform: Affine,
+    },
+    /// A custom vello render. Only backends which render with vello can draw these.
+    Custom {
+        /// The custom render function.
+        render: WidgetRenderCustom,
+        /// The layout of the widget.
+        layout: WidgetLayout,
+        /// The styles of the widget.
+        styles: Box<WoodpeckerStyle>,
+        /// The scale factor of the render target.
+        scale: f32,
+    },
+    /// Starts a layer which clips its content and applies opacity to it as a group.
+    PushLayer {
+        /// The clip shape of the layer.
+        clip: kurbo::RoundedRect,
+        /// The opacity of the layer.
+        opacity: f32,
+    },
+    /// Ends the last pushed layer.
+    PopLayer,
+}
+
+/// A positioned run of glyphs.
+#[derive(Clone, Debug)]
+pub struct GlyphRun {
+    /// The font the glyphs belong to.
+    pub font: peniko::Font,
+    /// The font size in pixels.
+    pub font_size: f32,
+    /// Variable font coordinates.
+    pub normalized_coords: Vec<i16>,
+    /// The transform applied to every glyph.
+    pub transform: Affine,
+    /// The brush the glyphs are filled with.
+    pub brush: Brush,
+    /// Strokes the glyph outlines with this width in pixels instead of filling them.
+    pub stroke: Option<f32>,
+    /// The glyphs and their positions.
+    pub glyphs: Vec<vello::Glyph>,
+}
+
+impl GlyphRun {
+    /// Builds the outlines of every glyph in the run as a single path.
+    pub fn outline(&self) -> BezPath {
+        let mut path = BezPath::new();
+        let Ok(font_ref) = FontRef::from_index(self.font.data.as_ref(), self.font.index) else {
+            return path;
+        };
+        let outlines = font_ref.outline_glyphs();
+        let coords = self
+            .normalized_coords
+            .iter()
+            .map(|coord| F2Dot14::from_bits(*coord))
+            .collect::<Vec<_>>();
+        let size = Size::new(self.font_size);
+
+        for glyph in self.glyphs.iter() {
+            let Some(outline) = outlines.get(GlyphId::new(glyph.id)) else {
+                continue;
+            };
+            let mut pen = GlyphPen {
+                path: &mut path,
+                transform: self.transform
+                    * Affine::translate(kurbo::Vec2::new(glyph.x as f64, glyph.y as f64)),
+            };
+            let _ = outline.draw(
+                DrawSettings::unhinted(size, LocationRef::new(&coords)),
+                &mut pen,
+            );
+        }
+        path
+    }
+
+    /// The area painted by the run, the glyph outlines or the stroke around them.
+    pub fn shape(&self) -> BezPath {
+        let outline = self.outline();
+        match self.stroke {
+            Some(width) => kurbo::stroke(
+                outline,
+                &text_stroke(width),
+                &kurbo::StrokeOpts::default(),
+                0.1,
+            ),
+            None => outline,
+        }
+    }
+}
+
+/// The draw operations of a UI root for a single frame.
+///
+/// Add this component to a UI root to have it filled in every frame. The list can be replayed
+/// with any [`UiRenderBackend`].
+#[derive(Component, Clone, Debug, Default)]
+pub struct DisplayList {
+    /// The draw operations in painting order.
+    pub ops: Vec<DrawOp>,
+    /// The size of the render target in physical pixels.
+    pub size: Vec2,
+}
+
+/// Something which can draw a display list.
+pub trait UiRenderBackend {
+    /// Draws a single operation.
+    fn draw(&mut self, op: &DrawOp);
+
+    /// Draws every operation in order.
+    fn draw_all(&mut self, ops: &[DrawOp]) {
+        for op in ops {
+            self.draw(op);
+        }
+    }
+}
+
+/// The default backend which encodes draw operations into a vello scene.
+pub struct VelloBackend<'a> {
+    scene: &'a mut VelloScene,
+    svg_manager: &'a mut SvgManager,
+    svg_assets: &'a Assets<SvgAsset>,
+    lottie_manager: &'a mut LottieManager,
+    lottie_assets: &'a Assets<LottieAsset>,
+}
+
+impl<'a> VelloBackend<'a> {
+    /// Creates a backend which appends to the given scene.
+    ///
+    /// The svg and Lottie managers are the [`SvgManager`] and [`LottieManager`] resources of the
+    /// app.
+    pub fn new(
+        scene: &'a mut VelloScene,
+        svg_manager: &'a mut SvgManager,
+        svg_assets: &'a Assets<SvgAsset>,
+        lottie_manager: &'a mut LottieManager,
+        lottie_assets: &'a Assets<LottieAsset>,
+    ) -> Self {
+        Self {
+            scene,
+            svg_manager,
+            svg_assets,
+            lottie_manager,
+            lottie_assets,
+        }
+    }
+}
+
+impl UiRenderBackend for VelloBackend<'_> {
+    fn draw(&mut self, op: &DrawOp) {
+        match op {
+            DrawOp::Rect { rect, color } => {
+                self.scene.fill(
+                    peniko::Fill::NonZero,
+                    Affine::default(),
+                    to_vello_color(*color),
+                    None,
+                    rect,
+                );
+            }
+            DrawOp::RoundedRect { rect, color }
+            | DrawOp::Border {
+                outer: rect, color, ..
+            } => {
+                self.scene.fill(
+                    peniko::Fill::NonZero,
+                    Affine::default(),
+                    to_vello_color(*color),
+                    None,
+                    rect,
+                );
+            }
+            DrawOp::GlyphRun(run) => {
+                let glyphs = self
+                    .scene
+                    .draw_glyphs(&run.font)
+                    .hint(true)
+                    .font_size(run.font_size)
+                    .transform(run.transform)
+                    .normalized_coords(&run.normalized_coords)
+                    .brush(&run.brush);
+                match run.stroke {
+                    Some(width) => glyphs.draw(&text_stroke(width), run.glyphs.iter().copied()),
+                    None => glyphs.draw(peniko::Fill::NonZero, run.glyphs.iter().copied()),
+                }
+            }
+            DrawOp::Path {
+                path,
+                transform,
+                brush,
+                stroke,
+            } => match stroke {
+                Some(stroke) => self.scene.stroke(stroke, *transform, brush, None, path),
+                None => self
+                    .scene
+                    .fill(peniko::Fill::NonZero, *transform, brush, None, path),
+            },
+            DrawOp::Image {
+                image,
+                transform,
+                tint,
+            } => {
+                let Some(tint) = tint else {
+                    self.scene.draw_image(image, *transform);
+                    return;
+                };
+                // The tint is multiplied onto the image inside of an isolated group so it only
+                // covers the pixels of the image.
+                let bounds = kurbo::Rect::new(0.0, 0.0, image.width as f64, image.height as f64);
+                self.scene
+                    .push_layer(peniko::BlendMode::default(), 1.0, *transform, &bounds);
+                self.scene.draw_image(image, *transform);
+                self.scene.push_layer(
+                    peniko::BlendMode::new(peniko::Mix::Multiply, peniko::Compose::SrcAtop),
+                    1.0,
+                    *transform,
+                    &bounds,
+                );
+                self.scene.fill(
+                    peniko::Fill::NonZero,
+                    *transform,
+                    to_vello_color(*tint),
+                    None,
+                    &bounds,
+                );
+                self.scene.pop_layer();
+                self.scene.pop_layer();
+            }
+            DrawOp::Svg {
+                handle,
+                color,
+                element_colors,
+                transform,
+            } => {
+                let Some(svg_scene) =
+                    self.svg_manager
+                        .get_cached(handle, self.svg_assets, *color, element_colors)
+                else {
+                    return;
+                };
+                self.scene.append(&svg_scene, Some(*transform));
+            }
+            DrawOp::Lottie {
+                handle,
+                frame,
+                colors,
+                transform,
+            } => {
+                let Some(composition) =
+                    self.lottie_manager
+                        .get_cached(handle, self.lottie_assets, colors)
+                else {
+                    return;
+                };
+                self.lottie_manager
+                    .render(self.scene, &composition, *frame, *transform);
+            }
+            DrawOp::Custom {
+                render,
+                layout,
+                styles,
+                scale,
+            } => {
+                render.render(self.scene, layout, styles, *scale);
+            }
+            DrawOp::PushLayer { clip, opacity } => {
+                let mask_blend =
+                    peniko::BlendMode::new(peniko::Mix::Normal, peniko::Compose::SrcOver);
+                self.scene
+                    .push_layer(mask_blend, *opacity, Affine::default(), clip);
+            }
+            DrawOp::PopLayer => {
+                self.scene.pop_layer();
+            }
+        }
+    }
+}
+
+/// Returns the area painted by a [`DrawOp::Path`] in render target coordinates.
+pub(crate) fn painted_path(
+    path: &BezPath,
+    transform: Affine,
+    stroke: Option<&kurbo::Stroke>,
+) -> BezPath {
+    let mut painted = match stroke {
+        Some(stroke) => kurbo::stroke(path, stroke, &kurbo::StrokeOpts::default(), 0.1),
+        None => path.clone(),
+    };
+    painted.apply_affine(transform);
+    painted
+}
+
+/// Returns the factors the red, green and blue channels of an image are multiplied with for
+/// a tint, blending between no tint and the full tint by its alpha.
+pub(crate) fn tint_factors(tint: Color) -> [f32; 3] {
+    let tint = tint.to_srgba();
+    [tint.red, tint.green, tint.blue].map(|channel| 1.0 - tint.alpha + tint.alpha * channel)
+}
+
+/// Converts a bevy color into a vello color.
+pub(crate) fn to_vello_color(color: Color) -> peniko::Color {
+    let color = color.to_srgba();
+    peniko::Color::new([color.red, color.green, color.blue, color.alpha])
+}
+
+/// Collects glyph outlines into a path, flipping them into the y down space of the UI.
+struct GlyphPen<'a> {
+    path: &'a mut BezPath,
+    transform: Affine,
+}
+
+impl GlyphPen<'_> {
+    fn point(&self, x: f32, y: f32) -> kurbo::Point {
+        self.transform * kurbo::Point::new(x as f64, -y as f64)
+    }
+}
+
+impl OutlinePen for GlyphPen<'_> {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.path.move_to(p);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.path.line_to(p);
+    }
+
+    fn quad_to(&mut self, cx0: f32, cy0: f32, x: f32, y: f32) {
+        let (c, p) = (self.point(cx0, cy0), self.point(x, y));
+        self.path.quad_to(c, p);
+    }
+
+    fn curve_to(&mut self, cx0: f32, cy0: f32, cx1: f32, cy1: f32, x: f32, y: f32) {
+        let (c0, c1, p) = (self.point(cx0, cy0), self.point(cx1, cy1), self.point(x, y));
+        self.path.curve_to(c0, c1, p);
+    }
+
+    fn close(&mut self) {
+        self.path.close_path();
+    }
+}
+
+/// The stroke style of outlined glyphs, round joins keep sharp corners from spiking.
+fn text_stroke(width: f32) -> kurbo::Stroke {
+    kurbo::Stroke::new(width as f64).with_join(kurbo::Join::Round)
+}
@@ -1,14 +1,22 @@
-use std::sync::Arc;
+use std::{
+    borrow::Cow,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::Arc,
+};
 
 use bevy::{
+    asset::uuid::Uuid,
     platform::collections::{HashMap, HashSet},
     prelude::*,
 };
 use bevy_vello::{prelude::VelloFont, vello::peniko::Brush};
+use parley::fontique::{FallbackKey, FamilyId, GenericFamily, Script};
 
 use crate::{
     layout::{measure::LayoutMeasure, system::measure_text},
     prelude::WidgetLayout,
+    render::WidgetRender,
+    render_cache::RenderCache,
     styles::WoodpeckerStyle,
     DefaultFont,
 };
@@ -29,13 +37,33 @@ pub enum TextAlign {
     End,
 }
 
+/// Where a fallback chain is used.
+#[derive(Debug, Clone, Copy)]
+enum FallbackTarget {
+    /// Text in a script and optionally a locale.
+    Script(FallbackKey),
+    /// Emoji, in any script.
+    Emoji,
+}
+
 /// Used to keep track of fonts and to measure text with a given font
-/// Internally this uses cosmic text to layout and measure text.
+/// Internally this uses parley to layout and measure text.
+///
+/// Glyphs missing from the font of a widget are looked up in the fallback chains, first in
+/// [`FontManager::add_fallbacks`], then in [`FontManager::add_script_fallbacks`] for the script
+/// of the text and finally in the system fonts if they are enabled. Color emoji fonts (COLR or
+/// bitmap) added with [`FontManager::add_emoji_fallbacks`] are drawn in color by vello.
 #[derive(Resource)]
 pub struct FontManager {
     font_data: HashMap<Handle<VelloFont>, Vec<u8>>,
     vello_to_family: HashMap<Handle<VelloFont>, String>,
     fonts: HashSet<Handle<VelloFont>>,
+    fallbacks: Vec<String>,
+    fallback_chains: Vec<(FallbackTarget, Vec<String>)>,
+    locale: Option<&'static str>,
+    locales: HashSet<&'static str>,
+    /// Set when fonts, fallbacks or the locale changed so text gets laid out again.
+    fonts_changed: bool,
     /// The parley font context for parley shaping/etc..
     pub font_cx: parley::FontContext,
     /// The parley layout context for parley shaping/etc..
@@ -44,17 +72,40 @@ pub struct FontManager {
 
 impl Default for FontManager {
     fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl FontManager {
+    /// Creates a new font manager.
+    ///
+    /// When `system_fonts` is false only fonts loaded as assets are used, which keeps text looking
+    /// the same on every machine. Insert it before adding the
+    /// [`crate::prelude::WoodpeckerUIPlugin`] to replace the default manager, which uses the
+    /// system fonts on desktop platforms.
+    pub fn new(system_fonts: bool) -> Self {
         Self {
             vello_to_family: Default::default(),
             font_data: Default::default(),
             fonts: HashSet::default(),
-            font_cx: parley::FontContext::new(),
+            fallbacks: Vec::new(),
+            fallback_chains: Vec::new(),
+            locale: None,
+            locales: HashSet::default(),
+            fonts_changed: false,
+            font_cx: parley::FontContext {
+                collection: parley::fontique::Collection::new(
+                    parley::fontique::CollectionOptions {
+                        shared: false,
+                        system_fonts,
+                    },
+                ),
+                source_cache: Default::default(),
+            },
             layout_cx: parley::LayoutContext::new(),
         }
     }
-}
 
-impl FontManager {
     /// Returns a parley driver for the given engine.
     pub fn driver<'a>(
         &'a mut self,
@@ -76,6 +127,161 @@ impl FontManager {
         self.fonts.insert(handle.clone());
     }
 
+    /// Looks up a font by its family name, for example "Noto Sans".
+    ///
+    /// Fonts loaded as assets are found once they finished loading, system fonts are found if
+    /// they are enabled. The returned id can be used as [`WoodpeckerStyle::font`].
+    pub fn font(&mut self, family: &str) -> Option<AssetId<VelloFont>> {
+        if let Some((handle, _)) = self
+            .vello_to_family
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(family))
+        {
+            return Some(handle.id());
+        }
+
+        // System fonts have no asset so they get an id derived from their name.
+        let collection = &mut self.font_cx.collection;
+        let family_id = collection.family_id(family)?;
+        let family_name = collection.family_name(family_id)?.to_string();
+        let mut hasher = DefaultHasher::new();
+        family_name.hash(&mut hasher);
+        let id = AssetId::Uuid {
+            uuid: Uuid::from_u64_pair(u64::from_be_bytes(*b"woodpeck"), hasher.finish()),
+        };
+        self.vello_to_family.insert(Handle::Weak(id), family_name);
+        Some(id)
+    }
+
+    /// Adds font families which are tried, in order, for glyphs missing from the font of a
+    /// widget.
+    pub fn add_fallbacks(&mut self, families: &[&str]) {
+        self.fallbacks
+            .extend(families.iter().map(|family| family.to_string()));
+        self.fonts_changed = true;
+    }
+
+    /// Adds font families which are tried, in order, for text in a script and optionally a
+    /// locale.
+    ///
+    /// The script is an ISO 15924 code such as "Hani", "Arab" or "Cyrl". The locale is a BCP 47
+    /// language tag such as "ja" or "zh-TW" and is matched against [`FontManager::set_locale`].
+    /// Families which haven't loaded yet are added once they do.
+    pub fn add_script_fallbacks(&mut self, script: &str, locale: Option<&str>, families: &[&str]) {
+        let script = Script::from(script);
+        let key = match locale {
+            Some(locale) => FallbackKey::from((script, locale)),
+            None => FallbackKey::new(script, None),
+        };
+        self.push_fallbacks(FallbackTarget::Script(key), families);
+    }
+
+    /// Adds font families which are tried, in order, for emoji.
+    ///
+    /// Color emoji fonts with COLR or bitmap (CBDT/sbix) glyphs are drawn in color by the vello
+    /// renderer, other backends draw their outlines in the text color.
+    pub fn add_emoji_fallbacks(&mut self, families: &[&str]) {
+        self.push_fallbacks(FallbackTarget::Emoji, families);
+    }
+
+    /// Sets the locale of all text, used to pick the fallback chain and locale specific glyphs.
+    pub fn set_locale(&mut self, locale: Option<&str>) {
+        if self.locale == locale {
+            return;
+        }
+        // Parley styles need a static locale and an app only uses a few of them.
+        self.locale = locale.map(|locale| match self.locales.get(locale) {
+            Some(locale) => *locale,
+            None => {
+                let locale: &'static str = Box::leak(locale.to_string().into_boxed_str());
+                self.locales.insert(locale);
+                locale
+            }
+        });
+        self.fonts_changed = true;
+    }
+
+    /// The locale of all text.
+    pub fn locale(&self) -> Option<&'static str> {
+        self.locale
+    }
+
+    /// Returns the font stack of a font followed by the fallback families, or `None` if the font
+    /// hasn't been loaded yet.
+    pub fn try_font_stack(
+        &self,
+        vello_font: &AssetId<VelloFont>,
+    ) -> Option<parley::FontStack<'static>> {
+        let family = self.vello_to_family.get(&Handle::Weak(*vello_font))?;
+        Some(self.stack_of(std::iter::once(family.clone())))
+    }
+
+    /// Returns the font stack of a widget font, falling back to the default font while the
+    /// widget font is loading, and to the fallback families while neither is loaded.
+    pub fn font_stack_or_default(
+        &self,
+        font: Option<&AssetId<VelloFont>>,
+        default_font: &AssetId<VelloFont>,
+    ) -> parley::FontStack<'static> {
+        font.and_then(|font| self.try_font_stack(font))
+            .or_else(|| self.try_font_stack(default_font))
+            .unwrap_or_else(|| self.stack_of(std::iter::empty()))
+    }
+
+    /// The given families followed by the fallback families and a generic sans-serif family.
+    fn stack_of(&self, families: impl Iterator<Item = String>) -> parley::FontStack<'static> {
+        let families = families
+            .chain(self.fallbacks.iter().cloned())
+            .map(|family| parley::FontFamily::Named(Cow::Owned(family)))
+            .chain(std::iter::once(parley::FontFamily::Generic(
+                parley::GenericFamily::SansSerif,
+            )))
+            .collect::<Vec<_>>();
+        parley::FontStack::List(Cow::Owned(families))
+    }
+
+    fn push_fallbacks(&mut self, target: FallbackTarget, families: &[&str]) {
+        self.fallback_chains.push((
+            target,
+            families.iter().map(|family| family.to_string()).collect(),
+        ));
+        self.apply_fallbacks();
+    }
+
+    /// Puts the fallback families that are loaded in front of the default fallbacks.
+    ///
+    /// Chains are applied from last to first so the families added first end up in front.
+    fn apply_fallbacks(&mut self) {
+        self.fonts_changed = true;
+        let collection = &mut self.font_cx.collection;
+        for (target, families) in self.fallback_chains.iter().rev() {
+            let ids = families
+                .iter()
+                .filter_map(|family| collection.family_id(family))
+                .collect::<Vec<FamilyId>>();
+            let defaults = match *target {
+                FallbackTarget::Script(key) => {
+                    collection.fallback_families(key).collect::<Vec<_>>()
+                }
+                FallbackTarget::Emoji => collection
+                    .generic_families(GenericFamily::Emoji)
+                    .collect::<Vec<_>>(),
+            };
+            let chain = ids
+                .iter()
+                .copied()
+                .chain(defaults.into_iter().filter(|id| !ids.contains(id)));
+            match *target {
+                FallbackTarget::Script(key) => {
+                    collection.set_fallbacks(key, chain);
+                }
+                FallbackTarget::Emoji => {
+                    collection.set_generic_families(GenericFamily::Emoji, chain);
+                }
+            }
+        }
+    }
+
     /// Measures text for the given layout and font.
     pub fn measure(
         &mut self,
@@ -107,34 +313,52 @@ pub(crate) fn load_fonts(
             let font_data: &[u8] = &font_asset.bytes;
             let font_data = font_data.to_vec();
 
-            let face = ttf_parser::Face::parse(&font_data, 0).unwrap();
-            let family = face
-                .names()
-                .into_iter()
-                .find(|name| name.name_id == ttf_parser::name_id::FAMILY)
-                .expect("Couldn't find font family.");
-
-            let font_family = if family.is_unicode() {
-                family
-                    .to_string()
-                    .expect("Couldn't get string from family name.")
-            } else {
-                String::from_utf8(family.name.to_vec())
-                    .expect("Couldn't get string from family name.")
-            };
-
-            info!("Loaded font family: {}", font_family);
-
-            font_manager.font_cx.collection.register_fonts(
+            // Every face of a family is registered under the same name so parley can pick the
+            // face matching the weight, style and stretch of the text.
+            let font_cx = &mut font_manager.font_cx;
+            let families = font_cx.collection.register_fonts(
                 parley::fontique::Blob::new(Arc::new(font_data.clone())),
                 None,
             );
+            let Some(font_family) = families
+                .first()
+                .and_then(|(family_id, _)| font_cx.collection.family_name(*family_id))
+                .map(str::to_string)
+            else {
+                error!("Woodpecker UI: Couldn't find a font family in font {id}.");
+                continue;
+            };
+
+            info!("Loaded font family: {}", font_family);
 
             font_manager
                 .vello_to_family
                 .insert(Handle::Weak(*id), font_family);
 
             font_manager.font_data.insert(Handle::Weak(*id), font_data);
+            font_manager.apply_fallbacks();
+        }
+    }
+}
+
+/// Lays out and encodes all text again after fonts, fallbacks or the locale changed, since cached
+/// scene fragments and text layouts were shaped with the old ones.
+pub(crate) fn invalidate_text(
+    mut font_manager: ResMut<FontManager>,
+    mut render_cache: ResMut<RenderCache>,
+    mut widget_renders: Query<&mut WidgetRender>,
+) {
+    if !font_manager.fonts_changed {
+        return;
+    }
+    font_manager.fonts_changed = false;
+    render_cache.clear();
+    for mut widget_render in widget_renders.iter_mut() {
+        if matches!(
+            *widget_render,
+            WidgetRender::Text { .. } | WidgetRender::RichText { .. }
+        ) {
+            widget_render.set_changed();
         }
     }
 }
@@ -1,8 +1,119 @@
-use bevy::{platform::collections::HashMap, prelude::*, render::extract_resource::ExtractResource};
+use bevy::{prelude::*, render::extract_resource::ExtractResource};
 use bevy_vello::vello::peniko;
 
-#[derive(Resource, Default, ExtractResource, Clone)]
+use crate::{lru_cache::LruCache, render::WidgetRender};
+
+/// How many converted images are kept around.
+const IMAGE_CACHE_CAPACITY: usize = 256;
+/// How many cropped image regions are kept around.
+const REGION_CACHE_CAPACITY: usize = 1024;
+
+/// Caches bevy images converted to vello images.
+///
+/// Entries are dropped when their image is modified or removed, and the least recently used
+/// entries are dropped once a cache is full.
+#[derive(Resource, ExtractResource, Clone)]
 pub struct ImageManager {
-    pub images: HashMap<AssetId<Image>, peniko::Image>,
-    pub nine_patch_slices: HashMap<u64, peniko::Image>,
+    pub images: LruCache<AssetId<Image>, peniko::Image>,
+    /// Cropped regions of images, used by atlas images and nine patch slices.
+    pub regions: LruCache<(AssetId<Image>, URect), peniko::Image>,
+}
+
+impl Default for ImageManager {
+    fn default() -> Self {
+        Self {
+            images: LruCache::new(IMAGE_CACHE_CAPACITY),
+            regions: LruCache::new(REGION_CACHE_CAPACITY),
+        }
+    }
+}
+
+impl ImageManager {
+    /// Returns a region in pixels of an rgba8 image, cropping and caching it the first time.
+    pub fn region(
+        &mut self,
+        id: AssetId<Image>,
+        image: &Image,
+        rect: URect,
+        quality: peniko::ImageQuality,
+    ) -> Option<peniko::Image> {
+        if let Some(region) = self.regions.get(&(id, rect)) {
+            return Some(region.clone());
+        }
+        let data = image.data.as_ref()?;
+        let row_length = image.width() as usize * 4;
+        let mut region_data =
+            Vec::with_capacity(rect.width() as usize * rect.height() as usize * 4);
+        for y in rect.min.y..rect.max.y {
+            let start = y as usize * row_length + rect.min.x as usize * 4;
+            region_data.extend_from_slice(data.get(start..start + rect.width() as usize * 4)?);
+        }
+        let mut region = peniko::Image::new(
+            region_data.into(),
+            peniko::ImageFormat::Rgba8,
+            rect.width(),
+            rect.height(),
+        );
+        region.quality = quality;
+        self.regions.insert((id, rect), region.clone());
+        Some(region)
+    }
+}
+
+/// Drops converted copies of modified or removed images so edited images are drawn again.
+///
+/// Images which were never drawn are skipped, otherwise only the widgets drawing the image are
+/// re-encoded.
+pub(crate) fn invalidate_image_cache(
+    mut image_manager: ResMut<ImageManager>,
+    mut widget_renders: Query<&mut WidgetRender>,
+    mut event_reader: EventReader<AssetEvent<Image>>,
+) {
+    for event in event_reader.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            let dropped_image = image_manager.images.retain(|image_id| image_id != id);
+            let dropped_region = image_manager.regions.retain(|(image_id, _)| image_id != id);
+            if dropped_image || dropped_region {
+                WidgetRender::mark_asset_users_changed(&mut widget_renders, *id);
+            }
+        }
+    }
+}
+
+/// A region of an image, for example an icon of a sprite sheet.
+#[derive(Debug, Clone, Reflect, PartialEq)]
+pub enum ImageRegion {
+    /// A sprite of a texture atlas.
+    Atlas(TextureAtlas),
+    /// A rect in uv coordinates, from (0, 0) at the top left to (1, 1) at the bottom right.
+    Uv(Rect),
+}
+
+impl ImageRegion {
+    /// The sprite at the index of a texture atlas layout.
+    pub fn atlas(layout: Handle<TextureAtlasLayout>, index: usize) -> Self {
+        Self::Atlas(TextureAtlas { layout, index })
+    }
+
+    /// Returns the region in pixels inside of an image of the given size.
+    ///
+    /// Returns none if the atlas layout isn't loaded or the region is empty.
+    pub fn pixel_rect(
+        &self,
+        image_size: UVec2,
+        atlas_layouts: &Assets<TextureAtlasLayout>,
+    ) -> Option<URect> {
+        let rect = match self {
+            ImageRegion::Atlas(atlas) => atlas.texture_rect(atlas_layouts)?,
+            ImageRegion::Uv(uv) => {
+                let size = image_size.as_vec2();
+                URect::from_corners(
+                    (uv.min * size).round().as_uvec2(),
+                    (uv.max * size).round().as_uvec2(),
+                )
+            }
+        };
+        let rect = rect.intersect(URect::from_corners(UVec2::ZERO, image_size));
+        (!rect.is_empty()).then_some(rect)
+    }
 }
@@ -63,6 +63,33 @@ impl Default for TimeSinceLastPaste {
     }
 }
 
+/// Writes text to the system clipboard.
+pub(crate) fn copy_to_clipboard(text: String) {
+    #[cfg(not(target_arch = "wasm32"))]
+    if let Ok(mut clipboard) = arboard::Clipboard::new() {
+        match clipboard.set_text(text) {
+            Ok(_) => {}
+            Err(err) => error!("{err}"),
+        }
+    }
+    #[cfg(target_arch = "wasm32")]
+    {
+        let Some(clipboard) =
+            web_sys::window().and_then(|window| Some(window.navigator().clipboard()))
+        else {
+            warn!("no clipboard");
+            return;
+        };
+        let promise = clipboard.write_text(&text);
+        let future = wasm_bindgen_futures::JsFuture::from(promise);
+
+        let pool = bevy::tasks::TaskPool::new();
+        pool.spawn(async move {
+            let _ = future.await;
+        });
+    }
+}
+
 #[cfg(target_arch = "wasm32")]
 pub(crate) fn read_paste_events(
     mut commands: Commands,
@@ -12,7 +12,12 @@ impl Plugin for WoodpeckerLayoutPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<UiLayout>().add_systems(
             PostUpdate,
-            system::run.after(crate::runner::system).run_if(has_root()),
+            (
+                system::run.after(crate::runner::system),
+                crate::rich_text::position_inline_widgets.after(system::run),
+                crate::render::update_truncated_text.after(system::run),
+            )
+                .run_if(has_root()),
         );
     }
 }
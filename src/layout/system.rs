@@ -2,21 +2,25 @@
 
 use bevy::{ecs::system::SystemParam, platform::collections::HashMap, prelude::*};
 use bevy_trait_query::One;
+use bevy_vello::vello::peniko::Brush;
 use taffy::Layout;
 
 use crate::{
     context::{Widget, WoodpeckerContext},
     font::FontManager,
     hook_helper::StateMarker,
+    lottie::LottieAsset,
     prelude::{PreviousWidget, WidgetPosition, WidgetRender},
-    styles::Edge,
+    render::{font_properties, rich_text_layout},
+    render_to_texture::WoodpeckerTextureTarget,
+    styles::{Edge, TextOverflow},
     svg::SvgAsset,
     DefaultFont,
 };
 
 use super::{measure::LayoutMeasure, UiLayout, WoodpeckerStyle};
 
-#[derive(Debug, Copy, Clone, Reflect, Default)]
+#[derive(Debug, Copy, Clone, PartialEq, Reflect, Default)]
 pub struct ReflectedLayout {
     /// The z value of the node.
     /// This can be adjusted by the user to render nodes ontop of nodes
@@ -168,7 +172,10 @@ pub(crate) struct LayoutSystemParam<'w, 's> {
     context: Res<'w, WoodpeckerContext>,
     image_assets: Res<'w, Assets<Image>>,
     svg_assets: Res<'w, Assets<SvgAsset>>,
+    lottie_assets: Res<'w, Assets<LottieAsset>>,
+    atlas_layouts: Res<'w, Assets<TextureAtlasLayout>>,
     removed_widgets: RemovedComponents<'w, 's, WidgetLayout>,
+    texture_roots: Query<'w, 's, (Entity, &'static WoodpeckerTextureTarget)>,
 }
 
 // TODO: Document how layouting works..
@@ -187,29 +194,52 @@ pub(crate) fn run(layout_system_param: LayoutSystemParam) {
         context,
         image_assets,
         svg_assets,
+        lottie_assets,
+        atlas_layouts,
         mut removed_widgets,
+        texture_roots,
     } = layout_system_param;
 
     let root_node = context.get_root_widget();
     ui_layout.root_entity = root_node;
 
+    // Texture target roots are laid out as their own trees using the image size.
+    let roots = std::iter::once((
+        root_node,
+        texture_roots
+            .get(root_node)
+            .ok()
+            .map(|(_, target)| target.logical_size(&image_assets)),
+    ))
+    .chain(
+        texture_roots
+            .iter()
+            .filter(|(entity, _)| *entity != root_node)
+            .map(|(entity, target)| (entity, Some(target.logical_size(&image_assets)))),
+    )
+    .collect::<Vec<_>>();
+
     for entity in removed_widgets.read() {
         ui_layout.remove_child(entity);
     }
 
     // This needs to be in the correct order
-    traverse_upsert_node(
-        root_node,
-        &query,
-        &widget_render,
-        &default_font,
-        &mut font_manager,
-        &image_assets,
-        &svg_assets,
-        &mut ui_layout,
-        root_node,
-        Vec2::new(1.0, 1.0),
-    );
+    for (root, _) in roots.iter() {
+        traverse_upsert_node(
+            *root,
+            &query,
+            &widget_render,
+            &default_font,
+            &mut font_manager,
+            &image_assets,
+            &svg_assets,
+            &lottie_assets,
+            &atlas_layouts,
+            &mut ui_layout,
+            *root,
+            Vec2::new(1.0, 1.0),
+        );
+    }
 
     for (entity, children, _) in children_query.iter() {
         let normal_children = children
@@ -227,38 +257,65 @@ pub(crate) fn run(layout_system_param: LayoutSystemParam) {
             .collect::<Vec<_>>();
         ui_layout.add_children(entity, &normal_children);
 
-        // Add fixed children to the root node.
+        // Add fixed children to the root node of their tree.
         for child in children {
             let Ok((_, _, styles, _, _)) = query.get(*child) else {
                 continue;
             };
             if styles.position == WidgetPosition::Fixed {
-                ui_layout.add_child(root_node, *child);
+                let root = find_root(entity, &query, &roots).unwrap_or(root_node);
+                ui_layout.add_child(root, *child);
             }
         }
     }
 
-    let Ok((width, height)) = query
-        .get(root_node)
-        .map(|(_, _, style, _, _)| (style.width.value_or(1.0), style.height.value_or(1.0)))
-    else {
-        return;
-    };
-    ui_layout.compute(root_node, Vec2::new(width, height));
-
-    // TODO(PERF): Figure out how we can combine traversal and compute together..
-    let mut order = 0;
     let mut cache = HashMap::default();
-    traverse_layout_update(
-        &mut commands,
-        root_node,
-        &ui_layout,
-        &query,
-        &layout_query,
-        &mut cache,
-        &mut order,
-        0,
-    );
+    for (root, root_size) in roots.iter() {
+        let Ok((width, height)) = query
+            .get(*root)
+            .map(|(_, _, style, _, _)| (style.width.value_or(1.0), style.height.value_or(1.0)))
+        else {
+            continue;
+        };
+        ui_layout.compute(*root, root_size.unwrap_or(Vec2::new(width, height)));
+
+        // TODO(PERF): Figure out how we can combine traversal and compute together..
+        let mut order = 0;
+        traverse_layout_update(
+            &mut commands,
+            *root,
+            &ui_layout,
+            &query,
+            &layout_query,
+            &mut cache,
+            &mut order,
+            0,
+        );
+    }
+}
+
+/// Walks up the hierarchy until one of the given roots is found.
+fn find_root(
+    entity: Entity,
+    query: &Query<
+        (
+            Entity,
+            One<&dyn Widget>,
+            &WoodpeckerStyle,
+            Option<&ChildOf>,
+            Option<&Children>,
+        ),
+        (Without<StateMarker>, Without<PreviousWidget>),
+    >,
+    roots: &[(Entity, Option<Vec2>)],
+) -> Option<Entity> {
+    let mut current = entity;
+    loop {
+        if roots.iter().any(|(root, _)| *root == current) {
+            return Some(current);
+        }
+        current = query.get(current).ok()?.3?.parent();
+    }
 }
 
 fn traverse_layout_update(
@@ -316,7 +373,14 @@ fn traverse_layout_update(
             .flatten()
             .unwrap_or(parent_id);
         *order += 1;
-        commands.entity(entity).insert(layout);
+        // Only insert changed layouts so change detection can be used to skip re-rendering.
+        if layout_query
+            .get(entity)
+            .ok()
+            .is_none_or(|current| current.0 != layout.0)
+        {
+            commands.entity(entity).insert(layout);
+        }
 
         let Some(children) = children.map(|c| c.iter().collect::<Vec<_>>()) else {
             return;
@@ -354,6 +418,8 @@ fn traverse_upsert_node(
     font_manager: &mut FontManager,
     image_assets: &Assets<Image>,
     svg_assets: &Assets<SvgAsset>,
+    lottie_assets: &Assets<LottieAsset>,
+    atlas_layouts: &Assets<TextureAtlasLayout>,
     layout: &mut UiLayout,
     current_node: Entity,
     camera_scale: Vec2,
@@ -373,6 +439,8 @@ fn traverse_upsert_node(
                 font_manager,
                 image_assets,
                 svg_assets,
+                lottie_assets,
+                atlas_layouts,
                 default_font,
                 widget_render,
                 styles,
@@ -399,6 +467,8 @@ fn traverse_upsert_node(
             font_manager,
             image_assets,
             svg_assets,
+            lottie_assets,
+            atlas_layouts,
             layout,
             child,
             camera_scale,
@@ -410,6 +480,8 @@ fn match_render_size(
     font_manager: &mut FontManager,
     image_assets: &Assets<Image>,
     svg_assets: &Assets<SvgAsset>,
+    lottie_assets: &Assets<LottieAsset>,
+    atlas_layouts: &Assets<TextureAtlasLayout>,
     default_font: &DefaultFont,
     widget_render: &WidgetRender,
     styles: &WoodpeckerStyle,
@@ -424,19 +496,43 @@ fn match_render_size(
 
             Some(LayoutMeasure::Image(super::measure::ImageMeasure { size }))
         }
+        WidgetRender::AtlasImage {
+            handle,
+            region,
+            nine_patch: None,
+        } => {
+            let image = image_assets.get(handle)?;
+
+            let size = region
+                .pixel_rect(image.size(), atlas_layouts)?
+                .size()
+                .as_vec2();
+
+            Some(LayoutMeasure::Image(super::measure::ImageMeasure { size }))
+        }
         WidgetRender::Svg { handle, .. } => {
             let svg_asset = svg_assets.get(handle)?;
 
             let size = Vec2::new(svg_asset.width, svg_asset.height);
             Some(LayoutMeasure::Image(super::measure::ImageMeasure { size }))
         }
-        WidgetRender::RichText { content } => measure_text(
-            &content.text,
+        WidgetRender::Lottie { handle, .. } => {
+            let lottie_asset = lottie_assets.get(handle)?;
+
+            let size = Vec2::new(lottie_asset.width, lottie_asset.height);
+            Some(LayoutMeasure::Image(super::measure::ImageMeasure { size }))
+        }
+        WidgetRender::RichText { content } => measure_layout(
+            &rich_text_layout(
+                content,
+                styles,
+                default_font,
+                font_manager,
+                1.0,
+                parent_layout.size.x * camera_scale.x,
+            ),
             styles,
-            font_manager,
-            default_font,
-            parent_layout,
-            camera_scale,
+            parent_layout.size.x * camera_scale.x,
         ),
         WidgetRender::Text { content } => measure_text(
             content,
@@ -469,13 +565,10 @@ pub(crate) fn measure_text(
             .map(|lh| styles.font_size / lh)
             .unwrap_or(1.2),
     ));
-    text_styles.insert(parley::StyleProperty::FontStack(parley::FontStack::Single(
-        parley::FontFamily::Named(
-            font_manager
-                .get_family(styles.font.as_ref().unwrap_or(&default_font.0.id()))
-                .into(),
-        ),
-    )));
+    text_styles.insert(parley::StyleProperty::FontStack(
+        font_manager.font_stack_or_default(styles.font.as_ref(), &default_font.0.id()),
+    ));
+    text_styles.insert(parley::StyleProperty::Locale(font_manager.locale()));
 
     text_styles.insert(parley::StyleProperty::OverflowWrap(
         match styles.text_wrap {
@@ -485,6 +578,9 @@ pub(crate) fn measure_text(
             crate::styles::TextWrap::WordOrGlyph => parley::OverflowWrap::Anywhere,
         },
     ));
+    for prop in font_properties(styles) {
+        text_styles.insert(prop);
+    }
     layout_editor.set_width(Some(parent_layout.size.x * camera_scale.x));
     let alignment = match styles
         .text_alignment
@@ -498,14 +594,35 @@ pub(crate) fn measure_text(
     };
     layout_editor.set_alignment(alignment);
     let text_layout = layout_editor.layout(&mut font_manager.font_cx, &mut font_manager.layout_cx);
+    measure_layout(text_layout, styles, parent_layout.size.x * camera_scale.x)
+}
 
+/// Measures the size of an already laid out text.
+///
+/// Lines after [`WoodpeckerStyle::max_lines`] are skipped and truncated text is no wider than
+/// `max_width`.
+fn measure_layout(
+    text_layout: &parley::Layout<Brush>,
+    styles: &WoodpeckerStyle,
+    max_width: f32,
+) -> Option<LayoutMeasure> {
     if !text_layout.is_empty() {
         let mut size = Vec2::new(0.0, 0.0);
-        text_layout.lines().for_each(|l| {
-            let line_metrics = l.metrics();
-            size.x = size.x.max(line_metrics.advance + 1.0);
-            size.y += line_metrics.line_height;
-        });
+        text_layout
+            .lines()
+            .take(
+                styles
+                    .max_lines
+                    .map_or(usize::MAX, |max_lines| max_lines as usize),
+            )
+            .for_each(|l| {
+                let line_metrics = l.metrics();
+                size.x = size.x.max(line_metrics.advance + 1.0);
+                size.y += line_metrics.line_height;
+            });
+        if styles.text_overflow != TextOverflow::Visible {
+            size.x = size.x.min(max_width);
+        }
         Some(LayoutMeasure::Fixed(super::measure::FixedMeasure { size }))
     } else {
         None
@@ -61,7 +61,7 @@
 //! }
 //!
 //! ```
-use bevy::render::extract_resource::ExtractResourcePlugin;
+use bevy::render::extract_resource::{ExtractResource, ExtractResourcePlugin};
 use bevy::{
     asset::embedded_asset, prelude::*, reflect::GetTypeRegistration, render::view::RenderLayers,
 };
@@ -79,13 +79,17 @@ use image::ImageManager;
 use layout::WoodpeckerLayoutPlugin;
 use metrics::WidgetMetrics;
 use observer_cache::ObserverCache;
+use render_cache::RenderCache;
+use render_to_texture::RenderToTexturePlugin;
 // use picking_backend::MouseWheelScroll;
 use svg::{SvgAsset, SvgLoader, SvgManager};
 use widgets::WoodpeckerUIWidgetPlugin;
 
+mod animated_image;
 mod children;
 mod context;
 mod convert_render_target;
+mod display_list;
 mod entity_mapping;
 mod focus;
 mod font;
@@ -93,35 +97,60 @@ mod hook_helper;
 mod image;
 mod keyboard_input;
 mod layout;
+mod lottie;
+mod lru_cache;
 mod metrics;
 mod observer_cache;
 mod on_change;
 mod picking_backend;
 mod render;
+mod render_cache;
+mod render_to_texture;
 mod rich_text;
 mod runner;
+mod selectable_text;
+mod shape;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
 mod styles;
 mod svg;
+mod svg_export;
+#[cfg(feature = "software-render")]
+pub mod tiny_skia_backend;
 mod vello_renderer;
 mod vello_svg;
+mod views;
 mod widgets;
 
 /// A module that exports all publicly exposed types.
 pub mod prelude {
+    pub use crate::animated_image::{
+        AnimatedImage, AnimatedImageError, AnimatedImageFrame, AnimatedImagePlayer,
+    };
     pub use crate::children::{Mounted, PassedChildren, WidgetChildren};
     pub use crate::context::*;
+    pub use crate::display_list::{DisplayList, DrawOp, GlyphRun, UiRenderBackend, VelloBackend};
     pub use crate::entity_mapping::*;
     pub use crate::focus::*;
     pub use crate::font::{FontManager, TextAlign};
     pub use crate::hook_helper::{HookHelper, PreviousWidget};
+    pub use crate::image::ImageRegion;
     pub use crate::keyboard_input::{WidgetKeyboardButtonEvent, WidgetKeyboardCharEvent};
     pub use crate::layout::system::{WidgetLayout, WidgetPreviousLayout};
+    pub use crate::lottie::{
+        LottieAsset, LottieError, LottieManager, LottieMarker, LottiePlayer, LottieSegment,
+    };
     pub use crate::metrics::WidgetMetrics;
     pub use crate::on_change::Change;
-    pub use crate::render::{WidgetRender, WidgetRenderCustom};
+    pub use crate::render::{TruncatedText, WidgetRender, WidgetRenderCustom};
+    pub use crate::render_to_texture::{WoodpeckerTextureMesh, WoodpeckerTextureTarget};
     pub use crate::rich_text::*;
+    pub use crate::selectable_text::{SelectableText, TextSelection};
+    pub use crate::shape::*;
     pub use crate::styles::*;
-    pub use crate::svg::SvgAsset;
+    pub use crate::svg::{SvgAsset, SvgElementColors, SvgManager, SvgSelector};
+    pub use crate::svg_export::{ExportSvg, SvgBackend};
+    pub use crate::views::WoodpeckerViewRoot;
     pub use crate::widgets::*;
     pub use crate::PreviousResource;
     pub use crate::WoodpeckerView;
@@ -134,7 +163,7 @@ pub mod prelude {
 }
 
 /// Defines useful render settings
-#[derive(Resource, Clone)]
+#[derive(Resource, ExtractResource, Clone)]
 pub struct RenderSettings {
     /// The bevy render layer to use
     pub layer: RenderLayers,
@@ -233,8 +262,11 @@ impl Plugin for WoodpeckerUIPlugin {
             .add_plugins(WoodpeckerUIWidgetPlugin)
             .add_plugins(ExtractResourcePlugin::<ImageManager>::default())
             .add_plugins(ConvertRenderTargetPlugin)
+            .add_plugins(ExtractResourcePlugin::<RenderSettings>::default())
+            .add_plugins(RenderToTexturePlugin)
             .add_event::<focus::WidgetFocus>()
             .add_event::<focus::WidgetBlur>()
+            .add_event::<svg_export::ExportSvg>()
             .insert_resource(focus::CurrentFocus::new(Entity::PLACEHOLDER))
             .init_resource::<ObserverCache>()
             .init_resource::<FontManager>()
@@ -244,13 +276,21 @@ impl Plugin for WoodpeckerUIPlugin {
             .init_resource::<DefaultFont>()
             .init_resource::<WidgetMetrics>()
             .init_resource::<SvgManager>()
+            .init_resource::<lottie::LottieManager>()
             .init_resource::<ImageManager>()
+            .init_resource::<picking_backend::PointerUiPositions>()
+            .init_resource::<RenderCache>()
             .insert_resource(self.render_settings.clone())
             .init_asset::<SvgAsset>()
             .init_asset_loader::<SvgLoader>()
+            .init_asset::<lottie::LottieAsset>()
+            .init_asset_loader::<lottie::LottieLoader>()
+            .init_asset::<animated_image::AnimatedImage>()
+            .init_asset_loader::<animated_image::AnimatedImageLoader>()
             .add_systems(
                 Update,
                 (
+                    views::sync_view_roots.before(runner::system),
                     runner::system,
                     focus::CurrentFocus::click_focus,
                     #[cfg(not(target_arch = "wasm32"))]
@@ -266,13 +306,31 @@ impl Plugin for WoodpeckerUIPlugin {
                 Update,
                 (
                     font::load_fonts,
+                    font::invalidate_text
+                        .after(font::load_fonts)
+                        .before(vello_renderer::run),
+                    animated_image::update_animated_images.before(crate::layout::system::run),
+                    lottie::update_lottie_players.before(crate::layout::system::run),
+                    (
+                        svg::invalidate_svg_cache,
+                        image::invalidate_image_cache,
+                        lottie::invalidate_lottie_cache,
+                    )
+                        .before(vello_renderer::run),
                     picking_backend::mouse_wheel_system,
+                    selectable_text::copy_selected_text,
                     picking_backend::system.after(crate::layout::system::run),
+                    picking_backend::texture_mesh_system.after(crate::layout::system::run),
                     #[cfg(feature = "metrics")]
                     metrics::WidgetMetrics::print_metrics_x_seconds,
                 ),
             )
             .add_systems(Startup, startup)
+            .add_observer(rich_text::link_pointer_move)
+            .add_observer(rich_text::link_pointer_out)
+            .add_observer(rich_text::link_pointer_click)
+            .add_observer(selectable_text::selection_pointer_pressed)
+            .add_observer(selectable_text::selection_pointer_drag)
             // Reflection registration
             .register_type::<render::WidgetRender>()
             .register_type::<crate::prelude::WidgetLayout>()
@@ -0,0 +1,529 @@
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    ops::Range,
+    sync::Arc,
+};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, LoadContext},
+    prelude::*,
+};
+use bevy_vello::vello::{
+    self,
+    kurbo::Affine,
+    peniko::{self, color::DynamicColor},
+};
+use thiserror::Error;
+use velato::model::{self, Content, Shape};
+
+use crate::{
+    display_list::to_vello_color, hook_helper::PreviousWidget, lru_cache::LruCache,
+    render::WidgetRender,
+};
+
+/// A Lottie animation which can be rendered in the UI.
+///
+/// Loaded from `.lottie.json` files so other json assets aren't parsed as animations, use
+/// [`LottieAsset::from_slice`] to create one from other sources.
+#[derive(Asset, TypePath, Clone)]
+pub struct LottieAsset {
+    /// The parsed animation.
+    pub composition: Arc<velato::Composition>,
+    /// The named segments of the animation.
+    pub markers: Vec<LottieMarker>,
+    /// The width of the animation.
+    pub width: f32,
+    /// The height of the animation.
+    pub height: f32,
+}
+
+/// A named segment of a [`LottieAsset`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LottieMarker {
+    /// The name of the marker.
+    pub name: String,
+    /// The frames of the segment.
+    pub frames: Range<f64>,
+}
+
+/// Errors while loading a [`LottieAsset`].
+#[derive(Error, Debug)]
+pub enum LottieError {
+    /// The file couldn't be read.
+    #[error("Failed to read lottie animation: {0}")]
+    Io(#[from] std::io::Error),
+    /// The file isn't valid json.
+    #[error("Failed to parse lottie animation: {0}")]
+    Json(#[from] serde_json::Error),
+    /// The json isn't a valid animation.
+    #[error("Failed to load lottie animation: {0}")]
+    Lottie(#[from] velato::Error),
+}
+
+impl LottieAsset {
+    /// Parses an animation from the contents of a Lottie json file.
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, LottieError> {
+        let json: serde_json::Value = serde_json::from_slice(bytes)?;
+        // Markers aren't part of the velato composition, so they are read from the json.
+        let markers = json
+            .get("markers")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|marker| {
+                let name = marker.get("cm")?.as_str()?.to_string();
+                let start = marker.get("tm")?.as_f64()?;
+                let duration = marker
+                    .get("dr")
+                    .and_then(serde_json::Value::as_f64)
+                    .unwrap_or(0.0);
+                Some(LottieMarker {
+                    name,
+                    frames: start..start + duration,
+                })
+            })
+            .collect();
+        let composition = velato::Composition::from_json(json)?;
+        Ok(Self {
+            width: composition.width as f32,
+            height: composition.height as f32,
+            composition: Arc::new(composition),
+            markers,
+        })
+    }
+
+    /// Finds a marker by its name.
+    pub fn marker(&self, name: &str) -> Option<&LottieMarker> {
+        self.markers.iter().find(|marker| marker.name == name)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct LottieLoader;
+
+impl AssetLoader for LottieLoader {
+    type Asset = LottieAsset;
+
+    type Settings = ();
+
+    type Error = LottieError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        LottieAsset::from_slice(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["lottie.json"]
+    }
+}
+
+/// How many recolored animations are kept around.
+const RECOLORED_CACHE_CAPACITY: usize = 64;
+
+/// Caches recolored animations and renders them into vello scenes.
+///
+/// Recolored animations are dropped when their asset is modified or removed, and the least
+/// recently used ones are dropped once the cache is full.
+#[derive(Resource)]
+pub struct LottieManager {
+    renderer: velato::Renderer,
+    recolored: LruCache<(AssetId<LottieAsset>, u64), Arc<velato::Composition>>,
+}
+
+impl Default for LottieManager {
+    fn default() -> Self {
+        Self {
+            renderer: velato::Renderer::default(),
+            recolored: LruCache::new(RECOLORED_CACHE_CAPACITY),
+        }
+    }
+}
+
+impl LottieManager {
+    /// Returns the animation with the given layer colors replaced.
+    pub fn get_cached(
+        &mut self,
+        asset_id: impl Into<AssetId<LottieAsset>>,
+        lottie_assets: &Assets<LottieAsset>,
+        colors: &[(String, Color)],
+    ) -> Option<Arc<velato::Composition>> {
+        let asset_id: AssetId<LottieAsset> = asset_id.into();
+        let lottie = lottie_assets.get(asset_id)?;
+        if colors.is_empty() {
+            return Some(lottie.composition.clone());
+        }
+
+        let mut hasher = DefaultHasher::default();
+        for (layer, color) in colors {
+            layer.hash(&mut hasher);
+            color.to_srgba().to_hex().hash(&mut hasher);
+        }
+        let key = (asset_id, hasher.finish());
+
+        Some(
+            self.recolored
+                .get_or_insert_with(key, || Arc::new(recolor(&lottie.composition, colors)))
+                .clone(),
+        )
+    }
+
+    /// Appends a frame of an animation to the scene.
+    pub(crate) fn render(
+        &mut self,
+        scene: &mut vello::Scene,
+        composition: &velato::Composition,
+        frame: f64,
+        transform: Affine,
+    ) {
+        self.renderer
+            .append(composition, frame, transform, 1.0, scene);
+    }
+}
+
+/// Replaces the fill and stroke colors of the layers with the given names.
+fn recolor(composition: &velato::Composition, colors: &[(String, Color)]) -> velato::Composition {
+    let mut composition = composition.clone();
+    let layers = composition
+        .layers
+        .iter_mut()
+        .chain(composition.assets.values_mut().flatten());
+    for layer in layers {
+        let Some((_, color)) = colors.iter().find(|(name, _)| *name == layer.name) else {
+            continue;
+        };
+        let Content::Shape(shapes) = &mut layer.content else {
+            continue;
+        };
+        let color = to_vello_color(*color);
+        for shape in shapes.iter_mut() {
+            recolor_shape(shape, color);
+        }
+    }
+    composition
+}
+
+fn recolor_shape(shape: &mut Shape, color: peniko::Color) {
+    match shape {
+        Shape::Group(shapes, _) => {
+            for shape in shapes.iter_mut() {
+                recolor_shape(shape, color);
+            }
+        }
+        Shape::Draw(draw) => {
+            if let model::Brush::Fixed(peniko::Brush::Gradient(gradient)) = &mut draw.brush {
+                for stop in gradient.stops.0.iter_mut() {
+                    stop.color = DynamicColor::from_alpha_color(color);
+                }
+            } else if !matches!(draw.brush, model::Brush::Fixed(peniko::Brush::Image(_))) {
+                // Animated colors and gradients become a single solid color.
+                draw.brush = model::Brush::Fixed(peniko::Brush::Solid(color));
+            }
+        }
+        Shape::Geometry(_) | Shape::Repeater(_) => {}
+    }
+}
+
+/// Which part of a Lottie animation a [`LottiePlayer`] plays.
+#[derive(Reflect, Debug, Default, Clone, PartialEq)]
+pub enum LottieSegment {
+    /// The whole animation.
+    #[default]
+    Full,
+    /// The frames between `start` and `end`.
+    Frames {
+        /// The first frame.
+        start: f64,
+        /// The frame the segment ends at.
+        end: f64,
+    },
+    /// The frames of a [`LottieMarker`], the whole animation if there is no marker with this name.
+    Marker(String),
+}
+
+impl LottieSegment {
+    /// The frames of the segment inside of the animation.
+    fn frames(&self, lottie: &LottieAsset) -> Range<f64> {
+        let full = lottie.composition.frames.clone();
+        let frames = match self {
+            LottieSegment::Full => full.clone(),
+            LottieSegment::Frames { start, end } => *start..*end,
+            LottieSegment::Marker(name) => lottie
+                .marker(name)
+                .map(|marker| marker.frames.clone())
+                .unwrap_or(full.clone()),
+        };
+        let start = frames.start.clamp(full.start, full.end);
+        start..frames.end.clamp(start, full.end)
+    }
+}
+
+/// Plays a [`LottieAsset`] by drawing its current frame as the [`WidgetRender::Lottie`] of the
+/// widget.
+///
+/// ```ignore
+/// commands.spawn((
+///     Element,
+///     WoodpeckerStyle::default(),
+///     LottiePlayer::new(asset_server.load("loading.lottie.json"))
+///         .with_segment(LottieSegment::Marker("intro".into()))
+///         .with_color("Circle", Color::WHITE),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+#[require(WidgetRender = WidgetRender::Lottie { handle: Handle::default(), frame: 0.0, colors: vec![] })]
+pub struct LottiePlayer {
+    /// The animation which is played.
+    pub handle: Handle<LottieAsset>,
+    /// Is the animation playing?
+    pub playing: bool,
+    /// How fast the animation plays, 1.0 is the speed of the file and negative speeds play it
+    /// backwards.
+    pub speed: f32,
+    /// How often the segment plays before it stops on its last frame, `None` repeats forever.
+    pub loop_count: Option<u32>,
+    /// The part of the animation which is played.
+    pub segment: LottieSegment,
+    /// Replaces the fill and stroke colors of layers by their name.
+    pub colors: Vec<(String, Color)>,
+    frame: Option<f64>,
+    loops: u32,
+}
+
+impl LottiePlayer {
+    /// Creates a new player which repeats the animation forever.
+    pub fn new(handle: Handle<LottieAsset>) -> Self {
+        Self {
+            handle,
+            playing: true,
+            speed: 1.0,
+            loop_count: None,
+            segment: LottieSegment::Full,
+            colors: vec![],
+            frame: None,
+            loops: 0,
+        }
+    }
+
+    /// Sets how fast the animation plays.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Sets how often the animation plays before it stops.
+    pub fn with_loop_count(mut self, loop_count: u32) -> Self {
+        self.loop_count = Some(loop_count);
+        self
+    }
+
+    /// Sets the part of the animation which is played.
+    pub fn with_segment(mut self, segment: LottieSegment) -> Self {
+        self.segment = segment;
+        self
+    }
+
+    /// Replaces the colors of a layer.
+    pub fn with_color(mut self, layer: impl Into<String>, color: impl Into<Color>) -> Self {
+        self.colors.push((layer.into(), color.into()));
+        self
+    }
+
+    /// Resumes the animation.
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    /// Pauses the animation on the current frame.
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Plays the segment from its start.
+    pub fn restart(&mut self) {
+        self.frame = None;
+        self.loops = 0;
+        self.playing = true;
+    }
+
+    /// Plays another segment from its start.
+    pub fn play_segment(&mut self, segment: LottieSegment) {
+        self.segment = segment;
+        self.restart();
+    }
+
+    /// Jumps to a frame of the animation.
+    pub fn seek(&mut self, frame: f64) {
+        self.frame = Some(frame);
+    }
+
+    /// The frame which is shown, `None` until the animation is loaded.
+    pub fn frame(&self) -> Option<f64> {
+        self.frame
+    }
+
+    /// Returns true if the segment played `loop_count` times.
+    pub fn is_finished(&self) -> bool {
+        self.loop_count.is_some_and(|count| self.loops >= count)
+    }
+
+    /// Moves the animation forward by the given time.
+    fn advance(&mut self, delta: f32, lottie: &LottieAsset) {
+        let frames = self.segment.frames(lottie);
+        let length = frames.end - frames.start;
+        let backwards = self.speed < 0.0;
+        let start = if backwards { frames.end } else { frames.start };
+        let mut frame = self.frame.unwrap_or(start);
+        if self.playing && !self.is_finished() && length > 0.0 {
+            frame += (delta * self.speed) as f64 * lottie.composition.frame_rate;
+            // Backwards playback starts at the end of the segment, which is outside of it.
+            while frame < frames.start || frame > frames.end || (!backwards && frame == frames.end)
+            {
+                self.loops += 1;
+                if self.is_finished() {
+                    // The end of a segment is exclusive, so forwards playback stops a frame
+                    // before it.
+                    frame = if backwards {
+                        frames.start
+                    } else {
+                        (frames.end - 1.0).max(frames.start)
+                    };
+                    self.playing = false;
+                    break;
+                }
+                frame += if frame < frames.start {
+                    length
+                } else {
+                    -length
+                };
+            }
+        }
+        self.frame = Some(frame);
+    }
+}
+
+/// Advances lottie players and draws their current frame.
+pub(crate) fn update_lottie_players(
+    time: Res<Time>,
+    lottie_assets: Res<Assets<LottieAsset>>,
+    mut query: Query<(&mut LottiePlayer, &mut WidgetRender), Without<PreviousWidget>>,
+) {
+    for (mut player, mut widget_render) in query.iter_mut() {
+        let Some(lottie) = lottie_assets.get(&player.handle) else {
+            continue;
+        };
+        player.advance(time.delta_secs(), lottie);
+        let frame = player.frame.unwrap_or_default();
+        if !matches!(
+            &*widget_render,
+            WidgetRender::Lottie { handle, frame: shown, colors }
+                if *handle == player.handle && *shown == frame && *colors == player.colors
+        ) {
+            *widget_render = WidgetRender::Lottie {
+                handle: player.handle.clone(),
+                frame,
+                colors: player.colors.clone(),
+            };
+        }
+    }
+}
+
+/// Drops recolored copies of modified or removed animations so edited animations are drawn again.
+pub(crate) fn invalidate_lottie_cache(
+    mut lottie_manager: ResMut<LottieManager>,
+    mut widget_renders: Query<&mut WidgetRender>,
+    mut event_reader: EventReader<AssetEvent<LottieAsset>>,
+) {
+    for event in event_reader.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            lottie_manager
+                .recolored
+                .retain(|(asset_id, _)| asset_id != id);
+            // Compositions without recolored layers are drawn straight from the asset and never
+            // enter the cache, so the widgets are looked up either way.
+            WidgetRender::mark_asset_users_changed(&mut widget_renders, *id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 2 second animation at 10 frames per second with a marker from frame 5 to 15.
+    fn lottie() -> LottieAsset {
+        LottieAsset::from_slice(
+            br#"{"v":"5.7.0","fr":10,"ip":0,"op":20,"w":10,"h":10,"layers":[],
+                "markers":[{"cm":"middle","tm":5,"dr":10}]}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn advance_wraps_around() {
+        let lottie = lottie();
+        let mut player = LottiePlayer::new(Handle::default());
+        player.advance(0.5, &lottie);
+        assert_eq!(player.frame(), Some(5.0));
+        player.advance(1.75, &lottie);
+        assert_eq!(player.frame(), Some(2.5));
+        assert!(!player.is_finished());
+    }
+
+    #[test]
+    fn advance_excludes_segment_end() {
+        let lottie = lottie();
+        let segment = LottieSegment::Frames {
+            start: 0.0,
+            end: 10.0,
+        };
+        let mut player = LottiePlayer::new(Handle::default()).with_segment(segment.clone());
+        player.advance(1.0, &lottie);
+        assert_eq!(player.frame(), Some(0.0));
+
+        // The last loop stops on the frame before the end.
+        let mut player = LottiePlayer::new(Handle::default())
+            .with_segment(segment)
+            .with_loop_count(1);
+        player.advance(1.0, &lottie);
+        assert_eq!(player.frame(), Some(9.0));
+        assert!(player.is_finished());
+        assert!(!player.playing);
+    }
+
+    #[test]
+    fn advance_backwards_counts_loops() {
+        let lottie = lottie();
+        let mut player = LottiePlayer::new(Handle::default())
+            .with_segment(LottieSegment::Marker("middle".into()))
+            .with_speed(-1.0)
+            .with_loop_count(2);
+        // Backwards playback starts at the end of the segment.
+        player.advance(0.0, &lottie);
+        assert_eq!(player.frame(), Some(15.0));
+        player.advance(0.25, &lottie);
+        assert_eq!(player.frame(), Some(12.5));
+        player.advance(0.75, &lottie);
+        assert_eq!(player.frame(), Some(5.0));
+        player.advance(0.25, &lottie);
+        assert_eq!(player.frame(), Some(12.5));
+        assert!(!player.is_finished());
+
+        // The second loop stops on the start of the segment.
+        player.advance(1.0, &lottie);
+        assert_eq!(player.frame(), Some(5.0));
+        assert!(player.is_finished());
+        assert!(!player.playing);
+
+        player.restart();
+        player.advance(0.0, &lottie);
+        assert_eq!(player.frame(), Some(15.0));
+        assert!(!player.is_finished());
+    }
+}
@@ -0,0 +1,93 @@
+use std::hash::Hash;
+
+use bevy::platform::collections::HashMap;
+
+/// A map which drops its least recently used entry once it holds more than `capacity` entries.
+///
+/// Evicting scans every entry, which is fine for the few hundred entries the render caches hold.
+#[derive(Clone)]
+pub(crate) struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>,
+    tick: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::default(),
+            tick: 0,
+        }
+    }
+
+    /// Returns the value of a key and marks it as recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.tick += 1;
+        let (value, used) = self.entries.get_mut(key)?;
+        *used = self.tick;
+        Some(value)
+    }
+
+    /// Inserts a value, evicting the least recently used entry if the cache is full.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.tick += 1;
+        self.entries.insert(key, (value, self.tick));
+        if self.entries.len() > self.capacity {
+            if let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, used))| *used)
+                .map(|(key, _)| key.clone())
+            {
+                self.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Returns the value of a key, inserting it first if it isn't cached.
+    pub fn get_or_insert_with(&mut self, key: K, value: impl FnOnce() -> V) -> &V {
+        if self.get(&key).is_none() {
+            self.insert(key.clone(), value());
+        }
+        &self.entries[&key].0
+    }
+
+    /// Only keeps the entries for which `keep` returns true.
+    ///
+    /// Returns true if any entry was dropped.
+    pub fn retain(&mut self, mut keep: impl FnMut(&K) -> bool) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|key, _| keep(key));
+        self.entries.len() != len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        // Reading "a" makes "b" the least recently used entry.
+        assert_eq!(cache.get(&"a"), Some(&1));
+        cache.insert("c", 3);
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.get(&"c"), Some(&3));
+
+        // Inserting over an existing key refreshes it instead of growing the cache.
+        cache.insert("a", 4);
+        cache.insert("d", 5);
+        assert_eq!(cache.get(&"c"), None);
+        assert_eq!(cache.get(&"a"), Some(&4));
+        assert_eq!(*cache.get_or_insert_with("d", || 6), 5);
+
+        assert!(cache.retain(|key| *key != "a"));
+        assert!(!cache.retain(|key| *key != "a"));
+        assert_eq!(cache.get(&"a"), None);
+    }
+}
@@ -12,6 +12,11 @@ pub struct WidgetMetrics {
     quads_displayed: usize,
     quads_displayed_since_last_frame: usize,
     quads_avg_buffer: Vec<usize>,
+
+    render_cache_hits: usize,
+    render_cache_hits_since_last_frame: usize,
+    render_cache_misses: usize,
+    render_cache_misses_since_last_frame: usize,
 }
 
 pub struct LastUpdated(Instant);
@@ -75,8 +80,12 @@ impl WidgetMetrics {
     }
 
     pub(crate) fn increase_quad_counts(&mut self) {
-        self.quads_displayed += 1;
-        self.quads_displayed_since_last_frame += 1;
+        self.increase_quad_counts_by(1);
+    }
+
+    pub(crate) fn increase_quad_counts_by(&mut self, count: usize) {
+        self.quads_displayed += count;
+        self.quads_displayed_since_last_frame += count;
     }
 
     pub(crate) fn clear_quad_last_frame(&mut self) {
@@ -91,6 +100,42 @@ impl WidgetMetrics {
             .push(self.quads_displayed_since_last_frame);
     }
 
+    /// Returns the total number of widgets whose cached scene fragment was reused over the
+    /// applications lifetime.
+    pub fn get_render_cache_hits(&self) -> usize {
+        self.render_cache_hits
+    }
+
+    /// Returns the number of widgets whose cached scene fragment was reused since the last frame.
+    pub fn get_render_cache_hits_since_last_frame(&self) -> usize {
+        self.render_cache_hits_since_last_frame
+    }
+
+    /// Returns the total number of widgets that had to be re-encoded over the applications lifetime.
+    pub fn get_render_cache_misses(&self) -> usize {
+        self.render_cache_misses
+    }
+
+    /// Returns the number of widgets that had to be re-encoded since the last frame.
+    pub fn get_render_cache_misses_since_last_frame(&self) -> usize {
+        self.render_cache_misses_since_last_frame
+    }
+
+    pub(crate) fn increase_render_cache_hits(&mut self, count: usize) {
+        self.render_cache_hits += count;
+        self.render_cache_hits_since_last_frame += count;
+    }
+
+    pub(crate) fn increase_render_cache_misses(&mut self) {
+        self.render_cache_misses += 1;
+        self.render_cache_misses_since_last_frame += 1;
+    }
+
+    pub(crate) fn clear_render_cache_last_frame(&mut self) {
+        self.render_cache_hits_since_last_frame = 0;
+        self.render_cache_misses_since_last_frame = 0;
+    }
+
     /// A system that prints widget metrics!
     pub fn print_metrics_x_seconds(
         metrics: Res<WidgetMetrics>,
@@ -107,6 +152,8 @@ Average Rendered over 100 frames: {},
 Total Quads Displayed: {},
 Quads Displayed Last Frame: {},
 Average Quads Displayed over 100 frames: {},
+Render Cache Hits Last Frame: {},
+Render Cache Misses Last Frame: {},
 Note: "Rendered" means that widget's render system was ran not that
 it was visible on screen. "Displayed" means shown on screen.
 =====================================================================
@@ -116,7 +163,9 @@ it was visible on screen. "Displayed" means shown on screen.
                 metrics.get_average_widgets_rendered_per_frame(),
                 metrics.get_quads_displayed(),
                 metrics.get_quads_displayed_since_last_frame(),
-                metrics.get_average_quads_displayed_per_frame()
+                metrics.get_average_quads_displayed_per_frame(),
+                metrics.get_render_cache_hits_since_last_frame(),
+                metrics.get_render_cache_misses_since_last_frame()
             );
         }
     }
@@ -3,24 +3,58 @@ use bevy::{
     picking::{
         backend::{HitData, PointerHits},
         hover::HoverMap,
+        mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastVisibility},
         pointer::{PointerId, PointerLocation, PointerMap},
     },
+    platform::collections::HashMap,
     prelude::*,
+    render::{
+        camera::NormalizedRenderTarget,
+        mesh::{Indices, VertexAttributeValues},
+    },
     window::PrimaryWindow,
 };
 
 use crate::{
     context::WoodpeckerContext,
     layout::system::WidgetLayout,
+    render_to_texture::{WoodpeckerTextureMesh, WoodpeckerTextureTarget},
     styles::{WidgetVisibility, WoodpeckerStyle},
+    views::{main_view_camera, WoodpeckerViewRoot},
     WoodpeckerView,
 };
 
+/// The last position of every pointer in the UI space of each root it was mapped into.
+///
+/// Pointer hits only carry a position while the pointer is over a widget, this lets drags keep
+/// mapping the pointer into the root they started in once it left the widget.
+#[derive(Resource, Default)]
+pub(crate) struct PointerUiPositions(HashMap<(PointerId, Entity), Vec2>);
+
+impl PointerUiPositions {
+    /// Returns the position of the pointer in the UI space of the closest root above the entity.
+    pub fn get(
+        &self,
+        pointer: PointerId,
+        entity: Entity,
+        parents: &Query<&ChildOf>,
+    ) -> Option<Vec2> {
+        std::iter::once(entity)
+            .chain(parents.iter_ancestors(entity))
+            .find_map(|entity| self.0.get(&(pointer, entity)).copied())
+    }
+}
+
 pub(crate) fn system(
     context: Res<WoodpeckerContext>,
+    mut positions: ResMut<PointerUiPositions>,
     pointers: Query<(&PointerId, &PointerLocation)>,
-    cameras: Query<(Entity, &Camera, &GlobalTransform, &Projection), With<WoodpeckerView>>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform), With<WoodpeckerView>>,
+    view_roots: Query<&WoodpeckerViewRoot>,
+    view_root_targets: Query<(Entity, &WoodpeckerViewRoot, &WoodpeckerTextureTarget)>,
+    images: Res<Assets<Image>>,
     primary_window: Single<(Entity, &Window), With<PrimaryWindow>>,
+    primary_window_query: Query<Entity, With<PrimaryWindow>>,
     layout_query: Query<(&WidgetLayout, &WoodpeckerStyle)>,
     child_query: Query<&Children>,
     pickable_query: Query<&Pickable>,
@@ -32,10 +66,51 @@ pub(crate) fn system(
     for (pointer, location) in pointers.iter().filter_map(|(pointer, pointer_location)| {
         pointer_location.location().map(|loc| (pointer, loc))
     }) {
-        let Some((cam_entity, camera, _cam_transform, _cam_ortho)) = cameras
-            .iter()
-            .filter(|(_, camera, _, _)| camera.is_active)
-            .next()
+        // Roots drawn by other views are picked in the viewport of their camera.
+        for (root, view_root, texture_target) in view_root_targets.iter() {
+            let Ok((cam_entity, camera, _)) = cameras.get(view_root.camera) else {
+                continue;
+            };
+            if !location.is_in_viewport(camera, &primary_window_query) {
+                continue;
+            }
+            let Some(viewport) = camera.logical_viewport_rect() else {
+                continue;
+            };
+            let view_size = texture_target.logical_size(&images);
+            positions
+                .0
+                .insert((*pointer, root), location.position - viewport.min);
+
+            let mut picks = vec![];
+            process_entity(
+                root,
+                cam_entity,
+                location.position - viewport.min,
+                viewport.min,
+                view_size / 2.0,
+                Vec2::ONE,
+                #[cfg(feature = "debug-render")]
+                &mut gizmos,
+                &layout_query,
+                &child_query,
+                &pickable_query,
+                &mut picks,
+                total,
+            );
+
+            let order = camera.order as f32;
+            output.write(PointerHits::new(*pointer, picks, order));
+        }
+
+        // Pointers over other windows never hit the main root.
+        if matches!(&location.target, NormalizedRenderTarget::Window(window) if window.entity() != primary_window.0)
+        {
+            continue;
+        }
+
+        let Some((cam_entity, camera, _cam_transform)) =
+            main_view_camera(cameras.iter(), &view_roots)
         else {
             continue;
         };
@@ -47,6 +122,9 @@ pub(crate) fn system(
 
         let cursor_pos_world =
             ((location.position - offset) / size) * camera.logical_target_size().unwrap();
+        positions
+            .0
+            .insert((*pointer, context.get_root_widget()), cursor_pos_world);
 
         // We need to walk the tree here because of visibility. If a parent is hidden it's children shouldn't be hit with clicks.
         let mut picks = vec![];
@@ -71,6 +149,116 @@ pub(crate) fn system(
     }
 }
 
+/// Forwards pointers hitting a [`WoodpeckerTextureMesh`] to the UI tree rendered into its texture.
+///
+/// The pointer is ray cast from every non UI camera, the UV coordinates of the closest hit are
+/// mapped into the texture and the texture root is then picked the same way as the main root.
+pub(crate) fn texture_mesh_system(
+    pointers: Query<(&PointerId, &PointerLocation)>,
+    mut positions: ResMut<PointerUiPositions>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform), Without<WoodpeckerView>>,
+    primary_window: Query<Entity, With<PrimaryWindow>>,
+    texture_meshes: Query<(&WoodpeckerTextureMesh, &Mesh3d)>,
+    texture_targets: Query<&WoodpeckerTextureTarget>,
+    meshes: Res<Assets<Mesh>>,
+    images: Res<Assets<Image>>,
+    mut ray_cast: MeshRayCast,
+    layout_query: Query<(&WidgetLayout, &WoodpeckerStyle)>,
+    child_query: Query<&Children>,
+    pickable_query: Query<&Pickable>,
+    mut output: EventWriter<PointerHits>,
+    #[cfg(feature = "debug-render")] mut gizmos: Gizmos,
+) {
+    if texture_meshes.is_empty() {
+        return;
+    }
+
+    let total = pickable_query.iter().count();
+    let filter = |entity| texture_meshes.contains(entity);
+    let settings = MeshRayCastSettings::default()
+        .with_filter(&filter)
+        .with_visibility(RayCastVisibility::VisibleInView);
+
+    for (pointer, location) in pointers.iter().filter_map(|(pointer, pointer_location)| {
+        pointer_location.location().map(|loc| (pointer, loc))
+    }) {
+        // Only cameras drawing to the pointer's render target, under the pointer, can see the mesh.
+        for (cam_entity, camera, cam_transform) in cameras.iter().filter(|(_, camera, _)| {
+            camera.is_active && location.is_in_viewport(camera, &primary_window)
+        }) {
+            let Ok(ray) = camera.viewport_to_world(cam_transform, location.position) else {
+                continue;
+            };
+            let Some((mesh_entity, hit)) = ray_cast.cast_ray(ray, &settings).first() else {
+                continue;
+            };
+            let Ok((texture_mesh, mesh_3d)) = texture_meshes.get(*mesh_entity) else {
+                continue;
+            };
+            let Some(uv) = meshes
+                .get(&mesh_3d.0)
+                .zip(hit.triangle_index)
+                .and_then(|(mesh, triangle)| hit_uv(mesh, triangle, hit.barycentric_coords))
+            else {
+                continue;
+            };
+            let Ok(texture_target) = texture_targets.get(texture_mesh.root) else {
+                continue;
+            };
+            let image_size = texture_target.logical_size(&images);
+            positions
+                .0
+                .insert((*pointer, texture_mesh.root), uv * image_size);
+
+            let mut picks = vec![];
+            process_entity(
+                texture_mesh.root,
+                cam_entity,
+                uv * image_size,
+                Vec2::ZERO,
+                image_size / 2.0,
+                Vec2::ONE,
+                #[cfg(feature = "debug-render")]
+                &mut gizmos,
+                &layout_query,
+                &child_query,
+                &pickable_query,
+                &mut picks,
+                total,
+            );
+
+            // Keep the ordering relative to the mesh hit so geometry in front still blocks.
+            for (_, hit_data) in picks.iter_mut() {
+                hit_data.depth += hit.distance;
+            }
+
+            let order = camera.order as f32;
+            output.write(PointerHits::new(*pointer, picks, order));
+        }
+    }
+}
+
+/// Interpolates the UV coordinates of the given triangle at the barycentric coordinates.
+fn hit_uv(mesh: &Mesh, triangle: usize, barycentric: Vec3) -> Option<Vec2> {
+    let Some(VertexAttributeValues::Float32x2(uvs)) = mesh.attribute(Mesh::ATTRIBUTE_UV_0) else {
+        return None;
+    };
+    let [a, b, c] = match mesh.indices() {
+        Some(Indices::U16(indices)) => {
+            let i = indices.get(triangle * 3..triangle * 3 + 3)?;
+            [i[0] as usize, i[1] as usize, i[2] as usize]
+        }
+        Some(Indices::U32(indices)) => {
+            let i = indices.get(triangle * 3..triangle * 3 + 3)?;
+            [i[0] as usize, i[1] as usize, i[2] as usize]
+        }
+        None => [triangle * 3, triangle * 3 + 1, triangle * 3 + 2],
+    };
+    let [a, b, c] = [uvs.get(a)?, uvs.get(b)?, uvs.get(c)?].map(|uv| Vec2::from(*uv));
+    // The ray cast returns (u, v, w) where w belongs to the first vertex.
+    Some(b * barycentric.x + c * barycentric.y + a * barycentric.z)
+}
+
 fn process_entity(
     entity: Entity,
     cam_entity: Entity,
@@ -120,7 +308,11 @@ fn process_entity(
                 }
                 const ORDER_SPACING: f32 = 1.0 / 64_000.0;
                 let depth = -(layout.z as f32 + (layout.order as f32 * ORDER_SPACING));
-                pick_list.push((entity, HitData::new(cam_entity, depth, None, None)));
+                // The hit position is the pointer in the UI space of the root.
+                pick_list.push((
+                    entity,
+                    HitData::new(cam_entity, depth, Some(cursor_pos_world.extend(0.0)), None),
+                ));
             }
         }
     }
@@ -222,3 +414,86 @@ pub fn compute_letterboxed_transform(
 
     (offset, scaled_size, scale)
 }
+
+#[cfg(all(test, not(feature = "debug-render")))]
+mod tests {
+    use bevy::{
+        ecs::system::RunSystemOnce,
+        picking::mesh_picking::ray_cast::{ray_mesh_intersection, Backfaces},
+    };
+
+    use super::*;
+    use crate::layout::system::ReflectedLayout;
+
+    fn widget(world: &mut World, location: Vec2, size: Vec2) -> Entity {
+        world
+            .spawn((
+                WidgetLayout(ReflectedLayout {
+                    location,
+                    size,
+                    ..Default::default()
+                }),
+                WoodpeckerStyle::default(),
+                Pickable::default(),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn texture_mesh_hits_pick_widgets_at_their_uv() {
+        // A quad facing +Z showing a 200x100 texture, hit a quarter in from its top left corner.
+        let mesh = Mesh::from(Rectangle::new(2.0, 2.0));
+        let positions = mesh
+            .attribute(Mesh::ATTRIBUTE_POSITION)
+            .and_then(VertexAttributeValues::as_float3)
+            .unwrap();
+        let indices = mesh.indices().unwrap().iter().collect::<Vec<_>>();
+        let ray = Ray3d::new(Vec3::new(-0.5, 0.5, 5.0), Dir3::NEG_Z);
+        let hit = ray_mesh_intersection(
+            ray,
+            &Mat4::IDENTITY,
+            positions,
+            None,
+            Some(&indices),
+            Backfaces::Cull,
+        )
+        .unwrap();
+        let uv = hit_uv(&mesh, hit.triangle_index.unwrap(), hit.barycentric_coords).unwrap();
+        assert!(uv.abs_diff_eq(Vec2::new(0.25, 0.25), 1e-5), "{uv}");
+
+        let mut world = World::new();
+        let left = widget(&mut world, Vec2::ZERO, Vec2::new(100.0, 100.0));
+        let right = widget(&mut world, Vec2::new(100.0, 0.0), Vec2::new(100.0, 100.0));
+        let root = widget(&mut world, Vec2::ZERO, Vec2::new(200.0, 100.0));
+        world.entity_mut(root).add_children(&[left, right]);
+
+        let picks = world
+            .run_system_once(
+                move |layout_query: Query<(&WidgetLayout, &WoodpeckerStyle)>,
+                      child_query: Query<&Children>,
+                      pickable_query: Query<&Pickable>| {
+                    let image_size = Vec2::new(200.0, 100.0);
+                    let mut picks = vec![];
+                    process_entity(
+                        root,
+                        Entity::PLACEHOLDER,
+                        uv * image_size,
+                        Vec2::ZERO,
+                        image_size / 2.0,
+                        Vec2::ONE,
+                        &layout_query,
+                        &child_query,
+                        &pickable_query,
+                        &mut picks,
+                        pickable_query.iter().count(),
+                    );
+                    picks
+                        .into_iter()
+                        .map(|(entity, _)| entity)
+                        .collect::<Vec<_>>()
+                },
+            )
+            .unwrap();
+        assert_eq!(picks, vec![root, left]);
+    }
+}
@@ -1,11 +1,11 @@
-use std::{
-    hash::{DefaultHasher, Hash, Hasher},
-    sync::Arc,
-};
+use std::{ops::Range, sync::Arc};
 
-use bevy::{asset::RenderAssetUsages, image::ImageSampler, prelude::*};
+use bevy::{
+    asset::{RenderAssetUsages, UntypedAssetId},
+    image::ImageSampler,
+    prelude::*,
+};
 use bevy_vello::{
-    prelude::VelloFont,
     vello::{
         self,
         kurbo::{self, Affine, RoundedRectRadii},
@@ -14,16 +14,22 @@ use bevy_vello::{
     },
     VelloScene,
 };
-use image::GenericImage;
 use parley::StyleSet;
 
 use crate::{
     convert_render_target::RenderTargetImages,
+    display_list::{to_vello_color, DrawOp, GlyphRun},
     font::FontManager,
-    image::ImageManager,
+    image::{ImageManager, ImageRegion},
+    lottie::LottieAsset,
     metrics::WidgetMetrics,
-    prelude::{RichText, WidgetLayout, WoodpeckerStyle},
-    svg::{SvgAsset, SvgManager},
+    prelude::{
+        GlyphEffect, InlineContent, PreviousWidget, RichText, TextDecoration, TextOverflow,
+        WidgetLayout, WoodpeckerStyle,
+    },
+    rich_text::glyph_effect_at,
+    shape::Shape,
+    svg::{SvgAsset, SvgSelector},
     DefaultFont,
 };
 
@@ -80,13 +86,45 @@ pub enum WidgetRender {
         /// A bevy image scale mode.
         scale_mode: SpriteImageMode,
     },
+    /// A region of an image, for example a sprite of a sprite sheet.
+    ///
+    /// The region is cropped out of the image, so one sheet can be used for many widgets.
+    AtlasImage {
+        /// A handle to a bevy image.
+        handle: Handle<Image>,
+        /// The region of the image to draw.
+        region: ImageRegion,
+        /// Draws the region as a nine patch image with the given slices instead of scaling it to
+        /// fit.
+        nine_patch: Option<TextureSlicer>,
+    },
+    /// Vector shapes in the local coordinates of the widget.
+    Canvas {
+        /// The shapes in painting order.
+        #[reflect(ignore)]
+        shapes: Vec<Shape>,
+    },
     /// A SVG asset.
     Svg {
         /// A handle to the SVG asset.
         handle: Handle<SvgAsset>,
         /// An optional color that replaces paths and fills within the svg.
+        ///
+        /// Add [`SvgElementColors`](crate::prelude::SvgElementColors) to recolor single elements.
         color: Option<Color>,
     },
+    /// A frame of a Lottie animation.
+    ///
+    /// Add a [`LottiePlayer`](crate::prelude::LottiePlayer) to play the animation instead of
+    /// setting the frame yourself.
+    Lottie {
+        /// A handle to the Lottie asset.
+        handle: Handle<LottieAsset>,
+        /// The frame of the animation which is drawn.
+        frame: f64,
+        /// Replaces the fill and stroke colors of layers by their name.
+        colors: Vec<(String, Color)>,
+    },
 }
 
 impl WidgetRender {
@@ -101,7 +139,10 @@ impl WidgetRender {
             WidgetRender::PopLayer => {}
             WidgetRender::Image { .. } => {}
             WidgetRender::NinePatch { .. } => {}
+            WidgetRender::AtlasImage { .. } => {}
+            WidgetRender::Canvas { .. } => {}
             WidgetRender::RenderTarget { .. } => {}
+            WidgetRender::Lottie { .. } => {}
             WidgetRender::Svg {
                 color: path_color, ..
             } => {
@@ -121,26 +162,231 @@ impl WidgetRender {
             WidgetRender::PopLayer => "PopLayer",
             WidgetRender::Image { .. } => "Image",
             WidgetRender::NinePatch { .. } => "NinePatch",
+            WidgetRender::AtlasImage { .. } => "AtlasImage",
+            WidgetRender::Canvas { .. } => "Canvas",
             WidgetRender::RenderTarget { .. } => "RenderTarget",
             WidgetRender::Svg { .. } => "Svg",
+            WidgetRender::Lottie { .. } => "Lottie",
         }
     }
 
-    pub(crate) fn render(
+    /// Returns true if the encoded output of this render command can be retained
+    /// between frames.
+    ///
+    /// Layers are never cached as they need to be balanced in the final scene, custom renders
+    /// may draw from state that changes every frame, and asset backed renders are only cached
+    /// once their asset is loaded.
+    pub(crate) fn is_cacheable(
         &self,
-        vello_scene: &mut VelloScene,
+        image_assets: &Assets<Image>,
+        svg_assets: &Assets<SvgAsset>,
+        lottie_assets: &Assets<LottieAsset>,
+        atlas_layouts: &Assets<TextureAtlasLayout>,
+    ) -> bool {
+        match self {
+            WidgetRender::Layer | WidgetRender::PopLayer | WidgetRender::Custom { .. } => false,
+            WidgetRender::Image { handle }
+            | WidgetRender::NinePatch { handle, .. }
+            | WidgetRender::RenderTarget { handle } => image_assets.contains(handle),
+            WidgetRender::AtlasImage { handle, region, .. } => {
+                image_assets.contains(handle)
+                    && match region {
+                        ImageRegion::Atlas(atlas) => atlas_layouts.contains(&atlas.layout),
+                        ImageRegion::Uv(_) => true,
+                    }
+            }
+            WidgetRender::Svg { handle, .. } => svg_assets.contains(handle),
+            WidgetRender::Lottie { handle, .. } => lottie_assets.contains(handle),
+            WidgetRender::RichText { content } => {
+                content.inline.iter().all(|item| match &item.content {
+                    InlineContent::Image(handle) => image_assets.contains(handle),
+                    InlineContent::Svg { handle, .. } => svg_assets.contains(handle),
+                    InlineContent::Widget(_) => true,
+                })
+            }
+            WidgetRender::Quad | WidgetRender::Text { .. } | WidgetRender::Canvas { .. } => true,
+        }
+    }
+
+    /// Returns true if this render command draws the given asset.
+    pub(crate) fn uses_asset(&self, id: UntypedAssetId) -> bool {
+        match self {
+            WidgetRender::Image { handle }
+            | WidgetRender::NinePatch { handle, .. }
+            | WidgetRender::RenderTarget { handle }
+            | WidgetRender::AtlasImage { handle, .. } => handle.id().untyped() == id,
+            WidgetRender::Svg { handle, .. } => handle.id().untyped() == id,
+            WidgetRender::Lottie { handle, .. } => handle.id().untyped() == id,
+            WidgetRender::RichText { content } => {
+                content.inline.iter().any(|item| match &item.content {
+                    InlineContent::Image(handle) => handle.id().untyped() == id,
+                    InlineContent::Svg { handle, .. } => handle.id().untyped() == id,
+                    InlineContent::Widget(_) => false,
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Marks every widget drawing the given asset as changed, so only their cached fragments
+    /// are re-encoded.
+    pub(crate) fn mark_asset_users_changed(
+        widget_renders: &mut Query<&mut WidgetRender>,
+        id: impl Into<UntypedAssetId>,
+    ) {
+        let id = id.into();
+        for mut widget_render in widget_renders.iter_mut() {
+            if widget_render.uses_asset(id) {
+                widget_render.set_changed();
+            }
+        }
+    }
+
+    /// Lays out the text of a [`WidgetRender::Text`] or [`WidgetRender::RichText`] at the given
+    /// scale. Returns `None` for every other render.
+    pub(crate) fn text_layout(
+        &self,
+        parent_layout: &WidgetLayout,
+        default_font: &DefaultFont,
+        font_manager: &mut FontManager,
+        widget_style: &WoodpeckerStyle,
+        camera_scale: Vec2,
+    ) -> Option<parley::Layout<Brush>> {
+        match self {
+            WidgetRender::RichText { content } => Some(rich_text_layout(
+                content,
+                widget_style,
+                default_font,
+                font_manager,
+                camera_scale.x,
+                parent_layout.size.x * camera_scale.x,
+            )),
+            WidgetRender::Text { content } => {
+                // TODO: Cache this.
+                let mut layout_editor =
+                    parley::PlainEditor::new(widget_style.font_size * camera_scale.x);
+                layout_editor.set_text(content);
+                let styles = layout_editor.edit_styles();
+                let color = widget_style.color.to_srgba();
+                styles.insert(parley::StyleProperty::Brush(Brush::Solid(
+                    peniko::Color::new([color.red, color.green, color.blue, color.alpha]),
+                )));
+                styles.insert(parley::StyleProperty::LineHeight(
+                    widget_style
+                        .line_height
+                        .map(|lh| widget_style.font_size / lh)
+                        .unwrap_or(1.2),
+                ));
+                styles.insert(parley::StyleProperty::FontStack(
+                    font_manager
+                        .font_stack_or_default(widget_style.font.as_ref(), &default_font.0.id()),
+                ));
+                styles.insert(parley::StyleProperty::Locale(font_manager.locale()));
+
+                styles.insert(parley::StyleProperty::OverflowWrap(
+                    match widget_style.text_wrap {
+                        crate::styles::TextWrap::None => parley::OverflowWrap::Normal,
+                        crate::styles::TextWrap::Glyph => parley::OverflowWrap::Anywhere,
+                        crate::styles::TextWrap::Word => parley::OverflowWrap::BreakWord,
+                        crate::styles::TextWrap::WordOrGlyph => parley::OverflowWrap::Anywhere,
+                    },
+                ));
+                for prop in font_properties(widget_style) {
+                    styles.insert(prop);
+                }
+                for prop in decoration_properties(&widget_style.text_decoration, camera_scale.x) {
+                    styles.insert(prop);
+                }
+                layout_editor.set_width(Some(parent_layout.size.x * camera_scale.x));
+                let alignment = match widget_style
+                    .text_alignment
+                    .unwrap_or(crate::font::TextAlign::Left)
+                {
+                    crate::font::TextAlign::Left => parley::Alignment::Left,
+                    crate::font::TextAlign::Right => parley::Alignment::Right,
+                    crate::font::TextAlign::Center => parley::Alignment::Middle,
+                    crate::font::TextAlign::Justified => parley::Alignment::Justified,
+                    crate::font::TextAlign::End => parley::Alignment::End,
+                };
+                layout_editor.set_alignment(alignment);
+                let text_layout =
+                    layout_editor.layout(&mut font_manager.font_cx, &mut font_manager.layout_cx);
+
+                Some(text_layout.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// The decorated ranges of a text widget in the order they are drawn.
+    fn text_decorations(&self, widget_style: &WoodpeckerStyle) -> Vec<TextDecorationSpan> {
+        match self {
+            WidgetRender::Text { content } => vec![TextDecorationSpan {
+                range: 0..content.len(),
+                decoration: widget_style.text_decoration,
+                color: widget_style.color,
+            }],
+            WidgetRender::RichText { content } => {
+                // Decoration lines default to the color of the text they start at.
+                let color_at = |index: usize| {
+                    content
+                        .spans
+                        .iter()
+                        .rev()
+                        .filter(|span| span.range.contains(&index))
+                        .find_map(|span| span.style.color)
+                        .or_else(|| {
+                            content
+                                .highlighted
+                                .color_text
+                                .iter()
+                                .rev()
+                                .find(|color_text| color_text.range.contains(&index))
+                                .map(|color_text| color_text.color)
+                        })
+                        .unwrap_or(widget_style.color)
+                };
+                std::iter::once(TextDecorationSpan {
+                    range: 0..content.text.len(),
+                    decoration: widget_style.text_decoration,
+                    color: widget_style.color,
+                })
+                .chain(
+                    content
+                        .decorations
+                        .iter()
+                        .map(|decorated_text| TextDecorationSpan {
+                            range: decorated_text.range.clone(),
+                            decoration: decorated_text.decoration,
+                            color: color_at(decorated_text.range.start),
+                        }),
+                )
+                .collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Appends the backend neutral draw operations of this widget to `ops`.
+    ///
+    /// Returns true if a layer was pushed.
+    pub(crate) fn draw_ops(
+        &self,
+        ops: &mut Vec<DrawOp>,
         layout: &WidgetLayout,
         parent_layout: &WidgetLayout,
         default_font: &DefaultFont,
-        _font_assets: &Assets<VelloFont>,
         image_assets: &mut Assets<Image>,
         svg_assets: &Assets<SvgAsset>,
+        lottie_assets: &Assets<LottieAsset>,
+        atlas_layouts: &Assets<TextureAtlasLayout>,
         font_manager: &mut FontManager,
-        svg_manager: &mut SvgManager,
         image_manager: &mut ImageManager,
         render_targets: &mut RenderTargetImages,
         metrics: &mut WidgetMetrics,
         widget_style: &WoodpeckerStyle,
+        selection: Option<(Range<usize>, Color)>,
+        element_colors: &[(SvgSelector, Color)],
         camera_scale: Vec2,
         camera_size: Vec2,
     ) -> bool {
@@ -166,6 +412,13 @@ impl WidgetRender {
             return false;
         }
 
+        let radii = RoundedRectRadii::new(
+            widget_style.border_radius.top_left.value_or(0.0) as f64,
+            widget_style.border_radius.top_right.value_or(0.0) as f64,
+            widget_style.border_radius.bottom_right.value_or(0.0) as f64,
+            widget_style.border_radius.bottom_left.value_or(0.0) as f64,
+        );
+
         match self {
             WidgetRender::Quad => {
                 let border_left = layout.border.left.value_or(0.0) as f64;
@@ -173,306 +426,172 @@ impl WidgetRender {
                 let border_right = layout.border.right.value_or(0.0) as f64;
                 let border_bottom = layout.border.bottom.value_or(0.0) as f64;
 
-                let color = widget_style.background_color.to_srgba();
-                let border_color = widget_style.border_color.to_srgba();
-                let rect = kurbo::RoundedRect::new(
+                let outer = kurbo::RoundedRect::new(
                     location_x as f64,
                     location_y as f64,
                     location_x as f64 + size_x as f64,
                     location_y as f64 + size_y as f64,
-                    RoundedRectRadii::new(
-                        widget_style.border_radius.top_left.value_or(0.0) as f64,
-                        widget_style.border_radius.top_right.value_or(0.0) as f64,
-                        widget_style.border_radius.bottom_right.value_or(0.0) as f64,
-                        widget_style.border_radius.bottom_left.value_or(0.0) as f64,
-                    ),
-                );
-
-                vello_scene.fill(
-                    peniko::Fill::NonZero,
-                    kurbo::Affine::default(),
-                    peniko::Color::new([
-                        border_color.red,
-                        border_color.green,
-                        border_color.blue,
-                        border_color.alpha,
-                    ]),
-                    None,
-                    &rect,
+                    radii,
                 );
-
-                let rect = kurbo::RoundedRect::new(
+                let inner = kurbo::RoundedRect::new(
                     location_x as f64 + border_left,
                     location_y as f64 + border_top,
                     location_x as f64 + (size_x as f64 - border_right),
                     location_y as f64 + (size_y as f64 - border_bottom),
-                    RoundedRectRadii::new(
-                        widget_style.border_radius.top_left.value_or(0.0) as f64,
-                        widget_style.border_radius.top_right.value_or(0.0) as f64,
-                        widget_style.border_radius.bottom_right.value_or(0.0) as f64,
-                        widget_style.border_radius.bottom_left.value_or(0.0) as f64,
-                    ),
-                );
-                vello_scene.fill(
-                    peniko::Fill::NonZero,
-                    kurbo::Affine::default(),
-                    peniko::Color::new([color.red, color.green, color.blue, color.alpha]),
-                    None,
-                    &rect,
-                );
-                metrics.increase_quad_counts();
-            }
-            WidgetRender::RichText { content } => {
-                let font_name = font_manager
-                    .get_family(widget_style.font.as_ref().unwrap_or(&default_font.0.id()))
-                    .into();
-                let mut builder = font_manager.layout_cx.ranged_builder(
-                    &mut font_manager.font_cx,
-                    &content.text,
-                    1.0,
-                    true,
+                    radii,
                 );
 
-                let mut styles = StyleSet::new(widget_style.font_size * camera_scale.x);
-                let color = widget_style.color.to_srgba();
-                styles.insert(parley::StyleProperty::Brush(Brush::Solid(
-                    peniko::color::AlphaColor::new([
-                        color.red,
-                        color.green,
-                        color.blue,
-                        color.alpha,
-                    ]),
-                )));
-                styles.insert(parley::StyleProperty::LineHeight(
-                    widget_style
-                        .line_height
-                        .map(|lh| widget_style.font_size / lh)
-                        .unwrap_or(1.2),
-                ));
-                styles.insert(parley::StyleProperty::FontStack(parley::FontStack::Single(
-                    parley::FontFamily::Named(font_name),
-                )));
-                styles.insert(parley::StyleProperty::OverflowWrap(
-                    match widget_style.text_wrap {
-                        crate::styles::TextWrap::None => parley::OverflowWrap::Normal,
-                        crate::styles::TextWrap::Glyph => parley::OverflowWrap::Anywhere,
-                        crate::styles::TextWrap::Word => parley::OverflowWrap::BreakWord,
-                        crate::styles::TextWrap::WordOrGlyph => parley::OverflowWrap::Anywhere,
-                    },
-                ));
-                for prop in styles.inner().values() {
-                    builder.push_default(prop.to_owned());
+                ops.push(DrawOp::Border {
+                    outer,
+                    inner,
+                    color: widget_style.border_color,
+                });
+                if radii.as_single_radius() == Some(0.0) {
+                    ops.push(DrawOp::Rect {
+                        rect: inner.rect(),
+                        color: widget_style.background_color,
+                    });
+                } else {
+                    ops.push(DrawOp::RoundedRect {
+                        rect: inner,
+                        color: widget_style.background_color,
+                    });
                 }
-
-                let alignment = match widget_style
-                    .text_alignment
-                    .unwrap_or(crate::font::TextAlign::Left)
-                {
-                    crate::font::TextAlign::Left => parley::Alignment::Left,
-                    crate::font::TextAlign::Right => parley::Alignment::Right,
-                    crate::font::TextAlign::Center => parley::Alignment::Middle,
-                    crate::font::TextAlign::Justified => parley::Alignment::Justified,
-                    crate::font::TextAlign::End => parley::Alignment::End,
+                metrics.increase_quad_counts();
+            }
+            WidgetRender::RichText { .. } | WidgetRender::Text { .. } => {
+                let Some(text_layout) = self.text_layout(
+                    parent_layout,
+                    default_font,
+                    font_manager,
+                    widget_style,
+                    camera_scale,
+                ) else {
+                    return did_layer;
                 };
-
-                for color_text in content.highlighted.color_text.iter() {
-                    let color = color_text.color.to_srgba();
-                    builder.push(
-                        parley::StyleProperty::Brush(Brush::Solid(peniko::color::AlphaColor::new(
-                            [color.red, color.green, color.blue, color.alpha],
-                        ))),
-                        color_text.range.clone(),
-                    );
-                }
-
-                let mut layout = builder.build(&content.text);
-                layout.break_all_lines(Some(parent_layout.size.x * camera_scale.x));
-                layout.align(
-                    Some(parent_layout.size.x * camera_scale.x),
-                    alignment,
-                    parley::AlignmentOptions::default(),
-                );
-
-                for line in layout.lines() {
-                    for item in line.items() {
-                        let parley::PositionedLayoutItem::GlyphRun(glyph_run) = item else {
+                let truncation = TextTruncation::new(&text_layout, widget_style, size_x);
+                if let WidgetRender::RichText { content } = self {
+                    for inline_box in inline_boxes(&text_layout) {
+                        if inline_box.y >= truncation.height {
+                            continue;
+                        }
+                        let Some(item) = content.inline.get(inline_box.id as usize) else {
                             continue;
                         };
-
-                        let mut x = glyph_run.offset();
-                        let y = glyph_run.baseline();
-                        let run = glyph_run.run();
-                        let font = run.font();
-                        let font_size = run.font_size();
-                        let synthesis = run.synthesis();
-                        let style = glyph_run.style();
-
-                        let posx = location_x;
-                        let posy = location_y;
-
-                        // Culling
-                        let mut glyph_xform = synthesis
-                            .skew()
-                            .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0))
-                            .unwrap_or_else(vello::kurbo::Affine::default);
-
-                        let trans = glyph_xform.translation();
-                        glyph_xform = glyph_xform.with_translation(
-                            trans + bevy_vello::vello::kurbo::Vec2::new(posx as f64, posy as f64),
-                        );
-
-                        vello_scene
-                            .draw_glyphs(font)
-                            .hint(true)
-                            .font_size(font_size)
-                            .transform(glyph_xform)
-                            .normalized_coords(run.normalized_coords())
-                            .brush(&style.brush)
-                            .draw(
-                                vello::peniko::Fill::NonZero,
-                                glyph_run.glyphs().map(|glyph| {
-                                    let gx = x + glyph.x;
-                                    let gy = y - glyph.y;
-                                    x += glyph.advance;
-                                    vello::Glyph {
-                                        id: glyph.id as _,
-                                        x: gx,
-                                        y: gy,
-                                    }
-                                }),
-                            );
+                        if content
+                            .glyph_effect(item.index)
+                            .is_some_and(|effect| effect.alpha <= 0.0)
+                        {
+                            continue;
+                        }
+                        let location =
+                            Vec2::new(location_x + inline_box.x, location_y + inline_box.y);
+                        let size = Vec2::new(inline_box.width, inline_box.height);
+                        ops.extend(match &item.content {
+                            InlineContent::Image(handle) => image_op(
+                                handle,
+                                image_assets,
+                                image_manager,
+                                widget_style,
+                                location,
+                                size,
+                            ),
+                            InlineContent::Svg { handle, color } => {
+                                svg_op(handle, *color, &[], svg_assets, location, size)
+                            }
+                            // Inline widgets render themselves.
+                            InlineContent::Widget(_) => None,
+                        });
                     }
                 }
-            }
-            WidgetRender::Text { content } => {
-                // TODO: Cache this.
-                let mut layout_editor =
-                    parley::PlainEditor::new(widget_style.font_size * camera_scale.x);
-                layout_editor.set_text(content);
-                let styles = layout_editor.edit_styles();
-                styles.insert(parley::StyleProperty::LineHeight(
-                    widget_style
-                        .line_height
-                        .map(|lh| widget_style.font_size / lh)
-                        .unwrap_or(1.2),
-                ));
-                styles.insert(parley::StyleProperty::FontStack(parley::FontStack::Single(
-                    parley::FontFamily::Named(
-                        font_manager
-                            .get_family(widget_style.font.as_ref().unwrap_or(&default_font.0.id()))
-                            .into(),
-                    ),
-                )));
 
-                styles.insert(parley::StyleProperty::OverflowWrap(
-                    match widget_style.text_wrap {
-                        crate::styles::TextWrap::None => parley::OverflowWrap::Normal,
-                        crate::styles::TextWrap::Glyph => parley::OverflowWrap::Anywhere,
-                        crate::styles::TextWrap::Word => parley::OverflowWrap::BreakWord,
-                        crate::styles::TextWrap::WordOrGlyph => parley::OverflowWrap::Anywhere,
-                    },
-                ));
-                layout_editor.set_width(Some(parent_layout.size.x * camera_scale.x));
-                let alignment = match widget_style
-                    .text_alignment
-                    .unwrap_or(crate::font::TextAlign::Left)
-                {
-                    crate::font::TextAlign::Left => parley::Alignment::Left,
-                    crate::font::TextAlign::Right => parley::Alignment::Right,
-                    crate::font::TextAlign::Center => parley::Alignment::Middle,
-                    crate::font::TextAlign::Justified => parley::Alignment::Justified,
-                    crate::font::TextAlign::End => parley::Alignment::End,
+                let mut decorations = self.text_decorations(widget_style);
+                if let Some((range, color)) = selection {
+                    decorations.push(TextDecorationSpan {
+                        range,
+                        decoration: TextDecoration::NONE.with_highlight(color),
+                        color,
+                    });
+                }
+                let glyph_effects = match self {
+                    WidgetRender::RichText { content } => content.glyph_effects.as_slice(),
+                    _ => &[],
                 };
-                layout_editor.set_alignment(alignment);
-                let text_layout =
-                    layout_editor.layout(&mut font_manager.font_cx, &mut font_manager.layout_cx);
-
-                for line in text_layout.lines() {
-                    for item in line.items() {
-                        let parley::PositionedLayoutItem::GlyphRun(glyph_run) = item else {
-                            continue;
-                        };
-
-                        let mut x = glyph_run.offset();
-                        let y = glyph_run.baseline();
-                        let run = glyph_run.run();
-                        let font = run.font();
-                        let font_size = run.font_size();
-                        let synthesis = run.synthesis();
-
-                        let posx = location_x;
-                        let posy = location_y;
-
-                        // Culling
-                        let mut glyph_xform = synthesis
-                            .skew()
-                            .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0))
-                            .unwrap_or_else(vello::kurbo::Affine::default);
-
-                        let trans = glyph_xform.translation();
-                        glyph_xform = glyph_xform.with_translation(
-                            trans + bevy_vello::vello::kurbo::Vec2::new(posx as f64, posy as f64),
-                        );
-
-                        let color = widget_style.color.to_srgba();
-
-                        vello_scene
-                            .draw_glyphs(font)
-                            .hint(true)
-                            .font_size(font_size)
-                            .transform(glyph_xform)
-                            .normalized_coords(run.normalized_coords())
-                            .brush(&Brush::Solid(vello::peniko::Color::new([
-                                color.red,
-                                color.green,
-                                color.blue,
-                                color.alpha,
-                            ])))
-                            .draw(
-                                vello::peniko::Fill::NonZero,
-                                glyph_run.glyphs().map(|glyph| {
-                                    let gx = x + glyph.x;
-                                    let gy = y - glyph.y;
-                                    x += glyph.advance;
-                                    vello::Glyph {
-                                        id: glyph.id as _,
-                                        x: gx,
-                                        y: gy,
-                                    }
-                                }),
-                            );
-                    }
+                let clip = truncation.truncated && widget_style.text_overflow == TextOverflow::Clip;
+                if clip {
+                    ops.push(DrawOp::PushLayer {
+                        clip: kurbo::RoundedRect::new(
+                            location_x as f64,
+                            location_y as f64,
+                            (location_x + size_x) as f64,
+                            (location_y + truncation.height) as f64,
+                            0.0,
+                        ),
+                        opacity: 1.0,
+                    });
+                }
+                text_layout_ops(
+                    ops,
+                    &text_layout,
+                    location_x,
+                    location_y,
+                    &decorations,
+                    glyph_effects,
+                    &truncation,
+                    widget_style,
+                    camera_scale.x,
+                );
+                if clip {
+                    ops.push(DrawOp::PopLayer);
                 }
             }
             WidgetRender::Custom { render } => {
-                render.render(vello_scene, layout, widget_style, camera_scale.x);
+                ops.push(DrawOp::Custom {
+                    render: render.clone(),
+                    layout: *layout,
+                    styles: Box::new(*widget_style),
+                    scale: camera_scale.x,
+                });
             }
             WidgetRender::Layer => {
-                let mask_blend = vello::peniko::BlendMode::new(
-                    vello::peniko::Mix::Normal,
-                    vello::peniko::Compose::SrcOver,
-                );
-                vello_scene.push_layer(
-                    mask_blend,
-                    widget_style.opacity,
-                    Affine::default(),
-                    &kurbo::RoundedRect::new(
+                ops.push(DrawOp::PushLayer {
+                    clip: kurbo::RoundedRect::new(
                         location_x as f64,
                         location_y as f64,
                         location_x as f64 + size_x as f64,
                         location_y as f64 + size_y as f64,
-                        RoundedRectRadii::new(
-                            widget_style.border_radius.top_left.value_or(0.0) as f64,
-                            widget_style.border_radius.top_right.value_or(0.0) as f64,
-                            widget_style.border_radius.bottom_right.value_or(0.0) as f64,
-                            widget_style.border_radius.bottom_left.value_or(0.0) as f64,
-                        ),
+                        radii,
                     ),
-                );
+                    opacity: widget_style.opacity,
+                });
                 did_layer = true;
             }
             WidgetRender::PopLayer => {
-                vello_scene.pop_layer();
+                ops.push(DrawOp::PopLayer);
+            }
+            WidgetRender::Canvas { shapes } => {
+                let transform =
+                    Affine::scale_non_uniform(camera_scale.x as f64, camera_scale.y as f64)
+                        .then_translate(kurbo::Vec2::new(location_x as f64, location_y as f64));
+                for shape in shapes.iter() {
+                    let path = shape.primitive.to_path();
+                    if let Some(fill) = &shape.fill {
+                        ops.push(DrawOp::Path {
+                            path: path.clone(),
+                            transform,
+                            brush: fill.to_vello_brush(),
+                            stroke: None,
+                        });
+                    }
+                    if let Some(stroke) = &shape.stroke {
+                        ops.push(DrawOp::Path {
+                            path,
+                            transform,
+                            brush: stroke.brush.to_vello_brush(),
+                            stroke: Some(stroke.to_kurbo_stroke()),
+                        });
+                    }
+                }
             }
             WidgetRender::Image {
                 handle: image_handle,
@@ -480,152 +599,108 @@ impl WidgetRender {
                 let Some(image) = image_assets.get(image_handle) else {
                     return did_layer;
                 };
-
-                let scale = fit_image(image.size().as_vec2(), Vec2::new(size_x, size_y)) as f64;
-
-                let transform = vello::kurbo::Affine::scale(scale).with_translation(
-                    bevy_vello::prelude::kurbo::Vec2::new(location_x as f64, location_y as f64),
+                let Some(vello_image) = cached_image(
+                    image_handle.id(),
+                    image,
+                    image_manager,
+                    widget_style.image_quality.into(),
+                ) else {
+                    return did_layer;
+                };
+                fitted_image_ops(
+                    ops,
+                    vello_image,
+                    image.size().as_vec2(),
+                    widget_style,
+                    radii,
+                    Vec2::new(location_x, location_y),
+                    Vec2::new(size_x, size_y),
                 );
-
-                let image_quality = widget_style.image_quality.into();
-                let vello_image = image_manager
-                    .images
-                    .entry(image_handle.into())
-                    .or_insert_with(move || {
-                        let mut image = peniko::Image::new(
-                            image.data.clone().unwrap().into(), // TODO: Don't unwrap here.
-                            peniko::ImageFormat::Rgba8,
-                            image.size().x,
-                            image.size().y,
-                        );
-                        image.quality = image_quality;
-                        image
-                    });
-
-                vello_scene.draw_image(vello_image, transform);
             }
             WidgetRender::Svg {
                 handle,
                 color: path_color,
             } => {
-                let Some(svg_asset) = svg_assets.get(handle) else {
-                    return did_layer;
-                };
-
-                let (width, height) = (svg_asset.width, svg_asset.height);
-
-                let transform = vello::kurbo::Affine::scale(fit_image(
-                    Vec2::new(width, height),
+                ops.extend(svg_op(
+                    handle,
+                    *path_color,
+                    element_colors,
+                    svg_assets,
+                    Vec2::new(location_x, location_y),
+                    Vec2::new(size_x, size_y),
+                ));
+            }
+            WidgetRender::Lottie {
+                handle,
+                frame,
+                colors,
+            } => {
+                ops.extend(lottie_op(
+                    handle,
+                    *frame,
+                    colors,
+                    lottie_assets,
+                    Vec2::new(location_x, location_y),
                     Vec2::new(size_x, size_y),
-                ) as f64)
-                .with_translation(bevy_vello::prelude::kurbo::Vec2::new(
-                    location_x as f64,
-                    location_y as f64,
                 ));
-
-                let Some(svg_scene) = svg_manager.get_cached(handle, svg_assets, *path_color)
-                else {
-                    return did_layer;
-                };
-
-                vello_scene.append(&svg_scene, Some(transform));
             }
             WidgetRender::NinePatch { handle, scale_mode } => {
                 let Some(image) = image_assets.get(handle) else {
                     return did_layer;
                 };
-
-                let image_rect = Rect {
-                    min: Vec2::ZERO,
-                    max: Vec2::new(image.size().x as f32, image.size().y as f32),
+                let image_rect = URect::from_corners(UVec2::ZERO, image.size());
+                nine_patch_ops(
+                    ops,
+                    handle.id(),
+                    image,
+                    image_rect,
+                    scale_mode,
+                    image_manager,
+                    widget_style,
+                    Vec2::new(location_x, location_y),
+                    Vec2::new(size_x, size_y),
+                );
+            }
+            WidgetRender::AtlasImage {
+                handle,
+                region,
+                nine_patch,
+            } => {
+                let Some(image) = image_assets.get(handle) else {
+                    return did_layer;
                 };
-                let layout_size = Vec2::new(size_x, size_y);
-                let slices = match scale_mode {
-                    SpriteImageMode::Auto => {
-                        todo!("Not supported yet!");
-                    }
-                    SpriteImageMode::Sliced(slicer) => {
-                        slicer.compute_slices(image_rect, Some(layout_size))
-                    }
-                    SpriteImageMode::Tiled {
-                        tile_x,
-                        tile_y,
-                        stretch_value,
-                    } => {
-                        let slice = TextureSlice {
-                            texture_rect: image_rect,
-                            draw_size: layout_size,
-                            offset: Vec2::ZERO,
-                        };
-                        slice.tiled(*stretch_value, (*tile_x, *tile_y))
-                    }
-                    SpriteImageMode::Scale(_) => todo!("Not supported yet!"),
+                let Some(image_rect) = region.pixel_rect(image.size(), atlas_layouts) else {
+                    return did_layer;
                 };
-
-                fn subsection_image_data(image: &mut image::DynamicImage, region: Rect) -> Vec<u8> {
-                    let sub_image = image
-                        .sub_image(
-                            region.min.x as u32,
-                            region.min.y as u32,
-                            region.size().x as u32,
-                            region.size().y as u32,
-                        )
-                        .to_image();
-                    // let _ = sub_image.save_with_format(format!("image{}{}.png", region.min.x, region.min.y), image::ImageFormat::Png);
-                    sub_image.as_raw().clone()
-                }
-
-                for slice in slices.iter() {
-                    let texture_rect_floor = Rect {
-                        min: slice.texture_rect.min,
-                        max: slice.texture_rect.max,
-                    };
-                    let min = texture_rect_floor.min.as_uvec2();
-                    let max = texture_rect_floor.max.as_uvec2();
-
-                    let mut hasher = DefaultHasher::default();
-                    min.hash(&mut hasher);
-                    max.hash(&mut hasher);
-                    let key = hasher.finish();
-
-                    if !image_manager.nine_patch_slices.contains_key(&key) {
-                        let image = image::RgbaImage::from_raw(
-                            image.size().x,
-                            image.size().y,
-                            image.data.clone().unwrap().clone(), // TODO: replace unwrap with continue/return.
-                        )
-                        .unwrap();
-                        let mut image: image::DynamicImage = image::DynamicImage::ImageRgba8(image);
-                        let sub_section_data =
-                            subsection_image_data(&mut image, texture_rect_floor);
-                        let image_quality = widget_style.image_quality.into();
-                        let mut vello_image = peniko::Image::new(
-                            sub_section_data.into(),
-                            peniko::ImageFormat::Rgba8,
-                            texture_rect_floor.size().x as u32,
-                            texture_rect_floor.size().y as u32,
-                        );
-                        vello_image.quality = image_quality;
-                        image_manager.nine_patch_slices.insert(key, vello_image);
-                    }
-
-                    let vello_image = image_manager.nine_patch_slices.get(&key).unwrap();
-                    let scale = slice.draw_size / texture_rect_floor.size();
-                    let pos = (
-                        slice.offset.x.round() + (size_x / 2.0),
-                        -slice.offset.y.round() + (size_y / 2.0),
+                let location = Vec2::new(location_x, location_y);
+                let size = Vec2::new(size_x, size_y);
+                if let Some(slicer) = nine_patch {
+                    nine_patch_ops(
+                        ops,
+                        handle.id(),
+                        image,
+                        image_rect,
+                        &SpriteImageMode::Sliced(slicer.clone()),
+                        image_manager,
+                        widget_style,
+                        location,
+                        size,
+                    );
+                } else if let Some(vello_image) = image_manager.region(
+                    handle.id(),
+                    image,
+                    image_rect,
+                    widget_style.image_quality.into(),
+                ) {
+                    fitted_image_ops(
+                        ops,
+                        vello_image,
+                        image_rect.size().as_vec2(),
+                        widget_style,
+                        radii,
+                        location,
+                        size,
                     );
-
-                    let transform =
-                        vello::kurbo::Affine::scale_non_uniform(scale.x as f64, scale.y as f64)
-                            .with_translation(bevy_vello::prelude::kurbo::Vec2::new(
-                                (location_x as f64 + pos.0 as f64)
-                                    - (slice.draw_size.x as f64 / 2.0),
-                                (location_y as f64 + pos.1 as f64)
-                                    - (slice.draw_size.y as f64 / 2.0),
-                            ));
-
-                    vello_scene.draw_image(vello_image, transform);
                 }
             }
             WidgetRender::RenderTarget { handle } => {
@@ -634,18 +709,6 @@ impl WidgetRender {
                 };
                 let image_texture_descriptor = image.texture_descriptor.clone();
 
-                let scale = fit_image(
-                    Vec2::new(
-                        image_texture_descriptor.size.width as f32,
-                        image_texture_descriptor.size.height as f32,
-                    ),
-                    Vec2::new(size_x, size_y),
-                ) as f64;
-
-                let transform = vello::kurbo::Affine::scale(scale).with_translation(
-                    bevy_vello::prelude::kurbo::Vec2::new(location_x as f64, location_y as f64),
-                );
-
                 if !render_targets.images.contains_key(handle) {
                     let mut conv_image = Image::new_uninit(
                         image_texture_descriptor.size,
@@ -676,13 +739,951 @@ impl WidgetRender {
                     render_targets.vello_images.insert(handle.clone(), image);
                 }
                 let vello_image = render_targets.vello_images.get(handle).unwrap();
-                vello_scene.draw_image(vello_image, transform);
+                fitted_image_ops(
+                    ops,
+                    vello_image.clone(),
+                    Vec2::new(
+                        image_texture_descriptor.size.width as f32,
+                        image_texture_descriptor.size.height as f32,
+                    ),
+                    widget_style,
+                    radii,
+                    Vec2::new(location_x, location_y),
+                    Vec2::new(size_x, size_y),
+                );
             }
         }
         did_layer
     }
 }
 
+/// Lays out rich text with its spans at the given scale, wrapping at `width` physical pixels.
+pub(crate) fn rich_text_layout(
+    content: &RichText,
+    widget_style: &WoodpeckerStyle,
+    default_font: &DefaultFont,
+    font_manager: &mut FontManager,
+    scale: f32,
+    width: f32,
+) -> parley::Layout<Brush> {
+    // Fonts which aren't loaded yet fall back to the font of the widget, then the default font.
+    let font_stack =
+        font_manager.font_stack_or_default(widget_style.font.as_ref(), &default_font.0.id());
+    let locale = font_manager.locale();
+    let span_stacks = content
+        .spans
+        .iter()
+        .map(|span| {
+            span.style
+                .font
+                .and_then(|font| font_manager.try_font_stack(&font))
+        })
+        .collect::<Vec<_>>();
+    let mut builder =
+        font_manager
+            .layout_cx
+            .ranged_builder(&mut font_manager.font_cx, &content.text, 1.0, true);
+
+    let mut styles = StyleSet::new(widget_style.font_size * scale);
+    styles.insert(parley::StyleProperty::Brush(Brush::Solid(to_vello_color(
+        widget_style.color,
+    ))));
+    styles.insert(parley::StyleProperty::LineHeight(
+        widget_style
+            .line_height
+            .map(|lh| widget_style.font_size / lh)
+            .unwrap_or(1.2),
+    ));
+    styles.insert(parley::StyleProperty::FontStack(font_stack));
+    styles.insert(parley::StyleProperty::Locale(locale));
+    styles.insert(parley::StyleProperty::OverflowWrap(
+        match widget_style.text_wrap {
+            crate::styles::TextWrap::None => parley::OverflowWrap::Normal,
+            crate::styles::TextWrap::Glyph => parley::OverflowWrap::Anywhere,
+            crate::styles::TextWrap::Word => parley::OverflowWrap::BreakWord,
+            crate::styles::TextWrap::WordOrGlyph => parley::OverflowWrap::Anywhere,
+        },
+    ));
+    for prop in styles.inner().values() {
+        builder.push_default(prop.to_owned());
+    }
+    for prop in font_properties(widget_style) {
+        builder.push_default(prop);
+    }
+    for prop in decoration_properties(&widget_style.text_decoration, scale) {
+        builder.push_default(prop);
+    }
+
+    let alignment = match widget_style
+        .text_alignment
+        .unwrap_or(crate::font::TextAlign::Left)
+    {
+        crate::font::TextAlign::Left => parley::Alignment::Left,
+        crate::font::TextAlign::Right => parley::Alignment::Right,
+        crate::font::TextAlign::Center => parley::Alignment::Middle,
+        crate::font::TextAlign::Justified => parley::Alignment::Justified,
+        crate::font::TextAlign::End => parley::Alignment::End,
+    };
+
+    for color_text in content.highlighted.color_text.iter() {
+        builder.push(
+            parley::StyleProperty::Brush(Brush::Solid(to_vello_color(color_text.color))),
+            color_text.range.clone(),
+        );
+    }
+    for (span, font_stack) in content.spans.iter().zip(span_stacks) {
+        let style = &span.style;
+        let font_size = style.font_size.unwrap_or(widget_style.font_size);
+        let mut properties = vec![];
+        if let Some(color) = style.color {
+            properties.push(parley::StyleProperty::Brush(Brush::Solid(to_vello_color(
+                color,
+            ))));
+        }
+        if let Some(font_stack) = font_stack {
+            properties.push(parley::StyleProperty::FontStack(font_stack));
+        }
+        if style.font_size.is_some() {
+            properties.push(parley::StyleProperty::FontSize(font_size * scale));
+        }
+        if let Some(weight) = style.font_weight {
+            properties.push(parley::StyleProperty::FontWeight(weight.into()));
+        }
+        if let Some(font_style) = style.font_style {
+            properties.push(parley::StyleProperty::FontStyle(font_style.into()));
+        }
+        if let Some(letter_spacing) = style.letter_spacing {
+            properties.push(parley::StyleProperty::LetterSpacing(letter_spacing * scale));
+        }
+        if let Some(line_height) = style.line_height {
+            properties.push(parley::StyleProperty::LineHeight(font_size / line_height));
+        }
+        for prop in properties {
+            builder.push(prop, span.range.clone());
+        }
+    }
+    for decorated_text in content.decorations.iter() {
+        for prop in decoration_properties(&decorated_text.decoration, scale) {
+            builder.push(prop, decorated_text.range.clone());
+        }
+    }
+    for (id, item) in content.inline.iter().enumerate() {
+        builder.push_inline_box(parley::InlineBox {
+            id: id as u64,
+            index: item.index,
+            width: item.size.x * scale,
+            height: item.size.y * scale,
+        });
+    }
+
+    let mut layout = builder.build(&content.text);
+    layout.break_all_lines(Some(width));
+    layout.align(Some(width), alignment, parley::AlignmentOptions::default());
+    layout
+}
+
+/// A decorated range of text.
+struct TextDecorationSpan {
+    range: Range<usize>,
+    decoration: TextDecoration,
+    /// The color used for overlines without a color.
+    color: Color,
+}
+
+/// The parley styles selecting the font face, axes and features of a widget style.
+pub(crate) fn font_properties(
+    widget_style: &WoodpeckerStyle,
+) -> Vec<parley::StyleProperty<'static, Brush>> {
+    let mut properties = vec![
+        parley::StyleProperty::FontWeight(widget_style.font_weight.into()),
+        parley::StyleProperty::FontStyle(widget_style.font_style.into()),
+        parley::StyleProperty::FontWidth(widget_style.font_stretch.into()),
+    ];
+    if !widget_style.font_variations.is_empty() {
+        let variations = widget_style
+            .font_variations
+            .iter()
+            .map(|variation| parley::FontVariation {
+                tag: u32::from_be_bytes(variation.tag),
+                value: variation.value,
+            })
+            .collect::<Vec<_>>();
+        properties.push(parley::StyleProperty::FontVariations(
+            parley::FontSettings::List(variations.into()),
+        ));
+    }
+    if !widget_style.font_features.is_empty() {
+        let features = widget_style
+            .font_features
+            .iter()
+            .map(|feature| parley::FontFeature {
+                tag: u32::from_be_bytes(feature.tag),
+                value: feature.value,
+            })
+            .collect::<Vec<_>>();
+        properties.push(parley::StyleProperty::FontFeatures(
+            parley::FontSettings::List(features.into()),
+        ));
+    }
+    properties
+}
+
+/// The parley styles for the underline and strikethrough of a text decoration.
+///
+/// Only decorations which are set are returned so spans add to the decoration of their widget.
+fn decoration_properties(
+    decoration: &TextDecoration,
+    scale: f32,
+) -> Vec<parley::StyleProperty<'static, Brush>> {
+    let mut properties = vec![];
+    if let Some(line) = decoration.underline {
+        properties.push(parley::StyleProperty::Underline(true));
+        properties.push(parley::StyleProperty::UnderlineBrush(
+            line.color.map(|color| Brush::Solid(to_vello_color(color))),
+        ));
+        properties.push(parley::StyleProperty::UnderlineSize(
+            line.thickness.map(|thickness| thickness * scale),
+        ));
+    }
+    if let Some(line) = decoration.strikethrough {
+        properties.push(parley::StyleProperty::Strikethrough(true));
+        properties.push(parley::StyleProperty::StrikethroughBrush(
+            line.color.map(|color| Brush::Solid(to_vello_color(color))),
+        ));
+        properties.push(parley::StyleProperty::StrikethroughSize(
+            line.thickness.map(|thickness| thickness * scale),
+        ));
+    }
+    properties
+}
+
+/// Which lines of a text layout are drawn and where they are cut off.
+pub(crate) struct TextTruncation {
+    /// The number of lines drawn.
+    pub(crate) visible_lines: usize,
+    /// The height of the drawn lines.
+    pub(crate) height: f32,
+    /// The width lines are cut off at.
+    pub(crate) width: f32,
+    /// How lines are cut off.
+    pub(crate) overflow: TextOverflow,
+    /// True if any text is hidden.
+    pub(crate) truncated: bool,
+}
+
+impl TextTruncation {
+    /// Computes the truncation of a text layout inside of a widget of the given width.
+    pub(crate) fn new(
+        text_layout: &parley::Layout<Brush>,
+        widget_style: &WoodpeckerStyle,
+        width: f32,
+    ) -> Self {
+        let line_count = text_layout.len();
+        let visible_lines = widget_style
+            .max_lines
+            .map(|max_lines| (max_lines as usize).min(line_count))
+            .unwrap_or(line_count);
+        let height = text_layout
+            .lines()
+            .take(visible_lines)
+            .map(|line| line.metrics().line_height)
+            .sum();
+        let overflows_width = widget_style.text_overflow != TextOverflow::Visible
+            && text_layout
+                .lines()
+                .take(visible_lines)
+                .any(|line| line.metrics().advance > width + 0.5);
+        Self {
+            visible_lines,
+            height,
+            width,
+            overflow: widget_style.text_overflow,
+            truncated: visible_lines < line_count || overflows_width,
+        }
+    }
+
+    /// Returns the x coordinate a line is cut off at, if it is cut off.
+    fn cut_off(&self, line_index: usize, line: &parley::Line<'_, Brush>) -> Option<f32> {
+        if !matches!(self.overflow, TextOverflow::Ellipsis | TextOverflow::Fade) {
+            return None;
+        }
+        let advance = line.metrics().advance;
+        if advance > self.width + 0.5 {
+            Some(self.width)
+        } else if self.truncated && line_index + 1 == self.visible_lines {
+            Some(advance)
+        } else {
+            None
+        }
+    }
+}
+
+/// Appends the glyph runs of a text layout positioned at the given location to `ops`.
+///
+/// Highlights are drawn behind the glyphs, underlines, strikethroughs and overlines on top.
+/// Underlines and strikethroughs only cover the glyphs which aren't hidden by a glyph effect.
+fn text_layout_ops(
+    ops: &mut Vec<DrawOp>,
+    text_layout: &parley::Layout<Brush>,
+    location_x: f32,
+    location_y: f32,
+    decorations: &[TextDecorationSpan],
+    glyph_effects: &[GlyphEffect],
+    truncation: &TextTruncation,
+    widget_style: &WoodpeckerStyle,
+    scale: f32,
+) {
+    for span in decorations.iter() {
+        let Some(color) = span.decoration.highlight else {
+            continue;
+        };
+        span_geometry(text_layout, span.range.clone(), |x0, x1, line| {
+            let metrics = line.metrics();
+            if metrics.min_coord >= truncation.height {
+                return;
+            }
+            ops.push(DrawOp::Rect {
+                rect: kurbo::Rect::new(
+                    (location_x + x0) as f64,
+                    (location_y + metrics.baseline - metrics.ascent) as f64,
+                    (location_x + x1) as f64,
+                    (location_y + metrics.baseline + metrics.descent) as f64,
+                ),
+                color,
+            });
+        });
+    }
+
+    let glyphs_start = ops.len();
+    for (line_index, line) in text_layout
+        .lines()
+        .enumerate()
+        .take(truncation.visible_lines)
+    {
+        let cut_off = truncation.cut_off(line_index, &line);
+        // Glyphs after this are hidden, an ellipsis is drawn in their place.
+        let mut ellipsis = None;
+        let limit = match (cut_off, truncation.overflow) {
+            (Some(cut_off), TextOverflow::Ellipsis) => {
+                let last_run = line.items().fold(None, |last, item| match item {
+                    parley::PositionedLayoutItem::GlyphRun(glyph_run)
+                        if glyph_run.offset() < cut_off =>
+                    {
+                        Some(glyph_run)
+                    }
+                    _ => last,
+                });
+                ellipsis = last_run
+                    .as_ref()
+                    .and_then(|glyph_run| ellipsis_glyphs(glyph_run.run()))
+                    .zip(last_run);
+                let ellipsis_width = ellipsis
+                    .as_ref()
+                    .map(|(glyphs, _)| glyphs.iter().map(|(_, advance)| advance).sum())
+                    .unwrap_or(0.0);
+                Some((truncation.width - ellipsis_width).min(cut_off))
+            }
+            (Some(cut_off), _) => Some(cut_off),
+            (None, _) => None,
+        };
+        let mut line_end = 0.0_f32;
+        // Glyph runs split a run by style, this is where the current glyph run starts in it.
+        let mut run_glyph_start = (0..0, 0);
+
+        for item in line.items() {
+            let parley::PositionedLayoutItem::GlyphRun(glyph_run) = item else {
+                continue;
+            };
+
+            let mut x = glyph_run.offset();
+            let y = glyph_run.baseline();
+            let run = glyph_run.run();
+            let glyph_count = glyph_run.glyphs().count();
+            let glyph_indices = if glyph_effects.is_empty() {
+                vec![]
+            } else {
+                if run_glyph_start.0 != run.text_range() {
+                    run_glyph_start = (run.text_range(), 0);
+                }
+                let indices = glyph_text_indices(run)
+                    .skip(run_glyph_start.1)
+                    .take(glyph_count)
+                    .collect::<Vec<_>>();
+                run_glyph_start.1 += glyph_count;
+                indices
+            };
+            let mut visible_end = None;
+            let transform = glyph_run_transform(&glyph_run, location_x, location_y);
+            let brush = glyph_run.style().brush.clone();
+            let glyph_op = |glyphs: Vec<vello::Glyph>, brush: Brush| {
+                DrawOp::GlyphRun(GlyphRun {
+                    font: run.font().clone(),
+                    font_size: run.font_size(),
+                    normalized_coords: run.normalized_coords().to_vec(),
+                    transform,
+                    brush,
+                    stroke: None,
+                    glyphs,
+                })
+            };
+
+            let mut glyphs = vec![];
+            for (glyph_index, glyph) in glyph_run.glyphs().enumerate() {
+                let start = x;
+                let end = x + glyph.advance;
+                x += glyph.advance;
+                let (offset, mut alpha) = glyph_indices
+                    .get(glyph_index)
+                    .and_then(|index| glyph_effect_at(glyph_effects, *index))
+                    .map(|effect| (effect.offset * scale, effect.alpha))
+                    .unwrap_or((Vec2::ZERO, 1.0));
+                if alpha <= 0.0 {
+                    continue;
+                }
+                let vello_glyph = vello::Glyph {
+                    id: glyph.id as _,
+                    x: start + glyph.x + offset.x,
+                    y: y - glyph.y + offset.y,
+                };
+                match (limit, truncation.overflow) {
+                    (Some(limit), _) if end > limit + 0.5 => continue,
+                    (Some(limit), TextOverflow::Fade) => {
+                        // Glyphs fade out over the last few em of the line.
+                        let fade_width = run.font_size() * 3.0;
+                        alpha *= ((limit - end) / fade_width + 0.1).clamp(0.0, 1.0);
+                    }
+                    _ => {}
+                }
+                line_end = line_end.max(end);
+                visible_end = Some(end);
+                if alpha < 1.0 {
+                    ops.push(glyph_op(vec![vello_glyph], fade_brush(&brush, alpha)));
+                    continue;
+                }
+                glyphs.push(vello_glyph);
+            }
+            let run_end = match (limit, visible_end) {
+                (_, None) if !glyph_effects.is_empty() => continue,
+                (_, Some(visible_end)) if !glyph_effects.is_empty() => visible_end,
+                (Some(_), _) => line_end.max(glyph_run.offset()),
+                (None, _) => glyph_run.offset() + glyph_run.advance(),
+            };
+            if !glyphs.is_empty() {
+                ops.push(glyph_op(glyphs, brush));
+            }
+
+            let style = glyph_run.style();
+            let run_metrics = run.metrics();
+            let decorations = [
+                style.underline.as_ref().map(|decoration| {
+                    (
+                        decoration,
+                        run_metrics.underline_offset,
+                        run_metrics.underline_size,
+                    )
+                }),
+                style.strikethrough.as_ref().map(|decoration| {
+                    (
+                        decoration,
+                        run_metrics.strikethrough_offset,
+                        run_metrics.strikethrough_size,
+                    )
+                }),
+            ];
+            for (decoration, offset, size) in decorations.into_iter().flatten() {
+                let Brush::Solid(color) = decoration.brush else {
+                    continue;
+                };
+                let [r, g, b, a] = color.components;
+                let top = y - decoration.offset.unwrap_or(offset);
+                let size = decoration.size.unwrap_or(size);
+                ops.push(DrawOp::Rect {
+                    rect: kurbo::Rect::new(
+                        (location_x + glyph_run.offset()) as f64,
+                        (location_y + top) as f64,
+                        (location_x + run_end) as f64,
+                        (location_y + top + size) as f64,
+                    ),
+                    color: Color::srgba(r, g, b, a),
+                });
+            }
+        }
+
+        if let Some((glyphs, glyph_run)) = ellipsis {
+            let run = glyph_run.run();
+            let mut x = line_end;
+            ops.push(DrawOp::GlyphRun(GlyphRun {
+                font: run.font().clone(),
+                font_size: run.font_size(),
+                normalized_coords: run.normalized_coords().to_vec(),
+                transform: glyph_run_transform(&glyph_run, location_x, location_y),
+                brush: glyph_run.style().brush.clone(),
+                stroke: None,
+                glyphs: glyphs
+                    .into_iter()
+                    .map(|(id, advance)| {
+                        let glyph = vello::Glyph {
+                            id,
+                            x,
+                            y: glyph_run.baseline(),
+                        };
+                        x += advance;
+                        glyph
+                    })
+                    .collect(),
+            }));
+        }
+    }
+
+    text_effect_ops(ops, glyphs_start, widget_style, scale);
+
+    for span in decorations.iter() {
+        let Some(overline) = span.decoration.overline else {
+            continue;
+        };
+        let color = overline.color.unwrap_or(span.color);
+        span_geometry(text_layout, span.range.clone(), |x0, x1, line| {
+            let metrics = line.metrics();
+            if metrics.min_coord >= truncation.height {
+                return;
+            }
+            let size = overline
+                .thickness
+                .map(|thickness| thickness * scale)
+                .unwrap_or_else(|| {
+                    line.runs()
+                        .next()
+                        .map(|run| run.metrics().underline_size)
+                        .unwrap_or(scale)
+                });
+            let top = metrics.baseline - metrics.ascent;
+            ops.push(DrawOp::Rect {
+                rect: kurbo::Rect::new(
+                    (location_x + x0) as f64,
+                    (location_y + top) as f64,
+                    (location_x + x1) as f64,
+                    (location_y + top + size) as f64,
+                ),
+                color,
+            });
+        });
+    }
+}
+
+/// Inserts the shadow and outline of the glyph runs after `start` in front of them.
+///
+/// Outlines are stroked twice as wide behind the glyphs so only the outer half shows.
+fn text_effect_ops(
+    ops: &mut Vec<DrawOp>,
+    start: usize,
+    widget_style: &WoodpeckerStyle,
+    scale: f32,
+) {
+    if widget_style.text_stroke.is_none() && widget_style.text_shadow.is_none() {
+        return;
+    }
+    let runs = ops[start..]
+        .iter()
+        .filter_map(|op| match op {
+            DrawOp::GlyphRun(run) => Some(run.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let outline_width = widget_style
+        .text_stroke
+        .map(|stroke| stroke.width * scale * 2.0);
+    // Faded glyphs keep their fade in the shadow and outline.
+    let run_alpha = |run: &GlyphRun| match &run.brush {
+        Brush::Solid(color) => color.components[3] / widget_style.color.alpha().max(f32::EPSILON),
+        _ => 1.0,
+    };
+
+    let mut effects = vec![];
+    if let Some(shadow) = widget_style.text_shadow {
+        let offset = Affine::translate(kurbo::Vec2::new(
+            (shadow.offset.x * scale) as f64,
+            (shadow.offset.y * scale) as f64,
+        ));
+        let blur = shadow.blur * scale;
+        let steps = (blur / 2.0).ceil().clamp(0.0, 6.0) as usize;
+        // Every layer covers the center, so their alpha adds up to the shadow alpha there.
+        let layer_alpha = 1.0 - (1.0 - shadow.color.alpha()).powf(1.0 / (steps + 1) as f32);
+        for run in runs.iter() {
+            let brush = Brush::Solid(to_vello_color(
+                shadow
+                    .color
+                    .with_alpha(layer_alpha * run_alpha(run).min(1.0)),
+            ));
+            let widths =
+                std::iter::once(None)
+                    .chain(outline_width.map(Some))
+                    .chain((1..=steps).map(|step| {
+                        Some(outline_width.unwrap_or(0.0) + blur * 2.0 * step as f32 / steps as f32)
+                    }));
+            for stroke in widths {
+                push_run_copy(
+                    &mut effects,
+                    run,
+                    offset * run.transform,
+                    brush.clone(),
+                    stroke,
+                );
+            }
+        }
+    }
+    if let (Some(stroke), Some(width)) = (widget_style.text_stroke, outline_width) {
+        for run in runs.iter() {
+            let brush = Brush::Solid(to_vello_color(
+                stroke
+                    .color
+                    .with_alpha(stroke.color.alpha() * run_alpha(run).min(1.0)),
+            ));
+            push_run_copy(&mut effects, run, run.transform, brush, Some(width));
+        }
+    }
+    ops.splice(start..start, effects);
+}
+
+/// Pushes a copy of a glyph run with another transform, brush and stroke.
+fn push_run_copy(
+    ops: &mut Vec<DrawOp>,
+    run: &GlyphRun,
+    transform: Affine,
+    brush: Brush,
+    stroke: Option<f32>,
+) {
+    ops.push(DrawOp::GlyphRun(GlyphRun {
+        transform,
+        brush,
+        stroke,
+        ..run.clone()
+    }));
+}
+
+/// The byte index in the text of every glyph of a run, in visual order.
+fn glyph_text_indices<'a>(run: &'a parley::Run<'a, Brush>) -> impl Iterator<Item = usize> + 'a {
+    run.visual_clusters().flat_map(|cluster| {
+        let index = cluster.text_range().start;
+        cluster.glyphs().map(move |_| index)
+    })
+}
+
+/// The glyphs and advances of an ellipsis in the font of a run.
+///
+/// Falls back to three periods if the font has no ellipsis character.
+fn ellipsis_glyphs(run: &parley::Run<'_, Brush>) -> Option<Vec<(u32, f32)>> {
+    use skrifa::{instance::Size, MetadataProvider};
+
+    let font = run.font();
+    let font_ref = skrifa::FontRef::from_index(font.data.as_ref(), font.index).ok()?;
+    let coords = run
+        .normalized_coords()
+        .iter()
+        .map(|coord| skrifa::instance::NormalizedCoord::from_bits(*coord))
+        .collect::<Vec<_>>();
+    let metrics = font_ref.glyph_metrics(
+        Size::new(run.font_size()),
+        skrifa::instance::LocationRef::new(&coords),
+    );
+    let charmap = font_ref.charmap();
+    let glyph = |c: char| {
+        charmap
+            .map(c)
+            .map(|id| (id.to_u32(), metrics.advance_width(id).unwrap_or(0.0)))
+    };
+    glyph('…')
+        .map(|glyph| vec![glyph])
+        .or_else(|| glyph('.').map(|glyph| vec![glyph; 3]))
+}
+
+/// Multiplies the alpha of a solid brush.
+fn fade_brush(brush: &Brush, alpha: f32) -> Brush {
+    match brush {
+        Brush::Solid(color) => Brush::Solid(color.multiply_alpha(alpha)),
+        brush => brush.clone(),
+    }
+}
+
+/// The positioned inline boxes of a text layout.
+pub(crate) fn inline_boxes(
+    text_layout: &parley::Layout<Brush>,
+) -> impl Iterator<Item = parley::PositionedInlineBox> + '_ {
+    text_layout.lines().flat_map(|line| {
+        line.items().filter_map(|item| match item {
+            parley::PositionedLayoutItem::InlineBox(inline_box) => Some(inline_box),
+            parley::PositionedLayoutItem::GlyphRun(_) => None,
+        })
+    })
+}
+
+/// Calls `f` with the horizontal extent of a text range on every line it covers.
+pub(crate) fn span_geometry<'a>(
+    text_layout: &'a parley::Layout<Brush>,
+    range: Range<usize>,
+    mut f: impl FnMut(f32, f32, parley::Line<'a, Brush>),
+) {
+    if range.is_empty() {
+        return;
+    }
+    let selection = parley::Selection::new(
+        parley::Cursor::from_byte_index(text_layout, range.start, parley::Affinity::Downstream),
+        parley::Cursor::from_byte_index(text_layout, range.end, parley::Affinity::Upstream),
+    );
+    selection.geometry_with(text_layout, |rect, line_index| {
+        if let Some(line) = text_layout.get(line_index) {
+            f(rect.x0 as f32, rect.x1 as f32, line);
+        }
+    });
+}
+
+/// The transform of a glyph run, including synthesized skew, at the given location.
+fn glyph_run_transform(
+    glyph_run: &parley::GlyphRun<'_, Brush>,
+    location_x: f32,
+    location_y: f32,
+) -> Affine {
+    let glyph_xform = glyph_run
+        .run()
+        .synthesis()
+        .skew()
+        .map(|angle| Affine::skew(angle.to_radians().tan() as f64, 0.0))
+        .unwrap_or_default();
+
+    let trans = glyph_xform.translation();
+    glyph_xform.with_translation(trans + kurbo::Vec2::new(location_x as f64, location_y as f64))
+}
+
+/// Computes the slices a nine patch image is drawn with.
+///
+/// `image_rect` is the part of the image in pixels which is sliced.
+pub(crate) fn nine_patch_slices(
+    image_rect: Rect,
+    scale_mode: &SpriteImageMode,
+    layout_size: Vec2,
+) -> Vec<TextureSlice> {
+    match scale_mode {
+        SpriteImageMode::Auto => {
+            todo!("Not supported yet!");
+        }
+        SpriteImageMode::Sliced(slicer) => slicer.compute_slices(image_rect, Some(layout_size)),
+        SpriteImageMode::Tiled {
+            tile_x,
+            tile_y,
+            stretch_value,
+        } => {
+            let slice = TextureSlice {
+                texture_rect: image_rect,
+                draw_size: layout_size,
+                offset: Vec2::ZERO,
+            };
+            slice.tiled(*stretch_value, (*tile_x, *tile_y))
+        }
+        SpriteImageMode::Scale(_) => todo!("Not supported yet!"),
+    }
+}
+
+/// Draws the given region of an image as a nine patch into the given rectangle.
+fn nine_patch_ops(
+    ops: &mut Vec<DrawOp>,
+    id: AssetId<Image>,
+    image: &Image,
+    image_rect: URect,
+    scale_mode: &SpriteImageMode,
+    image_manager: &mut ImageManager,
+    widget_style: &WoodpeckerStyle,
+    location: Vec2,
+    size: Vec2,
+) {
+    let slices = nine_patch_slices(image_rect.as_rect(), scale_mode, size);
+    for slice in slices.iter() {
+        let texture_rect = URect::from_corners(
+            slice.texture_rect.min.as_uvec2(),
+            slice.texture_rect.max.as_uvec2(),
+        );
+        if texture_rect.is_empty() {
+            continue;
+        }
+        let Some(vello_image) =
+            image_manager.region(id, image, texture_rect, widget_style.image_quality.into())
+        else {
+            continue;
+        };
+
+        let scale = slice.draw_size / texture_rect.size().as_vec2();
+        let pos = (
+            slice.offset.x.round() + (size.x / 2.0),
+            -slice.offset.y.round() + (size.y / 2.0),
+        );
+
+        let transform = vello::kurbo::Affine::scale_non_uniform(scale.x as f64, scale.y as f64)
+            .with_translation(bevy_vello::prelude::kurbo::Vec2::new(
+                (location.x as f64 + pos.0 as f64) - (slice.draw_size.x as f64 / 2.0),
+                (location.y as f64 + pos.1 as f64) - (slice.draw_size.y as f64 / 2.0),
+            ));
+
+        ops.push(DrawOp::Image {
+            image: vello_image,
+            transform,
+            tint: widget_style.image_tint,
+        });
+    }
+}
+
+/// Draws an image with the object fit, object position and tint of the widget.
+///
+/// The image is clipped to the rounded widget rectangle if it overflows it or has rounded
+/// corners, so no [`WidgetRender::Layer`] is needed.
+fn fitted_image_ops(
+    ops: &mut Vec<DrawOp>,
+    image: peniko::Image,
+    image_size: Vec2,
+    widget_style: &WoodpeckerStyle,
+    radii: RoundedRectRadii,
+    location: Vec2,
+    size: Vec2,
+) {
+    let fitted_size = widget_style.object_fit.fit(image_size, size);
+    let offset = widget_style.object_position.offset(fitted_size, size);
+    let scale = fitted_size / image_size;
+    let transform = Affine::scale_non_uniform(scale.x as f64, scale.y as f64).with_translation(
+        kurbo::Vec2::new(
+            (location.x + offset.x) as f64,
+            (location.y + offset.y) as f64,
+        ),
+    );
+
+    // Allow for a little rounding error so contained images don't push a layer.
+    let overflows =
+        offset.cmplt(Vec2::splat(-0.5)).any() || (offset + fitted_size).cmpgt(size + 0.5).any();
+    let clip = overflows || radii.as_single_radius() != Some(0.0);
+    if clip {
+        ops.push(DrawOp::PushLayer {
+            clip: kurbo::RoundedRect::new(
+                location.x as f64,
+                location.y as f64,
+                (location.x + size.x) as f64,
+                (location.y + size.y) as f64,
+                radii,
+            ),
+            opacity: 1.0,
+        });
+    }
+    ops.push(DrawOp::Image {
+        image,
+        transform,
+        tint: widget_style.image_tint,
+    });
+    if clip {
+        ops.push(DrawOp::PopLayer);
+    }
+}
+
+/// Returns the vello image of a bevy image, converting it the first time.
+fn cached_image(
+    id: AssetId<Image>,
+    image: &Image,
+    image_manager: &mut ImageManager,
+    quality: peniko::ImageQuality,
+) -> Option<peniko::Image> {
+    if let Some(vello_image) = image_manager.images.get(&id) {
+        return Some(vello_image.clone());
+    }
+    let mut vello_image = peniko::Image::new(
+        image.data.clone()?.into(),
+        peniko::ImageFormat::Rgba8,
+        image.size().x,
+        image.size().y,
+    );
+    vello_image.quality = quality;
+    image_manager.images.insert(id, vello_image.clone());
+    Some(vello_image)
+}
+
+/// Draws an image scaled to fit into the given rectangle.
+fn image_op(
+    image_handle: &Handle<Image>,
+    image_assets: &Assets<Image>,
+    image_manager: &mut ImageManager,
+    widget_style: &WoodpeckerStyle,
+    location: Vec2,
+    size: Vec2,
+) -> Option<DrawOp> {
+    let image = image_assets.get(image_handle)?;
+
+    let scale = fit_image(image.size().as_vec2(), size) as f64;
+
+    let transform = vello::kurbo::Affine::scale(scale).with_translation(
+        bevy_vello::prelude::kurbo::Vec2::new(location.x as f64, location.y as f64),
+    );
+
+    let vello_image = cached_image(
+        image_handle.id(),
+        image,
+        image_manager,
+        widget_style.image_quality.into(),
+    )?;
+
+    Some(DrawOp::Image {
+        image: vello_image,
+        transform,
+        tint: None,
+    })
+}
+
+/// Draws an svg scaled to fit into the given rectangle.
+fn svg_op(
+    handle: &Handle<SvgAsset>,
+    color: Option<Color>,
+    element_colors: &[(SvgSelector, Color)],
+    svg_assets: &Assets<SvgAsset>,
+    location: Vec2,
+    size: Vec2,
+) -> Option<DrawOp> {
+    let svg_asset = svg_assets.get(handle)?;
+
+    let (width, height) = (svg_asset.width, svg_asset.height);
+
+    let transform = vello::kurbo::Affine::scale(fit_image(Vec2::new(width, height), size) as f64)
+        .with_translation(bevy_vello::prelude::kurbo::Vec2::new(
+            location.x as f64,
+            location.y as f64,
+        ));
+
+    Some(DrawOp::Svg {
+        handle: handle.clone(),
+        color,
+        element_colors: element_colors.to_vec(),
+        transform,
+    })
+}
+
+/// Draws a frame of a Lottie animation scaled to fit into the given rectangle.
+fn lottie_op(
+    handle: &Handle<LottieAsset>,
+    frame: f64,
+    colors: &[(String, Color)],
+    lottie_assets: &Assets<LottieAsset>,
+    location: Vec2,
+    size: Vec2,
+) -> Option<DrawOp> {
+    let lottie_asset = lottie_assets.get(handle)?;
+
+    let (width, height) = (lottie_asset.width, lottie_asset.height);
+
+    let transform = vello::kurbo::Affine::scale(fit_image(Vec2::new(width, height), size) as f64)
+        .with_translation(bevy_vello::prelude::kurbo::Vec2::new(
+            location.x as f64,
+            location.y as f64,
+        ));
+
+    Some(DrawOp::Lottie {
+        handle: handle.clone(),
+        frame,
+        colors: colors.to_vec(),
+        transform,
+    })
+}
+
 pub(crate) fn fit_image(size_to_fit: Vec2, container_size: Vec2) -> f32 {
     let multipler = size_to_fit.x * size_to_fit.y;
     let width_scale = container_size.x / size_to_fit.x;
@@ -733,3 +1734,73 @@ impl WidgetRenderCustom {
         self.inner.clone()(vello_scene, layout, styles, dpi);
     }
 }
+
+/// Added to [`WidgetRender::Text`] and [`WidgetRender::RichText`] widgets whose text is cut off by
+/// [`WoodpeckerStyle::text_overflow`] or [`WoodpeckerStyle::max_lines`].
+///
+/// Holds the full text so it can be shown elsewhere, for example in a tooltip.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+pub struct TruncatedText {
+    /// The full text of the widget.
+    pub full_text: String,
+}
+
+/// Adds or removes [`TruncatedText`] after layout.
+pub(crate) fn update_truncated_text(
+    mut commands: Commands,
+    query: Query<
+        (
+            Entity,
+            &WidgetRender,
+            &WidgetLayout,
+            &WoodpeckerStyle,
+            Option<&ChildOf>,
+            Option<&TruncatedText>,
+        ),
+        (
+            Without<PreviousWidget>,
+            Or<(
+                Changed<WidgetLayout>,
+                Changed<WidgetRender>,
+                Changed<WoodpeckerStyle>,
+            )>,
+        ),
+    >,
+    layouts: Query<&WidgetLayout>,
+    default_font: Res<DefaultFont>,
+    mut font_manager: ResMut<FontManager>,
+) {
+    for (entity, widget_render, layout, style, parent, truncated_text) in query.iter() {
+        let full_text = match widget_render {
+            WidgetRender::Text { content } => content.as_str(),
+            WidgetRender::RichText { content } => content.text.as_str(),
+            _ => continue,
+        };
+        let truncated = (style.max_lines.is_some() || style.text_overflow != TextOverflow::Visible)
+            && parent
+                .and_then(|parent| layouts.get(parent.parent()).ok())
+                .and_then(|parent_layout| {
+                    widget_render.text_layout(
+                        parent_layout,
+                        &default_font,
+                        &mut font_manager,
+                        style,
+                        Vec2::ONE,
+                    )
+                })
+                .is_some_and(|text_layout| {
+                    TextTruncation::new(&text_layout, style, layout.size.x).truncated
+                });
+
+        if !truncated {
+            if truncated_text.is_some() {
+                commands.entity(entity).remove::<TruncatedText>();
+            }
+        } else if truncated_text.is_none_or(|truncated_text| truncated_text.full_text != full_text)
+        {
+            commands.entity(entity).insert(TruncatedText {
+                full_text: full_text.to_string(),
+            });
+        }
+    }
+}
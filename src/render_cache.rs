@@ -0,0 +1,158 @@
+use bevy::{
+    ecs::entity::{EntityHashMap, EntityHashSet},
+    prelude::*,
+};
+use bevy_vello::VelloScene;
+
+use crate::{display_list::DrawOp, layout::system::WidgetLayout, styles::WoodpeckerStyle};
+
+/// Everything a widget's encoded scene fragment depends on.
+///
+/// If any of these change between frames the fragment is re-encoded.
+#[derive(Clone, PartialEq)]
+pub(crate) struct FragmentKey {
+    pub layout: WidgetLayout,
+    pub parent_layout: WidgetLayout,
+    pub styles: WoodpeckerStyle,
+    pub camera_scale: Vec2,
+    pub camera_size: Vec2,
+}
+
+/// Everything a subtree's encoded scene fragment depends on besides the widgets inside of it.
+#[derive(Clone, PartialEq)]
+pub(crate) struct SubtreeKey {
+    pub parent_layout: WidgetLayout,
+    pub parent_z: u32,
+    pub camera_scale: Vec2,
+    pub camera_size: Vec2,
+}
+
+struct CachedFragment {
+    key: FragmentKey,
+    scene: VelloScene,
+}
+
+/// The encoded scene fragment of a whole widget subtree.
+pub(crate) struct CachedSubtree {
+    pub key: SubtreeKey,
+    /// How many draw orders the subtree takes up.
+    pub span: u32,
+    pub scene: VelloScene,
+    /// The draw operations of the subtree, only kept if something asked for them.
+    pub ops: Option<Vec<DrawOp>>,
+    /// The number of widgets and quads drawn by the subtree, for metrics.
+    pub widgets: usize,
+    pub quads: usize,
+}
+
+/// Retains encoded vello scene fragments between frames so unchanged widgets don't need to be
+/// re-encoded every frame.
+///
+/// Fragments are kept per widget and per subtree. A subtree is dirty if any widget inside of it
+/// had its style, layout, render or children changed this frame. Clean subtrees are appended
+/// as a single fragment without being traversed, and a clean root isn't re-encoded at all.
+#[derive(Resource, Default)]
+pub(crate) struct RenderCache {
+    fragments: EntityHashMap<CachedFragment>,
+    subtrees: EntityHashMap<CachedSubtree>,
+    used_subtrees: EntityHashSet,
+    dirty: EntityHashSet,
+    /// Set when every fragment was dropped so clean roots still get re-encoded.
+    cleared: bool,
+}
+
+impl RenderCache {
+    /// Returns the cached fragment for the entity if it is still valid.
+    ///
+    /// `render_changed` should be true when the entity's [`crate::prelude::WidgetRender`]
+    /// component has been modified since the last frame.
+    pub fn get(
+        &mut self,
+        entity: Entity,
+        key: &FragmentKey,
+        render_changed: bool,
+    ) -> Option<&VelloScene> {
+        if render_changed {
+            return None;
+        }
+        self.fragments
+            .get(&entity)
+            .filter(|fragment| fragment.key == *key)
+            .map(|fragment| &fragment.scene)
+    }
+
+    /// Stores a freshly encoded fragment for the entity.
+    pub fn insert(&mut self, entity: Entity, key: FragmentKey, scene: VelloScene) -> &VelloScene {
+        self.fragments.insert(entity, CachedFragment { key, scene });
+        &self.fragments[&entity].scene
+    }
+
+    /// Marks the entity and all of its ancestors as dirty.
+    pub fn mark_dirty(&mut self, entity: Entity, parent: impl Fn(Entity) -> Option<Entity>) {
+        let mut current = Some(entity);
+        while let Some(entity) = current {
+            if !self.dirty.insert(entity) {
+                break;
+            }
+            current = parent(entity);
+        }
+    }
+
+    /// Returns true if the entity or anything below it changed this frame.
+    pub fn is_dirty(&self, entity: Entity) -> bool {
+        self.cleared || self.dirty.contains(&entity)
+    }
+
+    /// Returns the cached fragment of a clean subtree if it is still valid.
+    ///
+    /// Subtrees encoded without draw operations aren't valid if `needs_ops` is true.
+    pub fn subtree(
+        &self,
+        entity: Entity,
+        key: &SubtreeKey,
+        needs_ops: bool,
+    ) -> Option<&CachedSubtree> {
+        if self.is_dirty(entity) {
+            return None;
+        }
+        self.subtrees
+            .get(&entity)
+            .filter(|subtree| subtree.key == *key && (!needs_ops || subtree.ops.is_some()))
+    }
+
+    /// Returns the cached fragment of a subtree and keeps it around for the next frame.
+    pub fn use_subtree(&mut self, entity: Entity) -> Option<&CachedSubtree> {
+        self.used_subtrees.insert(entity);
+        self.subtrees.get(&entity)
+    }
+
+    /// Stores a freshly encoded subtree fragment.
+    pub fn insert_subtree(&mut self, entity: Entity, subtree: CachedSubtree) {
+        self.used_subtrees.insert(entity);
+        self.subtrees.insert(entity, subtree);
+    }
+
+    /// Drops the fragments of a widget which was despawned or stopped rendering.
+    pub fn remove(&mut self, entity: Entity) {
+        self.fragments.remove(&entity);
+        self.subtrees.remove(&entity);
+    }
+
+    /// Drops every fragment, for example when an asset the fragments were encoded from changed.
+    pub fn clear(&mut self) {
+        self.fragments.clear();
+        self.subtrees.clear();
+        self.cleared = true;
+    }
+
+    /// Drops subtree fragments which weren't used this frame and forgets what was dirty.
+    ///
+    /// Subtrees appended as part of a larger subtree are dropped this way, so only the
+    /// outermost clean subtrees are kept.
+    pub fn end_frame(&mut self) {
+        let used = std::mem::take(&mut self.used_subtrees);
+        self.subtrees.retain(|entity, _| used.contains(entity));
+        self.dirty.clear();
+        self.cleared = false;
+    }
+}
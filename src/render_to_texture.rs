@@ -0,0 +1,184 @@
+use bevy::{
+    asset::RenderAssetUsages,
+    ecs::query::QueryItem,
+    prelude::*,
+    render::{
+        extract_component::{ExtractComponent, ExtractComponentPlugin},
+        render_asset::RenderAssets,
+        render_resource::{
+            Extent3d, TextureDimension, TextureFormat, TextureUsages, TextureViewDescriptor,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        texture::GpuImage,
+        Render, RenderApp, RenderSet,
+    },
+};
+use bevy_vello::{
+    render::VelloRenderer,
+    vello::{peniko, RenderParams},
+};
+
+use crate::RenderSettings;
+
+/// Renders the UI root it is attached to into a bevy [`Image`] instead of the screen canvas.
+///
+/// Add this next to a root widget (for example [`crate::prelude::WoodpeckerApp`]). The layout size
+/// of the tree is taken from the size of the image. To display the image on a mesh use it as a
+/// material texture and add [`WoodpeckerTextureMesh`] to the mesh entity so pointer input is
+/// forwarded to the UI.
+///
+/// Vello writes sRGB encoded colors into a `Rgba8Unorm` storage texture, so the image has to be
+/// sampled through a `Rgba8UnormSrgb` view for materials and UI nodes to see the colors of the
+/// screen canvas. [`WoodpeckerTextureTarget::create_image`] creates an image set up this way.
+#[derive(Component, Clone, Debug)]
+#[require(TextureTargetScene)]
+pub struct WoodpeckerTextureTarget {
+    /// The image the UI tree is rendered into.
+    pub image: Handle<Image>,
+    /// The scale factor the UI tree is rendered at.
+    ///
+    /// The layout size of the tree is the image size divided by this value.
+    pub scale: f32,
+}
+
+impl WoodpeckerTextureTarget {
+    /// Creates a new texture target.
+    pub fn new(image: Handle<Image>) -> Self {
+        Self { image, scale: 1.0 }
+    }
+
+    /// Sets the scale factor the UI tree is rendered at.
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// The layout size of the UI tree rendered into this target.
+    pub fn logical_size(&self, images: &Assets<Image>) -> Vec2 {
+        images
+            .get(&self.image)
+            .map(|image| image.size().as_vec2() / self.scale.max(f32::EPSILON))
+            .unwrap_or_default()
+    }
+
+    /// Creates an image that can be used as a UI texture target.
+    ///
+    /// The texture is `Rgba8Unorm` with storage binding usage so vello can render into it, and
+    /// is sampled through a `Rgba8UnormSrgb` view.
+    pub fn create_image(images: &mut Assets<Image>, size: UVec2) -> Handle<Image> {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: size.x.max(1),
+                height: size.y.max(1),
+                ..default()
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 0],
+            TextureFormat::Rgba8Unorm,
+            RenderAssetUsages::default(),
+        );
+        image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+            | TextureUsages::COPY_DST
+            | TextureUsages::STORAGE_BINDING;
+        image.texture_descriptor.view_formats = &[TextureFormat::Rgba8UnormSrgb];
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            format: Some(TextureFormat::Rgba8UnormSrgb),
+            ..default()
+        });
+        images.add(image)
+    }
+}
+
+/// Marks a mesh entity as displaying the texture of a UI root with a [`WoodpeckerTextureTarget`].
+///
+/// Pointers are ray cast against these meshes and the UV coordinates of the hit are used to pick
+/// widgets inside of the texture.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct WoodpeckerTextureMesh {
+    /// The UI root entity which renders into the texture shown on this mesh.
+    pub root: Entity,
+}
+
+/// The scene encoded for a texture target root this frame.
+#[derive(Component, Default, Clone, Deref, DerefMut)]
+pub struct TextureTargetScene(pub bevy_vello::VelloScene);
+
+impl ExtractComponent for WoodpeckerTextureTarget {
+    type QueryData = (
+        &'static WoodpeckerTextureTarget,
+        &'static TextureTargetScene,
+    );
+    type QueryFilter = ();
+    type Out = ExtractedTextureTarget;
+
+    fn extract_component((target, scene): QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(ExtractedTextureTarget {
+            image: target.image.clone(),
+            scene: scene.0.clone(),
+        })
+    }
+}
+
+#[derive(Component)]
+/// A texture target extracted into the render world.
+pub struct ExtractedTextureTarget {
+    image: Handle<Image>,
+    scene: bevy_vello::VelloScene,
+}
+
+pub(crate) struct RenderToTexturePlugin;
+
+impl Plugin for RenderToTexturePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(ExtractComponentPlugin::<WoodpeckerTextureTarget>::default());
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.add_systems(
+            Render,
+            render_texture_targets
+                .in_set(RenderSet::Render)
+                .run_if(resource_exists::<RenderDevice>),
+        );
+    }
+}
+
+fn render_texture_targets(
+    targets: Query<&ExtractedTextureTarget>,
+    gpu_images: Res<RenderAssets<GpuImage>>,
+    device: Res<RenderDevice>,
+    queue: Res<RenderQueue>,
+    renderer: Option<Res<VelloRenderer>>,
+    render_settings: Res<RenderSettings>,
+) {
+    let Some(renderer) = renderer else {
+        return;
+    };
+    for target in targets.iter() {
+        let Some(gpu_image) = gpu_images.get(&target.image) else {
+            continue;
+        };
+        // Storage textures can't be sRGB, so vello renders through a linear view of the texture.
+        let storage_view = gpu_image.texture.create_view(&TextureViewDescriptor {
+            format: Some(TextureFormat::Rgba8Unorm),
+            ..default()
+        });
+
+        if let Err(err) = renderer.lock().unwrap().render_to_texture(
+            device.wgpu_device(),
+            &queue,
+            &target.scene,
+            &storage_view,
+            &RenderParams {
+                base_color: peniko::Color::TRANSPARENT,
+                width: gpu_image.size.width,
+                height: gpu_image.size.height,
+                antialiasing_method: render_settings.antialiasing,
+            },
+        ) {
+            error!("Woodpecker UI: Failed to render UI texture target: {err}");
+        }
+    }
+}
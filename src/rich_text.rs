@@ -1,6 +1,16 @@
 use std::ops::Range;
 
-use bevy::{color::Color, reflect::Reflect};
+use bevy::{ecs::system::SystemParam, picking::pointer::PointerButton, prelude::*};
+use bevy_vello::prelude::VelloFont;
+
+use crate::{
+    font::FontManager,
+    layout::system::WidgetLayout,
+    render::{inline_boxes, rich_text_layout, span_geometry, WidgetRender},
+    styles::{FontStyle, FontWeight, TextDecoration, WoodpeckerStyle},
+    svg::SvgAsset,
+    DefaultFont,
+};
 
 /// Color Text
 #[derive(Default, Debug, Clone, Reflect, PartialEq)]
@@ -11,6 +21,167 @@ pub struct ColorText {
     pub range: Range<usize>,
 }
 
+/// Decorated text
+#[derive(Default, Debug, Clone, Reflect, PartialEq)]
+pub struct DecoratedText {
+    /// The decoration of the text, added to the decoration of the widget style.
+    pub decoration: TextDecoration,
+    /// The range of the text in the original string.
+    pub range: Range<usize>,
+}
+
+/// The styles of a span of rich text.
+///
+/// Anything that isn't set uses the style of the widget.
+#[derive(Default, Debug, Clone, Copy, Reflect, PartialEq)]
+pub struct TextSpanStyle {
+    /// The text color.
+    pub color: Option<Color>,
+    /// The font, it needs to be loaded before the text is rendered.
+    pub font: Option<AssetId<VelloFont>>,
+    /// The font size in pixels.
+    pub font_size: Option<f32>,
+    /// The font weight.
+    pub font_weight: Option<FontWeight>,
+    /// The font style, for example italic.
+    pub font_style: Option<FontStyle>,
+    /// Extra spacing between letters in pixels.
+    pub letter_spacing: Option<f32>,
+    /// The line height in pixels.
+    pub line_height: Option<f32>,
+}
+
+impl TextSpanStyle {
+    /// Bold text.
+    pub const BOLD: TextSpanStyle = TextSpanStyle {
+        color: None,
+        font: None,
+        font_size: None,
+        font_weight: Some(FontWeight::BOLD),
+        font_style: None,
+        letter_spacing: None,
+        line_height: None,
+    };
+
+    /// Italic text.
+    pub const ITALIC: TextSpanStyle = TextSpanStyle {
+        color: None,
+        font: None,
+        font_size: None,
+        font_weight: None,
+        font_style: Some(FontStyle::Italic),
+        letter_spacing: None,
+        line_height: None,
+    };
+
+    /// Sets the text color.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets the font.
+    pub fn with_font(mut self, font: impl Into<AssetId<VelloFont>>) -> Self {
+        self.font = Some(font.into());
+        self
+    }
+
+    /// Sets the font size in pixels.
+    pub fn with_font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+
+    /// Sets the font weight.
+    pub fn with_font_weight(mut self, font_weight: FontWeight) -> Self {
+        self.font_weight = Some(font_weight);
+        self
+    }
+
+    /// Sets the font style.
+    pub fn with_font_style(mut self, font_style: FontStyle) -> Self {
+        self.font_style = Some(font_style);
+        self
+    }
+
+    /// Sets the spacing between letters in pixels.
+    pub fn with_letter_spacing(mut self, letter_spacing: f32) -> Self {
+        self.letter_spacing = Some(letter_spacing);
+        self
+    }
+
+    /// Sets the line height in pixels.
+    pub fn with_line_height(mut self, line_height: f32) -> Self {
+        self.line_height = Some(line_height);
+        self
+    }
+}
+
+/// Styled text
+#[derive(Default, Debug, Clone, Reflect, PartialEq)]
+pub struct StyledText {
+    /// The styles of the text.
+    pub style: TextSpanStyle,
+    /// The range of the text in the original string.
+    pub range: Range<usize>,
+}
+
+/// An interactive range of rich text.
+///
+/// Hovering and clicking a link triggers [`TextLinkOver`], [`TextLinkOut`] and [`TextLinkClick`]
+/// on the rich text widget. The widget needs to be [`Pickable`] for links to be hit.
+#[derive(Default, Debug, Clone, Reflect, PartialEq)]
+pub struct TextLink {
+    /// A user payload passed along with the link events, for example an url or an item id.
+    pub payload: String,
+    /// The range of the text in the original string.
+    pub range: Range<usize>,
+}
+
+/// Content which flows with rich text.
+#[derive(Debug, Clone, Reflect, PartialEq)]
+pub enum InlineContent {
+    /// An image scaled to fit the inline box.
+    Image(Handle<Image>),
+    /// An svg scaled to fit the inline box.
+    Svg {
+        /// The svg asset.
+        handle: Handle<SvgAsset>,
+        /// An optional color that replaces paths and fills within the svg.
+        color: Option<Color>,
+    },
+    /// A widget which is moved onto the inline box after layout.
+    ///
+    /// The widget should use [`crate::prelude::WidgetPosition::Absolute`] so it doesn't take up
+    /// space elsewhere. It shouldn't be a child of the rich text widget as text can't be measured
+    /// for widgets with children.
+    Widget(Entity),
+}
+
+/// Content placed inline at a byte index of rich text.
+#[derive(Debug, Clone, Reflect, PartialEq)]
+pub struct InlineItem {
+    /// The content of the inline box.
+    pub content: InlineContent,
+    /// The byte index in the text the box is placed at.
+    pub index: usize,
+    /// The size of the inline box in pixels.
+    pub size: Vec2,
+}
+
+/// Moves and fades the glyphs of a range of rich text without changing the layout.
+///
+/// Used to animate text, for example by the [`crate::prelude::Typewriter`] widget.
+#[derive(Debug, Clone, Reflect, PartialEq)]
+pub struct GlyphEffect {
+    /// How far the glyphs are moved in pixels.
+    pub offset: Vec2,
+    /// The opacity of the glyphs, glyphs with an opacity of zero are hidden.
+    pub alpha: f32,
+    /// The range of the text in the original string.
+    pub range: Range<usize>,
+}
+
 /// A list of colored text
 ///
 /// Note this does not content the actual text rather it contains
@@ -22,11 +193,25 @@ pub struct Highlighted {
 }
 
 /// Rich text support
-/// currently only supports colors.
-#[derive(Default, Debug, Clone, Reflect)]
+///
+/// Spans of the text can have their own color, font, size, weight, style, spacing and
+/// decorations.
+///
+/// ```ignore
+/// RichText::new()
+///     .with_text("You found ")
+///     .with_styled_text("Excalibur", TextSpanStyle::BOLD.with_color(Srgba::RED.into()))
+///     .with_styled_text(" (+5 str)", TextSpanStyle::ITALIC.with_font_size(14.0));
+/// ```
+#[derive(Default, Debug, Clone, Reflect, PartialEq)]
 pub struct RichText {
     pub(crate) text: String,
     pub(crate) highlighted: Highlighted,
+    pub(crate) decorations: Vec<DecoratedText>,
+    pub(crate) spans: Vec<StyledText>,
+    pub(crate) links: Vec<TextLink>,
+    pub(crate) inline: Vec<InlineItem>,
+    pub(crate) glyph_effects: Vec<GlyphEffect>,
     current_index: usize,
 }
 
@@ -37,6 +222,7 @@ impl RichText {
             text: String::new(),
             highlighted: Highlighted::default(),
             current_index: 0,
+            ..Default::default()
         }
     }
 
@@ -45,18 +231,380 @@ impl RichText {
         Self {
             text: text.to_string(),
             highlighted,
-            current_index: 0,
+            current_index: text.len(),
+            ..Default::default()
         }
     }
 
+    /// Adds a new text string using the widget style.
+    pub fn with_text(mut self, text: &str) -> Self {
+        self.text.push_str(text);
+        self.current_index += text.len();
+        self
+    }
+
+    /// Adds a new text string with specific styles.
+    pub fn with_styled_text(mut self, text: &str, style: TextSpanStyle) -> Self {
+        self.spans.push(StyledText {
+            style,
+            range: self.current_index..self.current_index + text.len(),
+        });
+        self.with_text(text)
+    }
+
+    /// Adds a new bold text string.
+    pub fn with_bold_text(self, text: &str) -> Self {
+        self.with_styled_text(text, TextSpanStyle::BOLD)
+    }
+
+    /// Adds a new italic text string.
+    pub fn with_italic_text(self, text: &str) -> Self {
+        self.with_styled_text(text, TextSpanStyle::ITALIC)
+    }
+
+    /// Styles a range of the text.
+    pub fn with_style(mut self, range: Range<usize>, style: TextSpanStyle) -> Self {
+        self.spans.push(StyledText { style, range });
+        self
+    }
+
     /// Adds a new text string with a specific color
     pub fn with_color_text(mut self, text: &str, color: bevy::prelude::Color) -> Self {
         self.text = format!("{}{}", self.text, text);
         self.highlighted.color_text.push(ColorText {
             color,
-            range: self.current_index..text.len(),
+            range: self.current_index..self.current_index + text.len(),
         });
         self.current_index += text.len();
         self
     }
+
+    /// Adds a new text string with a specific color and decoration
+    pub fn with_decorated_text(
+        mut self,
+        text: &str,
+        color: bevy::prelude::Color,
+        decoration: TextDecoration,
+    ) -> Self {
+        self.decorations.push(DecoratedText {
+            decoration,
+            range: self.current_index..self.current_index + text.len(),
+        });
+        self.with_color_text(text, color)
+    }
+
+    /// Decorates a range of the text.
+    pub fn with_decoration(mut self, range: Range<usize>, decoration: TextDecoration) -> Self {
+        self.decorations.push(DecoratedText { decoration, range });
+        self
+    }
+
+    /// Adds a new text string which is a link with the given payload.
+    pub fn with_link_text(mut self, text: &str, payload: impl Into<String>) -> Self {
+        self.links.push(TextLink {
+            payload: payload.into(),
+            range: self.current_index..self.current_index + text.len(),
+        });
+        self.with_text(text)
+    }
+
+    /// Turns a range of the text into a link with the given payload.
+    pub fn with_link(mut self, range: Range<usize>, payload: impl Into<String>) -> Self {
+        self.links.push(TextLink {
+            payload: payload.into(),
+            range,
+        });
+        self
+    }
+
+    /// Places inline content of the given size in pixels after the current text.
+    pub fn with_inline(mut self, content: InlineContent, size: Vec2) -> Self {
+        self.inline.push(InlineItem {
+            content,
+            index: self.current_index,
+            size,
+        });
+        self
+    }
+
+    /// Places an inline image of the given size in pixels after the current text.
+    pub fn with_inline_image(self, handle: Handle<Image>, size: Vec2) -> Self {
+        self.with_inline(InlineContent::Image(handle), size)
+    }
+
+    /// Places an inline svg of the given size in pixels after the current text.
+    pub fn with_inline_svg(
+        self,
+        handle: Handle<SvgAsset>,
+        color: Option<Color>,
+        size: Vec2,
+    ) -> Self {
+        self.with_inline(InlineContent::Svg { handle, color }, size)
+    }
+
+    /// Places a widget of the given size in pixels after the current text.
+    pub fn with_inline_widget(self, widget: Entity, size: Vec2) -> Self {
+        self.with_inline(InlineContent::Widget(widget), size)
+    }
+
+    /// Moves and fades the glyphs of a range of the text, later effects replace earlier ones.
+    pub fn with_glyph_effect(mut self, range: Range<usize>, offset: Vec2, alpha: f32) -> Self {
+        self.glyph_effects.push(GlyphEffect {
+            offset,
+            alpha,
+            range,
+        });
+        self
+    }
+
+    /// The text without any styling.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The links of the text.
+    pub fn links(&self) -> &[TextLink] {
+        &self.links
+    }
+
+    /// Returns the glyph effect applied to the character at a byte index.
+    pub(crate) fn glyph_effect(&self, index: usize) -> Option<&GlyphEffect> {
+        glyph_effect_at(&self.glyph_effects, index)
+    }
+}
+
+/// Returns the last effect covering a byte index.
+pub(crate) fn glyph_effect_at(effects: &[GlyphEffect], index: usize) -> Option<&GlyphEffect> {
+    effects
+        .iter()
+        .rev()
+        .find(|effect| effect.range.contains(&index))
+}
+
+/// Triggered on a rich text widget when a pointer starts hovering one of its links.
+///
+/// The event bubbles up to the ancestors of the widget.
+#[derive(Event, Debug, Clone)]
+#[event(traversal = &'static ChildOf, auto_propagate)]
+pub struct TextLinkOver {
+    /// The hovered link.
+    pub link: TextLink,
+}
+
+/// Triggered on a rich text widget when a pointer stops hovering one of its links.
+///
+/// The event bubbles up to the ancestors of the widget.
+#[derive(Event, Debug, Clone)]
+#[event(traversal = &'static ChildOf, auto_propagate)]
+pub struct TextLinkOut {
+    /// The link which was hovered.
+    pub link: TextLink,
+}
+
+/// Triggered on a rich text widget when one of its links is clicked.
+///
+/// The event bubbles up to the ancestors of the widget.
+#[derive(Event, Debug, Clone)]
+#[event(traversal = &'static ChildOf, auto_propagate)]
+pub struct TextLinkClick {
+    /// The clicked link.
+    pub link: TextLink,
+    /// The button that clicked the link.
+    pub button: PointerButton,
+}
+
+/// The link of a rich text widget which is currently hovered.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub(crate) struct HoveredTextLink(usize);
+
+/// Maps pointer positions to the links of rich text widgets.
+#[derive(SystemParam)]
+pub(crate) struct TextLinkHitTest<'w, 's> {
+    query: Query<
+        'w,
+        's,
+        (
+            &'static WidgetRender,
+            &'static WidgetLayout,
+            &'static WoodpeckerStyle,
+            Option<&'static ChildOf>,
+            Option<&'static HoveredTextLink>,
+        ),
+    >,
+    layout_query: Query<'w, 's, &'static WidgetLayout>,
+    default_font: Res<'w, DefaultFont>,
+    font_manager: ResMut<'w, FontManager>,
+}
+
+impl TextLinkHitTest<'_, '_> {
+    /// Returns the index of the link under the position in UI space and the hovered link.
+    fn link_at(
+        &mut self,
+        entity: Entity,
+        position: Option<Vec3>,
+    ) -> Option<(Option<usize>, Option<usize>, &RichText)> {
+        let (WidgetRender::RichText { content }, layout, style, parent, hovered) =
+            self.query.get(entity).ok()?
+        else {
+            return None;
+        };
+        let hovered = hovered.map(|hovered| hovered.0);
+        if content.links.is_empty() {
+            return Some((None, hovered, content));
+        }
+        let Some(position) = position else {
+            return Some((None, hovered, content));
+        };
+        let parent_layout = parent
+            .and_then(|parent| self.layout_query.get(parent.parent()).ok())
+            .copied()
+            .unwrap_or(*layout);
+
+        let text_layout = rich_text_layout(
+            content,
+            style,
+            &self.default_font,
+            &mut self.font_manager,
+            1.0,
+            parent_layout.size.x,
+        );
+        let local = position.truncate() - layout.location;
+        let link = content
+            .links
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, link)| {
+                let mut hit = false;
+                span_geometry(&text_layout, link.range.clone(), |x0, x1, line| {
+                    let metrics = line.metrics();
+                    hit |= (x0..x1).contains(&local.x)
+                        && (metrics.min_coord..metrics.max_coord).contains(&local.y);
+                });
+                hit
+            })
+            .map(|(index, _)| index);
+        Some((link, hovered, content))
+    }
+}
+
+pub(crate) fn link_pointer_move(
+    trigger: Trigger<Pointer<Move>>,
+    mut commands: Commands,
+    mut hit_test: TextLinkHitTest,
+) {
+    let entity = trigger.target();
+    let Some((link, hovered, content)) = hit_test.link_at(entity, trigger.hit.position) else {
+        return;
+    };
+    if link == hovered {
+        return;
+    }
+    if let Some(link) = hovered.and_then(|index| content.links.get(index)) {
+        commands.trigger_targets(TextLinkOut { link: link.clone() }, entity);
+    }
+    if let Some(index) = link {
+        commands.trigger_targets(
+            TextLinkOver {
+                link: content.links[index].clone(),
+            },
+            entity,
+        );
+        commands.entity(entity).insert(HoveredTextLink(index));
+    } else {
+        commands.entity(entity).remove::<HoveredTextLink>();
+    }
+}
+
+pub(crate) fn link_pointer_out(
+    trigger: Trigger<Pointer<Out>>,
+    mut commands: Commands,
+    mut hit_test: TextLinkHitTest,
+) {
+    let entity = trigger.target();
+    let Some((_, Some(hovered), content)) = hit_test.link_at(entity, None) else {
+        return;
+    };
+    if let Some(link) = content.links.get(hovered) {
+        commands.trigger_targets(TextLinkOut { link: link.clone() }, entity);
+    }
+    commands.entity(entity).remove::<HoveredTextLink>();
+}
+
+pub(crate) fn link_pointer_click(
+    trigger: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut hit_test: TextLinkHitTest,
+) {
+    let entity = trigger.target();
+    let Some((Some(index), _, content)) = hit_test.link_at(entity, trigger.hit.position) else {
+        return;
+    };
+    commands.trigger_targets(
+        TextLinkClick {
+            link: content.links[index].clone(),
+            button: trigger.button,
+        },
+        entity,
+    );
+}
+
+/// Moves inline widgets of rich text onto their inline boxes.
+pub(crate) fn position_inline_widgets(
+    rich_texts: Query<
+        (Entity, &WidgetRender, &WoodpeckerStyle, Option<&ChildOf>),
+        With<WidgetLayout>,
+    >,
+    mut layouts: Query<&mut WidgetLayout>,
+    children: Query<&Children>,
+    default_font: Res<DefaultFont>,
+    mut font_manager: ResMut<FontManager>,
+) {
+    for (entity, widget_render, style, parent) in rich_texts.iter() {
+        let WidgetRender::RichText { content } = widget_render else {
+            continue;
+        };
+        if !content
+            .inline
+            .iter()
+            .any(|item| matches!(item.content, InlineContent::Widget(_)))
+        {
+            continue;
+        }
+        let Ok(location) = layouts.get(entity).map(|layout| layout.location) else {
+            continue;
+        };
+        let Some(width) = parent
+            .and_then(|parent| layouts.get(parent.parent()).ok())
+            .map(|layout| layout.size.x)
+        else {
+            continue;
+        };
+
+        let text_layout =
+            rich_text_layout(content, style, &default_font, &mut font_manager, 1.0, width);
+        for inline_box in inline_boxes(&text_layout) {
+            let Some(InlineItem {
+                content: InlineContent::Widget(widget),
+                ..
+            }) = content.inline.get(inline_box.id as usize)
+            else {
+                continue;
+            };
+            let Ok(mut layout) = layouts.get_mut(*widget) else {
+                continue;
+            };
+            let offset = location + Vec2::new(inline_box.x, inline_box.y) - layout.location;
+            if offset == Vec2::ZERO {
+                continue;
+            }
+            // Children were laid out relative to the widget so they move along with it.
+            layout.location += offset;
+            for descendant in children.iter_descendants(*widget) {
+                if let Ok(mut layout) = layouts.get_mut(descendant) {
+                    layout.location += offset;
+                }
+            }
+        }
+    }
 }
@@ -19,6 +19,7 @@ use crate::{
     hook_helper::StateMarker,
     metrics::WidgetMetrics,
     prelude::{PreviousWidget, WidgetMapper},
+    render_to_texture::WoodpeckerTextureTarget,
     CurrentWidget, ObserverCache, WoodpeckerContext,
 };
 
@@ -37,11 +38,23 @@ pub(crate) fn system(world: &mut World) {
         widget_mapper.clear_added_this_frame();
     });
 
+    // Texture target roots are updated along side the main root.
+    let texture_roots = world
+        .query_filtered::<Entity, With<WoodpeckerTextureTarget>>()
+        .iter(world)
+        .filter(|e| *e != root_widget)
+        .collect::<Vec<_>>();
+
     let widgets_list = {
         let _ = info_span!("Query Widget Entities", name = "Query Widget Entities").entered();
-        vec![root_widget]
+        let mut widgets_list = vec![root_widget];
+        widgets_list.extend(get_all_children(world, root_widget));
+        for texture_root in texture_roots {
+            widgets_list.push(texture_root);
+            widgets_list.extend(get_all_children(world, texture_root));
+        }
+        widgets_list
             .into_iter()
-            .chain(get_all_children(world, root_widget))
             .filter(|e| {
                 if world.get_entity(*e).is_err() {
                     return false;
@@ -228,7 +241,11 @@ fn run_render_system(
     }
 
     // Root observers never can be re-created so we don't want to despawn them.
-    if widget_entity != root_widget {
+    if widget_entity != root_widget
+        && !world
+            .entity(widget_entity)
+            .contains::<WoodpeckerTextureTarget>()
+    {
         // Clear out observer entities on re-render
         world.resource_scope(
             |world: &mut World, mut observer_cache: Mut<ObserverCache>| {
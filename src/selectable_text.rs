@@ -0,0 +1,180 @@
+use std::ops::Range;
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::{
+    font::FontManager, keyboard_input::copy_to_clipboard, picking_backend::PointerUiPositions,
+    prelude::WidgetLayout, render::WidgetRender, styles::WoodpeckerStyle, widgets::colors,
+    DefaultFont,
+};
+
+/// Lets users drag select the text of a [`WidgetRender::Text`] or [`WidgetRender::RichText`]
+/// widget and copy it with Ctrl+C.
+///
+/// The current selection is stored in the [`TextSelection`] of the widget. Only one widget has a
+/// selection at a time, pressing anywhere else clears it.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq)]
+#[require(TextSelection, Pickable)]
+pub struct SelectableText {
+    /// The color drawn behind selected text.
+    pub selection_color: Color,
+}
+
+impl Default for SelectableText {
+    fn default() -> Self {
+        Self {
+            selection_color: colors::PRIMARY.with_alpha(0.5),
+        }
+    }
+}
+
+/// The selected byte range of a [`SelectableText`] widget.
+#[derive(Component, Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TextSelection {
+    /// The byte index where the selection started.
+    pub anchor: usize,
+    /// The byte index where the selection ends, this moves while dragging.
+    pub focus: usize,
+}
+
+impl TextSelection {
+    /// The selected byte range in order.
+    pub fn range(&self) -> Range<usize> {
+        self.anchor.min(self.focus)..self.anchor.max(self.focus)
+    }
+
+    /// Returns true if no text is selected.
+    pub fn is_empty(&self) -> bool {
+        self.anchor == self.focus
+    }
+
+    /// Returns the selected text of a text widget.
+    pub fn selected_text<'a>(&self, widget_render: &'a WidgetRender) -> Option<&'a str> {
+        let text = match widget_render {
+            WidgetRender::Text { content } => content.as_str(),
+            WidgetRender::RichText { content } => content.text.as_str(),
+            _ => return None,
+        };
+        text.get(self.range()).filter(|text| !text.is_empty())
+    }
+}
+
+/// Maps pointer positions to byte indices of selectable text.
+#[derive(SystemParam)]
+pub(crate) struct TextSelectionHitTest<'w, 's> {
+    query: Query<
+        'w,
+        's,
+        (
+            &'static WidgetRender,
+            &'static WidgetLayout,
+            &'static WoodpeckerStyle,
+            Option<&'static ChildOf>,
+        ),
+        With<SelectableText>,
+    >,
+    layout_query: Query<'w, 's, &'static WidgetLayout>,
+    default_font: Res<'w, DefaultFont>,
+    font_manager: ResMut<'w, FontManager>,
+}
+
+impl TextSelectionHitTest<'_, '_> {
+    /// Returns the byte index closest to the position in UI space.
+    fn index_at(&mut self, entity: Entity, position: Vec2) -> Option<usize> {
+        let (widget_render, layout, style, parent) = self.query.get(entity).ok()?;
+        let parent_layout = parent
+            .and_then(|parent| self.layout_query.get(parent.parent()).ok())
+            .copied()
+            .unwrap_or(*layout);
+        let text_layout = widget_render.text_layout(
+            &parent_layout,
+            &self.default_font,
+            &mut self.font_manager,
+            style,
+            Vec2::ONE,
+        )?;
+        let local = position - layout.location;
+        Some(parley::Cursor::from_point(&text_layout, local.x, local.y).index())
+    }
+}
+
+pub(crate) fn selection_pointer_pressed(
+    trigger: Trigger<Pointer<Pressed>>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut hit_test: TextSelectionHitTest,
+    mut selections: Query<(Entity, &mut TextSelection)>,
+) {
+    // Only handle the press once instead of for every entity it bubbles through.
+    if trigger.target() != trigger.target || trigger.button != PointerButton::Primary {
+        return;
+    }
+    let entity = trigger.target();
+    for (selection_entity, mut selection) in selections.iter_mut() {
+        if selection_entity != entity && !selection.is_empty() {
+            *selection = TextSelection::default();
+        }
+    }
+    let Some(position) = trigger.hit.position else {
+        return;
+    };
+    let Some(index) = hit_test.index_at(entity, position.truncate()) else {
+        return;
+    };
+    let Ok((_, mut selection)) = selections.get_mut(entity) else {
+        return;
+    };
+    if keyboard_input.pressed(KeyCode::ShiftLeft) || keyboard_input.pressed(KeyCode::ShiftRight) {
+        selection.focus = index;
+    } else {
+        *selection = TextSelection {
+            anchor: index,
+            focus: index,
+        };
+    }
+}
+
+pub(crate) fn selection_pointer_drag(
+    trigger: Trigger<Pointer<Drag>>,
+    positions: Res<PointerUiPositions>,
+    parents: Query<&ChildOf>,
+    mut hit_test: TextSelectionHitTest,
+    mut selections: Query<&mut TextSelection>,
+) {
+    if trigger.target() != trigger.target || trigger.button != PointerButton::Primary {
+        return;
+    }
+    let entity = trigger.target();
+    // The pointer is mapped into the root of the text the same way as the press was.
+    let Some(position) = positions.get(trigger.pointer_id, entity, &parents) else {
+        return;
+    };
+    let Some(index) = hit_test.index_at(entity, position) else {
+        return;
+    };
+    let Ok(mut selection) = selections.get_mut(entity) else {
+        return;
+    };
+    if selection.focus != index {
+        selection.focus = index;
+    }
+}
+
+/// Copies the selected text when Ctrl+C is pressed.
+pub(crate) fn copy_selected_text(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    selections: Query<(&TextSelection, &WidgetRender)>,
+) {
+    if !(keyboard_input.pressed(KeyCode::SuperLeft)
+        || keyboard_input.pressed(KeyCode::ControlLeft)
+        || keyboard_input.pressed(KeyCode::ControlRight))
+        || !keyboard_input.just_pressed(KeyCode::KeyC)
+    {
+        return;
+    }
+    if let Some(text) = selections
+        .iter()
+        .find_map(|(selection, widget_render)| selection.selected_text(widget_render))
+    {
+        copy_to_clipboard(text.to_string());
+    }
+}
@@ -0,0 +1,272 @@
+use bevy::prelude::*;
+use bevy_vello::vello::{
+    kurbo::{self, BezPath, Shape as _},
+    peniko,
+};
+
+use crate::display_list::to_vello_color;
+
+/// The geometry of a [`Shape`], in the local coordinates of the widget.
+///
+/// (0, 0) is the top left corner of the widget and y points down.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShapePrimitive {
+    /// Any kurbo path.
+    Path(BezPath),
+    /// A circle.
+    Circle(kurbo::Circle),
+    /// A line between two points.
+    Line(kurbo::Line),
+    /// An arc of an ellipse. It's open, a fill closes it with a straight line.
+    Arc(kurbo::Arc),
+    /// A closed polygon through the points.
+    Polygon(Vec<kurbo::Point>),
+    /// An open line through the points, for example a graph line.
+    Polyline(Vec<kurbo::Point>),
+    /// A cubic bezier curve.
+    Bezier(kurbo::CubicBez),
+}
+
+impl ShapePrimitive {
+    /// Converts the primitive into a path.
+    pub fn to_path(&self) -> BezPath {
+        // Curves are kept as curves, the tolerance is only used to split up arcs.
+        const TOLERANCE: f64 = 0.1;
+        match self {
+            ShapePrimitive::Path(path) => path.clone(),
+            ShapePrimitive::Circle(circle) => circle.to_path(TOLERANCE),
+            ShapePrimitive::Line(line) => line.to_path(TOLERANCE),
+            ShapePrimitive::Arc(arc) => arc.to_path(TOLERANCE),
+            ShapePrimitive::Polygon(points) | ShapePrimitive::Polyline(points) => {
+                let mut path = BezPath::new();
+                let mut points = points.iter();
+                if let Some(first) = points.next() {
+                    path.move_to(*first);
+                    points.for_each(|point| path.line_to(*point));
+                    if matches!(self, ShapePrimitive::Polygon(_)) {
+                        path.close_path();
+                    }
+                }
+                path
+            }
+            ShapePrimitive::Bezier(bezier) => bezier.to_path(TOLERANCE),
+        }
+    }
+}
+
+/// A gradient color stop, the offset goes from 0.0 to 1.0.
+pub type ShapeColorStop = (f32, Color);
+
+/// How a [`Shape`] is filled or stroked.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShapeBrush {
+    /// A single color.
+    Solid(Color),
+    /// A linear gradient between two points in local coordinates.
+    LinearGradient {
+        /// Where the gradient starts.
+        start: Vec2,
+        /// Where the gradient ends.
+        end: Vec2,
+        /// The colors of the gradient.
+        stops: Vec<ShapeColorStop>,
+    },
+    /// A radial gradient around a point in local coordinates.
+    RadialGradient {
+        /// The center of the gradient.
+        center: Vec2,
+        /// The radius of the gradient.
+        radius: f32,
+        /// The colors of the gradient.
+        stops: Vec<ShapeColorStop>,
+    },
+}
+
+impl From<Color> for ShapeBrush {
+    fn from(color: Color) -> Self {
+        ShapeBrush::Solid(color)
+    }
+}
+
+impl From<Srgba> for ShapeBrush {
+    fn from(color: Srgba) -> Self {
+        ShapeBrush::Solid(color.into())
+    }
+}
+
+impl ShapeBrush {
+    /// Converts the brush into a vello brush.
+    pub(crate) fn to_vello_brush(&self) -> peniko::Brush {
+        let stops = |stops: &[ShapeColorStop]| {
+            stops
+                .iter()
+                .map(|(offset, color)| peniko::ColorStop::from((*offset, to_vello_color(*color))))
+                .collect::<Vec<_>>()
+        };
+        match self {
+            ShapeBrush::Solid(color) => peniko::Brush::Solid(to_vello_color(*color)),
+            ShapeBrush::LinearGradient {
+                start,
+                end,
+                stops: color_stops,
+            } => peniko::Gradient::new_linear(
+                (start.x as f64, start.y as f64),
+                (end.x as f64, end.y as f64),
+            )
+            .with_stops(stops(color_stops).as_slice())
+            .into(),
+            ShapeBrush::RadialGradient {
+                center,
+                radius,
+                stops: color_stops,
+            } => peniko::Gradient::new_radial((center.x as f64, center.y as f64), *radius)
+                .with_stops(stops(color_stops).as_slice())
+                .into(),
+        }
+    }
+}
+
+/// The outline of a [`Shape`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeStroke {
+    /// The brush the outline is drawn with.
+    pub brush: ShapeBrush,
+    /// The width of the outline in pixels.
+    pub width: f32,
+    /// How the ends of open shapes look.
+    pub cap: kurbo::Cap,
+    /// How corners look.
+    pub join: kurbo::Join,
+}
+
+impl ShapeStroke {
+    /// Creates a new stroke with butt caps and miter joins.
+    pub fn new(width: f32, brush: impl Into<ShapeBrush>) -> Self {
+        Self {
+            brush: brush.into(),
+            width,
+            cap: kurbo::Cap::Butt,
+            join: kurbo::Join::Miter,
+        }
+    }
+
+    /// Sets how the ends of open shapes look.
+    pub fn with_cap(mut self, cap: kurbo::Cap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Sets how corners look.
+    pub fn with_join(mut self, join: kurbo::Join) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub(crate) fn to_kurbo_stroke(&self) -> kurbo::Stroke {
+        kurbo::Stroke::new(self.width as f64)
+            .with_caps(self.cap)
+            .with_join(self.join)
+    }
+}
+
+/// A vector shape drawn by a [`crate::prelude::Canvas`].
+///
+/// ```ignore
+/// // A cooldown indicator which is 25% done.
+/// Shape::arc(Vec2::splat(32.0), 28.0, -PI / 2.0, PI / 2.0)
+///     .with_stroke(ShapeStroke::new(6.0, Color::WHITE).with_cap(kurbo::Cap::Round));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Shape {
+    /// The geometry of the shape.
+    pub primitive: ShapePrimitive,
+    /// The brush the inside of the shape is filled with.
+    pub fill: Option<ShapeBrush>,
+    /// The outline of the shape, drawn on top of the fill.
+    pub stroke: Option<ShapeStroke>,
+}
+
+impl Shape {
+    /// Creates a shape which isn't filled or stroked yet.
+    pub fn new(primitive: ShapePrimitive) -> Self {
+        Self {
+            primitive,
+            fill: None,
+            stroke: None,
+        }
+    }
+
+    /// Creates a path shape.
+    pub fn path(path: BezPath) -> Self {
+        Self::new(ShapePrimitive::Path(path))
+    }
+
+    /// Creates a circle.
+    pub fn circle(center: Vec2, radius: f32) -> Self {
+        Self::new(ShapePrimitive::Circle(kurbo::Circle::new(
+            to_point(center),
+            radius as f64,
+        )))
+    }
+
+    /// Creates a line between two points.
+    pub fn line(start: Vec2, end: Vec2) -> Self {
+        Self::new(ShapePrimitive::Line(kurbo::Line::new(
+            to_point(start),
+            to_point(end),
+        )))
+    }
+
+    /// Creates a circular arc.
+    ///
+    /// Angles are in radians, 0.0 points right and positive angles turn clockwise.
+    pub fn arc(center: Vec2, radius: f32, start_angle: f32, sweep_angle: f32) -> Self {
+        Self::new(ShapePrimitive::Arc(kurbo::Arc::new(
+            to_point(center),
+            (radius as f64, radius as f64),
+            start_angle as f64,
+            sweep_angle as f64,
+            0.0,
+        )))
+    }
+
+    /// Creates a closed polygon.
+    pub fn polygon(points: impl IntoIterator<Item = Vec2>) -> Self {
+        Self::new(ShapePrimitive::Polygon(
+            points.into_iter().map(to_point).collect(),
+        ))
+    }
+
+    /// Creates an open line through the points.
+    pub fn polyline(points: impl IntoIterator<Item = Vec2>) -> Self {
+        Self::new(ShapePrimitive::Polyline(
+            points.into_iter().map(to_point).collect(),
+        ))
+    }
+
+    /// Creates a cubic bezier curve.
+    pub fn bezier(start: Vec2, control_a: Vec2, control_b: Vec2, end: Vec2) -> Self {
+        Self::new(ShapePrimitive::Bezier(kurbo::CubicBez::new(
+            to_point(start),
+            to_point(control_a),
+            to_point(control_b),
+            to_point(end),
+        )))
+    }
+
+    /// Fills the inside of the shape.
+    pub fn with_fill(mut self, brush: impl Into<ShapeBrush>) -> Self {
+        self.fill = Some(brush.into());
+        self
+    }
+
+    /// Strokes the outline of the shape.
+    pub fn with_stroke(mut self, stroke: ShapeStroke) -> Self {
+        self.stroke = Some(stroke);
+        self
+    }
+}
+
+fn to_point(point: Vec2) -> kurbo::Point {
+    kurbo::Point::new(point.x as f64, point.y as f64)
+}
@@ -0,0 +1,342 @@
+//! Golden image snapshot testing of UI renders.
+//!
+//! Snapshots are rendered with vello's CPU path (the same one used by
+//! [`crate::RenderSettings::use_cpu`]) on wgpu's fallback adapter, so they render the same on every
+//! machine and can run in CI without a GPU. A software adapter (lavapipe, llvmpipe, WARP) must be
+//! installed, otherwise [`SnapshotRenderer::new`] returns [`SnapshotError::NoAdapter`].
+//!
+//! A typical test mounts a UI root with a [`WoodpeckerTextureTarget`] into a headless bevy app,
+//! runs a frame and compares the result against a golden file:
+//!
+//! ```ignore
+//! app.update();
+//! let mut renderer = SnapshotRenderer::new().unwrap();
+//! let image = renderer.render_root(app.world(), root).unwrap();
+//! assert_snapshot(&image, "tests/snapshots/checkbox.png", SnapshotTolerance::default()).unwrap();
+//! ```
+//!
+//! Golden files are only written when `WOODPECKER_UPDATE_SNAPSHOTS=1` is set, which also overwrites
+//! existing ones. Without it a missing golden file is an error, so CI can't silently accept a new
+//! render.
+
+use std::path::{Path, PathBuf};
+
+use bevy::prelude::*;
+use bevy_vello::{
+    vello::{self, peniko, wgpu, AaConfig, AaSupport, RenderParams, RendererOptions},
+    VelloScene,
+};
+use image::{Rgba, RgbaImage};
+use thiserror::Error;
+
+use crate::render_to_texture::{TextureTargetScene, WoodpeckerTextureTarget};
+
+/// The environment variable which makes [`assert_snapshot`] write golden files.
+pub const UPDATE_SNAPSHOTS_ENV: &str = "WOODPECKER_UPDATE_SNAPSHOTS";
+
+/// Errors produced while rendering or comparing snapshots.
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum SnapshotError {
+    /// No software wgpu adapter could be found.
+    #[error(
+        "No software wgpu adapter available for snapshot rendering, install lavapipe, llvmpipe or WARP"
+    )]
+    NoAdapter,
+    /// The wgpu device could not be created.
+    #[error("Failed to create wgpu device: {0}")]
+    Device(#[from] wgpu::RequestDeviceError),
+    /// Vello failed to create the renderer or render the scene.
+    #[error("Vello failed: {0}")]
+    Vello(#[from] vello::Error),
+    /// The rendered texture could not be read back.
+    #[error("Failed to read back the rendered texture: {0}")]
+    Readback(#[from] wgpu::BufferAsyncError),
+    /// The entity has no [`WoodpeckerTextureTarget`] or its image doesn't exist.
+    #[error("Entity {0} is not a UI root with a texture target")]
+    NotATextureRoot(Entity),
+    /// Reading or writing a PNG file failed.
+    #[error("Failed to read or write image: {0}")]
+    Image(#[from] image::ImageError),
+    /// The golden file doesn't exist and [`UPDATE_SNAPSHOTS_ENV`] isn't set.
+    #[error(
+        "Golden file {} is missing, set {UPDATE_SNAPSHOTS_ENV}=1 to write it",
+        .0.display()
+    )]
+    MissingGolden(PathBuf),
+    /// The rendered image doesn't match the golden file.
+    #[error(
+        "Snapshot {} does not match: {mismatched} pixels differ, see {}",
+        golden.display(),
+        diff.display()
+    )]
+    Mismatch {
+        /// The golden file.
+        golden: PathBuf,
+        /// The diff image written next to the golden file.
+        diff: PathBuf,
+        /// The number of pixels outside of the tolerance.
+        mismatched: usize,
+    },
+}
+
+/// How much a snapshot is allowed to differ from its golden file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotTolerance {
+    /// The largest per channel difference (0-255) that still counts as equal.
+    pub channel: u8,
+    /// The amount of pixels allowed to differ, as a fraction of all pixels.
+    pub pixels: f32,
+}
+
+impl Default for SnapshotTolerance {
+    fn default() -> Self {
+        Self {
+            channel: 2,
+            pixels: 0.0,
+        }
+    }
+}
+
+/// Renders vello scenes into RGBA buffers using the CPU path.
+pub struct SnapshotRenderer {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    renderer: vello::Renderer,
+}
+
+impl SnapshotRenderer {
+    /// Creates a renderer on wgpu's headless fallback (software) adapter.
+    pub fn new() -> Result<Self, SnapshotError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let adapter =
+            bevy::tasks::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::LowPower,
+                force_fallback_adapter: true,
+                compatible_surface: None,
+            }))
+            .ok_or(SnapshotError::NoAdapter)?;
+        let (device, queue) = bevy::tasks::block_on(adapter.request_device(
+            &wgpu::DeviceDescriptor {
+                label: Some("woodpecker_ui_snapshot"),
+                required_features: wgpu::Features::empty(),
+                required_limits: adapter.limits(),
+                memory_hints: wgpu::MemoryHints::default(),
+            },
+            None,
+        ))?;
+        let renderer = vello::Renderer::new(
+            &device,
+            RendererOptions {
+                use_cpu: true,
+                antialiasing_support: AaSupport::area_only(),
+                num_init_threads: None,
+                pipeline_cache: None,
+            },
+        )?;
+
+        Ok(Self {
+            device,
+            queue,
+            renderer,
+        })
+    }
+
+    /// Renders the scene last encoded for a UI root with a [`WoodpeckerTextureTarget`].
+    pub fn render_root(&mut self, world: &World, root: Entity) -> Result<RgbaImage, SnapshotError> {
+        let entity = world
+            .get_entity(root)
+            .map_err(|_| SnapshotError::NotATextureRoot(root))?;
+        let (Some(target), Some(scene)) = (
+            entity.get::<WoodpeckerTextureTarget>(),
+            entity.get::<TextureTargetScene>(),
+        ) else {
+            return Err(SnapshotError::NotATextureRoot(root));
+        };
+        let size = world
+            .resource::<Assets<Image>>()
+            .get(&target.image)
+            .ok_or(SnapshotError::NotATextureRoot(root))?
+            .size();
+        self.render_scene(scene, size)
+    }
+
+    /// Renders a vello scene into an RGBA buffer of the given size.
+    pub fn render_scene(
+        &mut self,
+        scene: &VelloScene,
+        size: UVec2,
+    ) -> Result<RgbaImage, SnapshotError> {
+        let (width, height) = (size.x.max(1), size.y.max(1));
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("woodpecker_ui_snapshot_target"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::STORAGE_BINDING | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        self.renderer.render_to_texture(
+            &self.device,
+            &self.queue,
+            scene,
+            &view,
+            &RenderParams {
+                base_color: peniko::Color::TRANSPARENT,
+                width,
+                height,
+                antialiasing_method: AaConfig::Area,
+            },
+        )?;
+
+        // Rows of a buffer copy have to be aligned.
+        let unpadded_row = width * 4;
+        let padded_row = unpadded_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("woodpecker_ui_snapshot_readback"),
+            size: (padded_row * height) as u64,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: None,
+                },
+            },
+            extent,
+        );
+        self.queue.submit([encoder.finish()]);
+
+        let slice = buffer.slice(..);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver.recv().unwrap_or(Err(wgpu::BufferAsyncError))?;
+
+        let data = slice.get_mapped_range();
+        let mut pixels = Vec::with_capacity((unpadded_row * height) as usize);
+        for row in data.chunks(padded_row as usize) {
+            pixels.extend_from_slice(&row[..unpadded_row as usize]);
+        }
+        drop(data);
+        buffer.unmap();
+
+        Ok(RgbaImage::from_raw(width, height, pixels).expect("Buffer size matches the image"))
+    }
+}
+
+/// Compares an image against a golden PNG file.
+///
+/// If [`UPDATE_SNAPSHOTS_ENV`] is set the image is written as the new golden file instead,
+/// otherwise a missing golden file is a [`SnapshotError::MissingGolden`] error. On mismatch a
+/// `.diff.png` highlighting differing pixels in red and an `.actual.png` with the rendered image
+/// are written next to the golden file.
+pub fn assert_snapshot(
+    image: &RgbaImage,
+    golden: impl AsRef<Path>,
+    tolerance: SnapshotTolerance,
+) -> Result<(), SnapshotError> {
+    let golden = golden.as_ref();
+    let update = std::env::var_os(UPDATE_SNAPSHOTS_ENV).is_some_and(|value| value != "0");
+    if update {
+        if let Some(parent) = golden.parent() {
+            std::fs::create_dir_all(parent).map_err(image::ImageError::IoError)?;
+        }
+        image.save(golden)?;
+        return Ok(());
+    }
+    if !golden.exists() {
+        return Err(SnapshotError::MissingGolden(golden.to_path_buf()));
+    }
+
+    let expected = image::open(golden)?.to_rgba8();
+    let (diff, mismatched) = diff_images(&expected, image, tolerance.channel);
+    let total = (image.width() * image.height()).max(1) as f32;
+    if mismatched as f32 / total <= tolerance.pixels {
+        return Ok(());
+    }
+
+    let diff_path = golden.with_extension("diff.png");
+    diff.save(&diff_path)?;
+    image.save(golden.with_extension("actual.png"))?;
+    Err(SnapshotError::Mismatch {
+        golden: golden.to_path_buf(),
+        diff: diff_path,
+        mismatched,
+    })
+}
+
+/// Builds a diff image and counts the pixels that differ by more than `channel_tolerance`.
+///
+/// Matching pixels are drawn faded, differing ones in red. If the sizes differ every pixel
+/// outside of the overlapping area counts as a mismatch.
+pub fn diff_images(
+    expected: &RgbaImage,
+    actual: &RgbaImage,
+    channel_tolerance: u8,
+) -> (RgbaImage, usize) {
+    let width = expected.width().max(actual.width());
+    let height = expected.height().max(actual.height());
+    let mut mismatched = 0;
+    let diff = RgbaImage::from_fn(width, height, |x, y| {
+        let in_expected = x < expected.width() && y < expected.height();
+        let in_actual = x < actual.width() && y < actual.height();
+        if !in_expected || !in_actual {
+            mismatched += 1;
+            return Rgba([255, 0, 0, 255]);
+        }
+
+        let a = expected.get_pixel(x, y);
+        let b = actual.get_pixel(x, y);
+        let differs =
+            a.0.iter()
+                .zip(b.0.iter())
+                .any(|(a, b)| a.abs_diff(*b) > channel_tolerance);
+        if differs {
+            mismatched += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            Rgba([b[0], b[1], b[2], b[3] / 4])
+        }
+    });
+    (diff, mismatched)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_respects_channel_tolerance() {
+        let expected = RgbaImage::from_pixel(4, 4, Rgba([100, 100, 100, 255]));
+        let mut actual = expected.clone();
+        actual.put_pixel(0, 0, Rgba([102, 100, 100, 255]));
+        actual.put_pixel(1, 0, Rgba([110, 100, 100, 255]));
+
+        let (_, mismatched) = diff_images(&expected, &actual, 2);
+        assert_eq!(mismatched, 1);
+
+        let (_, mismatched) = diff_images(&expected, &RgbaImage::new(2, 4), 2);
+        assert_eq!(mismatched, 16);
+    }
+}
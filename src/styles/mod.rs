@@ -3,6 +3,8 @@ use bevy_vello::prelude::VelloFont;
 pub use corner::Corner;
 pub use edge::Edge;
 pub use layout::*;
+pub use object_fit::*;
+pub use text::*;
 pub use units::Units;
 
 use crate::font::TextAlign;
@@ -10,6 +12,8 @@ use crate::font::TextAlign;
 mod corner;
 mod edge;
 mod layout;
+mod object_fit;
+mod text;
 mod units;
 
 /// A struct used to pass styles into a widget.
@@ -176,7 +180,8 @@ pub struct WoodpeckerStyle {
     ///
     /// The order is (Top, Right, Bottom, Left).
     ///
-    /// Only applies to widgets with [`crate::prelude::WidgetRender::Quad`]
+    /// Only applies to widgets with [`crate::prelude::WidgetRender::Quad`], layers and images.
+    /// Images are clipped to the rounded corners.
     pub border_radius: Corner,
     /// The widths of the borders (in pixels)
     ///
@@ -191,6 +196,18 @@ pub struct WoodpeckerStyle {
     /// Font handle if none is set the [`crate::DefaultFont`] is used.
     /// We use AssetId here because it can be copied thus it makes styles easier.
     pub font: Option<AssetId<VelloFont>>,
+    /// The font weight, matched against every registered face of the font's family.
+    ///
+    /// Variable fonts use the weight as the value of their `wght` axis.
+    pub font_weight: FontWeight,
+    /// The font style (italic or oblique), matched against every registered face of the font's family.
+    pub font_style: FontStyle,
+    /// The font width, matched against every registered face of the font's family.
+    pub font_stretch: FontStretch,
+    /// Values for the axes of a variable font.
+    pub font_variations: FontVariations,
+    /// OpenType features such as tabular numbers or ligatures.
+    pub font_features: FontFeatures,
     /// The font size for this widget, in pixels
     ///
     /// Only applies to [`crate::prelude::WidgetRender::Text`]
@@ -206,8 +223,27 @@ pub struct WoodpeckerStyle {
     /// Alignent for text rendering
     /// If none is set it uses right for RTL and left for LTR text.
     pub text_alignment: Option<TextAlign>,
+    /// Underline, strikethrough, overline and highlight of text
+    pub text_decoration: TextDecoration,
+    /// An outline drawn around text.
+    pub text_stroke: Option<TextStroke>,
+    /// A shadow drawn behind text and its outline.
+    pub text_shadow: Option<TextDropShadow>,
+    /// How text which doesn't fit the widget width or [`WoodpeckerStyle::max_lines`] is cut off.
+    pub text_overflow: TextOverflow,
+    /// The maximum number of lines of text shown, lines after it are hidden.
+    pub max_lines: Option<u32>,
     /// Image Quality
     pub image_quality: ImageQuality,
+    /// How images are resized to fit the widget.
+    ///
+    /// Only applies to [`crate::prelude::WidgetRender::Image`],
+    /// [`crate::prelude::WidgetRender::RenderTarget`] and atlas images which aren't nine patches.
+    pub object_fit: ObjectFit,
+    /// Where images are placed inside of the widget.
+    pub object_position: ObjectPosition,
+    /// A color images are multiplied with. The alpha sets how strongly the tint is applied.
+    pub image_tint: Option<Color>,
     /// Z Index
     pub z_index: Option<WidgetZ>,
 }
@@ -337,9 +373,22 @@ impl WoodpeckerStyle {
         line_height: None,
         opacity: 1.0,
         font: None,
+        font_weight: FontWeight::NORMAL,
+        font_style: FontStyle::Normal,
+        font_stretch: FontStretch::NORMAL,
+        font_variations: FontVariations::NONE,
+        font_features: FontFeatures::NONE,
         text_wrap: TextWrap::WordOrGlyph,
         text_alignment: None,
+        text_decoration: TextDecoration::NONE,
+        text_stroke: None,
+        text_shadow: None,
+        text_overflow: TextOverflow::Visible,
+        max_lines: None,
         image_quality: ImageQuality::Medium,
+        object_fit: ObjectFit::Contain,
+        object_position: ObjectPosition::TOP_LEFT,
+        image_tint: None,
         z_index: None,
     };
 
@@ -0,0 +1,85 @@
+use bevy::prelude::*;
+
+use super::Units;
+
+/// How an image is resized to fit the widget, like the CSS `object-fit` property.
+#[derive(Reflect, Debug, Default, Clone, PartialEq, Copy)]
+pub enum ObjectFit {
+    /// Stretches the image to fill the widget, ignoring its aspect ratio.
+    Fill,
+    /// Scales the image to fit inside of the widget, keeping its aspect ratio.
+    #[default]
+    Contain,
+    /// Scales the image to cover the whole widget, keeping its aspect ratio. Overflowing parts
+    /// of the image are clipped.
+    Cover,
+    /// Draws the image at its own size. Overflowing parts of the image are clipped.
+    None,
+    /// Like [`ObjectFit::Contain`] but never scales the image up.
+    ScaleDown,
+}
+
+impl ObjectFit {
+    /// Returns the size of an image drawn into a widget of the given size.
+    pub fn fit(&self, image_size: Vec2, widget_size: Vec2) -> Vec2 {
+        let scale = widget_size / image_size;
+        match self {
+            ObjectFit::Fill => widget_size,
+            ObjectFit::Contain => image_size * scale.min_element(),
+            ObjectFit::Cover => image_size * scale.max_element(),
+            ObjectFit::None => image_size,
+            ObjectFit::ScaleDown => image_size * scale.min_element().min(1.0),
+        }
+    }
+}
+
+/// Where an image is placed inside of the widget, like the CSS `object-position` property.
+///
+/// Pixels offset the image from the top left corner of the widget. A percentage lines up that
+/// point of the image with the same point of the widget, so 50% centers the image.
+#[derive(Reflect, Debug, Clone, PartialEq, Copy)]
+pub struct ObjectPosition {
+    /// The horizontal position.
+    pub x: Units,
+    /// The vertical position.
+    pub y: Units,
+}
+
+impl Default for ObjectPosition {
+    fn default() -> Self {
+        Self::TOP_LEFT
+    }
+}
+
+impl ObjectPosition {
+    /// Places the image in the top left corner.
+    pub const TOP_LEFT: ObjectPosition = ObjectPosition {
+        x: Units::Pixels(0.0),
+        y: Units::Pixels(0.0),
+    };
+
+    /// Centers the image.
+    pub const CENTER: ObjectPosition = ObjectPosition {
+        x: Units::Percentage(50.0),
+        y: Units::Percentage(50.0),
+    };
+
+    /// Creates a new object position.
+    pub fn new(x: impl Into<Units>, y: impl Into<Units>) -> Self {
+        Self {
+            x: x.into(),
+            y: y.into(),
+        }
+    }
+
+    /// Returns the offset of an image of `fitted_size` from the top left corner of the widget.
+    pub fn offset(&self, fitted_size: Vec2, widget_size: Vec2) -> Vec2 {
+        let free = widget_size - fitted_size;
+        let resolve = |units: Units, free: f32| match units {
+            Units::Pixels(pixels) => pixels,
+            Units::Percentage(percentage) => free * percentage / 100.0,
+            Units::Auto => free / 2.0,
+        };
+        Vec2::new(resolve(self.x, free.x), resolve(self.y, free.y))
+    }
+}
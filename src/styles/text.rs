@@ -0,0 +1,364 @@
+use bevy::prelude::*;
+
+/// A line drawn along a run of text.
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TextDecorationLine {
+    /// The color of the line, the text color is used if none is set.
+    pub color: Option<Color>,
+    /// The thickness of the line in pixels, the font's metrics are used if none is set.
+    pub thickness: Option<f32>,
+}
+
+impl TextDecorationLine {
+    /// A line in the text color with the thickness from the font.
+    pub const DEFAULT: TextDecorationLine = TextDecorationLine {
+        color: None,
+        thickness: None,
+    };
+
+    /// Sets the color of the line.
+    pub fn with_color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// Sets the thickness of the line in pixels.
+    pub fn with_thickness(mut self, thickness: f32) -> Self {
+        self.thickness = Some(thickness);
+        self
+    }
+}
+
+/// Lines and a background highlight drawn with text.
+///
+/// Only applies to [`crate::prelude::WidgetRender::Text`] and
+/// [`crate::prelude::WidgetRender::RichText`].
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TextDecoration {
+    /// A line below the baseline.
+    pub underline: Option<TextDecorationLine>,
+    /// A line through the middle of the text.
+    pub strikethrough: Option<TextDecorationLine>,
+    /// A line above the text.
+    pub overline: Option<TextDecorationLine>,
+    /// A background color drawn behind the text.
+    pub highlight: Option<Color>,
+}
+
+impl TextDecoration {
+    /// No decorations.
+    pub const NONE: TextDecoration = TextDecoration {
+        underline: None,
+        strikethrough: None,
+        overline: None,
+        highlight: None,
+    };
+
+    /// Adds an underline.
+    pub fn with_underline(mut self, line: TextDecorationLine) -> Self {
+        self.underline = Some(line);
+        self
+    }
+
+    /// Adds a strikethrough.
+    pub fn with_strikethrough(mut self, line: TextDecorationLine) -> Self {
+        self.strikethrough = Some(line);
+        self
+    }
+
+    /// Adds an overline.
+    pub fn with_overline(mut self, line: TextDecorationLine) -> Self {
+        self.overline = Some(line);
+        self
+    }
+
+    /// Adds a background highlight.
+    pub fn with_highlight(mut self, color: Color) -> Self {
+        self.highlight = Some(color);
+        self
+    }
+
+    /// Returns true if nothing is drawn.
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+}
+
+/// An outline drawn around the glyphs of text.
+///
+/// The outline is stroked from the glyph outlines so it stays crisp at any scale.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct TextStroke {
+    /// The width of the outline outside of the glyphs in pixels.
+    pub width: f32,
+    /// The color of the outline.
+    pub color: Color,
+}
+
+impl TextStroke {
+    /// Creates a new text outline.
+    pub fn new(width: f32, color: Color) -> Self {
+        Self { width, color }
+    }
+}
+
+/// A shadow drawn behind text.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct TextDropShadow {
+    /// How far the shadow is moved from the text in pixels.
+    pub offset: Vec2,
+    /// How far the shadow is spread out in pixels.
+    ///
+    /// The blur is approximated by layering wider and fainter outlines of the glyphs.
+    pub blur: f32,
+    /// The color of the shadow.
+    pub color: Color,
+}
+
+impl TextDropShadow {
+    /// Creates a new text shadow.
+    pub fn new(offset: Vec2, blur: f32, color: Color) -> Self {
+        Self {
+            offset,
+            blur,
+            color,
+        }
+    }
+}
+
+/// The weight (boldness) of a font, from 1 to 1000.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct FontWeight(pub f32);
+
+impl FontWeight {
+    /// Thin weight (100).
+    pub const THIN: FontWeight = FontWeight(100.0);
+    /// Light weight (300).
+    pub const LIGHT: FontWeight = FontWeight(300.0);
+    /// Normal weight (400).
+    pub const NORMAL: FontWeight = FontWeight(400.0);
+    /// Medium weight (500).
+    pub const MEDIUM: FontWeight = FontWeight(500.0);
+    /// Semi bold weight (600).
+    pub const SEMI_BOLD: FontWeight = FontWeight(600.0);
+    /// Bold weight (700).
+    pub const BOLD: FontWeight = FontWeight(700.0);
+    /// Black weight (900).
+    pub const BLACK: FontWeight = FontWeight(900.0);
+}
+
+impl Default for FontWeight {
+    fn default() -> Self {
+        FontWeight::NORMAL
+    }
+}
+
+impl From<FontWeight> for parley::FontWeight {
+    fn from(weight: FontWeight) -> Self {
+        parley::FontWeight::new(weight.0)
+    }
+}
+
+/// The slant of a font.
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub enum FontStyle {
+    /// Upright glyphs.
+    #[default]
+    Normal,
+    /// Italic glyphs, synthesized by skewing if the font has no italic face.
+    Italic,
+    /// Slanted glyphs.
+    Oblique,
+}
+
+impl From<FontStyle> for parley::FontStyle {
+    fn from(style: FontStyle) -> Self {
+        match style {
+            FontStyle::Normal => parley::FontStyle::Normal,
+            FontStyle::Italic => parley::FontStyle::Italic,
+            FontStyle::Oblique => parley::FontStyle::Oblique(None),
+        }
+    }
+}
+
+/// How text that doesn't fit is cut off.
+///
+/// Text is truncated when it has more lines than [`crate::prelude::WoodpeckerStyle::max_lines`]
+/// or a line is wider than the widget.
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub enum TextOverflow {
+    /// Text can overflow the widget horizontally.
+    #[default]
+    Visible,
+    /// Text is clipped at the edge of the widget.
+    Clip,
+    /// The end of the last visible line is replaced with an ellipsis (…).
+    Ellipsis,
+    /// The end of the last visible line fades out.
+    Fade,
+}
+
+/// The width of a font face as a ratio of its normal width, from 0.5 to 2.0.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct FontStretch(pub f32);
+
+impl FontStretch {
+    /// Ultra condensed width (50%).
+    pub const ULTRA_CONDENSED: FontStretch = FontStretch(0.5);
+    /// Condensed width (75%).
+    pub const CONDENSED: FontStretch = FontStretch(0.75);
+    /// Semi condensed width (87.5%).
+    pub const SEMI_CONDENSED: FontStretch = FontStretch(0.875);
+    /// Normal width (100%).
+    pub const NORMAL: FontStretch = FontStretch(1.0);
+    /// Semi expanded width (112.5%).
+    pub const SEMI_EXPANDED: FontStretch = FontStretch(1.125);
+    /// Expanded width (125%).
+    pub const EXPANDED: FontStretch = FontStretch(1.25);
+    /// Ultra expanded width (200%).
+    pub const ULTRA_EXPANDED: FontStretch = FontStretch(2.0);
+}
+
+impl Default for FontStretch {
+    fn default() -> Self {
+        FontStretch::NORMAL
+    }
+}
+
+impl From<FontStretch> for parley::FontWidth {
+    fn from(stretch: FontStretch) -> Self {
+        parley::FontWidth::from_ratio(stretch.0)
+    }
+}
+
+/// A value for a variable font axis, for example `FontVariation::new(b"wght", 650.0)`.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct FontVariation {
+    /// The four letter tag of the axis.
+    pub tag: [u8; 4],
+    /// The value on the axis.
+    pub value: f32,
+}
+
+impl FontVariation {
+    /// Creates a new axis value.
+    pub const fn new(tag: &[u8; 4], value: f32) -> Self {
+        Self { tag: *tag, value }
+    }
+}
+
+/// An OpenType feature setting, for example `FontFeature::new(b"smcp", 1)` for small caps.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq)]
+pub struct FontFeature {
+    /// The four letter tag of the feature.
+    pub tag: [u8; 4],
+    /// The value of the feature, usually 1 to turn it on and 0 to turn it off.
+    pub value: u16,
+}
+
+impl FontFeature {
+    /// Digits which all have the same width.
+    pub const TABULAR_NUMBERS: FontFeature = FontFeature::new(b"tnum", 1);
+    /// Digits with varying widths.
+    pub const PROPORTIONAL_NUMBERS: FontFeature = FontFeature::new(b"pnum", 1);
+    /// Turns off standard ligatures.
+    pub const NO_LIGATURES: FontFeature = FontFeature::new(b"liga", 0);
+    /// Turns on discretionary ligatures.
+    pub const DISCRETIONARY_LIGATURES: FontFeature = FontFeature::new(b"dlig", 1);
+    /// Turns off contextual alternates.
+    pub const NO_CONTEXTUAL_ALTERNATES: FontFeature = FontFeature::new(b"calt", 0);
+    /// Lowercase letters drawn as small capitals.
+    pub const SMALL_CAPS: FontFeature = FontFeature::new(b"smcp", 1);
+    /// A zero with a slash or dot.
+    pub const SLASHED_ZERO: FontFeature = FontFeature::new(b"zero", 1);
+
+    /// Creates a new feature setting.
+    pub const fn new(tag: &[u8; 4], value: u16) -> Self {
+        Self { tag: *tag, value }
+    }
+}
+
+/// The maximum number of [`FontVariations`] or [`FontFeatures`] of a style.
+pub const MAX_FONT_SETTINGS: usize = 8;
+
+/// Values for the axes of a variable font.
+///
+/// Holds up to [`MAX_FONT_SETTINGS`] axes so styles stay [`Copy`].
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub struct FontVariations([Option<FontVariation>; MAX_FONT_SETTINGS]);
+
+impl FontVariations {
+    /// No axis values, the font's defaults and the font weight, style and stretch are used.
+    pub const NONE: FontVariations = FontVariations([None; MAX_FONT_SETTINGS]);
+
+    /// Sets the value of an axis, replacing an existing value for the same axis.
+    ///
+    /// Values past [`MAX_FONT_SETTINGS`] are ignored.
+    pub const fn with(mut self, variation: FontVariation) -> Self {
+        let mut i = 0;
+        while i < MAX_FONT_SETTINGS {
+            match self.0[i] {
+                Some(existing) if !tag_eq(&existing.tag, &variation.tag) => {}
+                _ => {
+                    self.0[i] = Some(variation);
+                    return self;
+                }
+            }
+            i += 1;
+        }
+        self
+    }
+
+    /// Iterates over the axis values.
+    pub fn iter(&self) -> impl Iterator<Item = &FontVariation> {
+        self.0.iter().flatten()
+    }
+
+    /// Returns true if there are no axis values.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+/// OpenType feature settings such as tabular numbers or ligatures.
+///
+/// Holds up to [`MAX_FONT_SETTINGS`] features so styles stay [`Copy`].
+#[derive(Reflect, Debug, Default, Clone, Copy, PartialEq)]
+pub struct FontFeatures([Option<FontFeature>; MAX_FONT_SETTINGS]);
+
+impl FontFeatures {
+    /// No features, the font's defaults are used.
+    pub const NONE: FontFeatures = FontFeatures([None; MAX_FONT_SETTINGS]);
+
+    /// Sets a feature, replacing an existing setting of the same feature.
+    ///
+    /// Features past [`MAX_FONT_SETTINGS`] are ignored.
+    pub const fn with(mut self, feature: FontFeature) -> Self {
+        let mut i = 0;
+        while i < MAX_FONT_SETTINGS {
+            match self.0[i] {
+                Some(existing) if !tag_eq(&existing.tag, &feature.tag) => {}
+                _ => {
+                    self.0[i] = Some(feature);
+                    return self;
+                }
+            }
+            i += 1;
+        }
+        self
+    }
+
+    /// Iterates over the features.
+    pub fn iter(&self) -> impl Iterator<Item = &FontFeature> {
+        self.0.iter().flatten()
+    }
+
+    /// Returns true if there are no features.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+const fn tag_eq(a: &[u8; 4], b: &[u8; 4]) -> bool {
+    a[0] == b[0] && a[1] == b[1] && a[2] == b[2] && a[3] == b[3]
+}
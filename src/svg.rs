@@ -1,5 +1,6 @@
 use std::{
     hash::{DefaultHasher, Hash, Hasher},
+    str::FromStr,
     sync::Arc,
 };
 
@@ -11,10 +12,20 @@ use bevy::{
 };
 use bevy_vello::integrations::VectorLoaderError;
 
+use crate::{lru_cache::LruCache, render::WidgetRender};
+
 #[derive(Default)]
 pub struct SvgLoader;
 
 /// An SVG asset which can be rendered in the UI.
+///
+/// Besides loading `.svg` files with the asset server, assets can be built at runtime from a
+/// string or a [`usvg::Tree`] and added to `Assets<SvgAsset>`:
+///
+/// ```ignore
+/// let icon: SvgAsset = format!(r#"<svg viewBox="0 0 10 10"><circle class="dot" cx="5" cy="5" r="{radius}"/></svg>"#).parse()?;
+/// let handle = svg_assets.add(icon);
+/// ```
 #[derive(Asset, TypePath, Clone)]
 pub struct SvgAsset {
     /// A usvg svg asset.
@@ -23,6 +34,169 @@ pub struct SvgAsset {
     pub width: f32,
     /// The height of the svg
     pub height: f32,
+    /// The ids of the elements with each class, usvg drops classes.
+    classes: HashMap<String, Vec<String>>,
+}
+
+impl SvgAsset {
+    /// Creates an svg asset from a usvg tree, for example one built at runtime.
+    ///
+    /// Elements of the tree can only be recolored by their id.
+    pub fn from_tree(tree: usvg::Tree) -> Self {
+        Self::from_tree_with_classes(tree, HashMap::default())
+    }
+
+    /// Creates an svg asset from a usvg tree and the ids of the elements with each class, so
+    /// elements can also be recolored by their class.
+    pub fn from_tree_with_classes(tree: usvg::Tree, classes: HashMap<String, Vec<String>>) -> Self {
+        let size = tree.size();
+        Self {
+            width: size.width(),
+            height: size.height(),
+            tree,
+            classes,
+        }
+    }
+
+    /// The ids of the elements with each class.
+    ///
+    /// usvg drops classes, so this is only known for svgs parsed from text or created with
+    /// [`SvgAsset::from_tree_with_classes`].
+    pub fn classes(&self) -> &HashMap<String, Vec<String>> {
+        &self.classes
+    }
+
+    /// Parses an svg document from utf-8 bytes.
+    pub fn from_data(data: &[u8]) -> Result<Self, usvg::Error> {
+        std::str::from_utf8(data)
+            .map_err(|_| usvg::Error::NotAnUtf8Str)?
+            .parse()
+    }
+
+    /// The ids of the elements a selector selects.
+    pub(crate) fn selected_ids<'a>(
+        &'a self,
+        selector: &'a SvgSelector,
+    ) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        match selector {
+            SvgSelector::Id(id) => Box::new(std::iter::once(id.as_str())),
+            SvgSelector::Class(class) => Box::new(
+                self.classes
+                    .get(class)
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str),
+            ),
+        }
+    }
+
+    /// The color of the element with the given id, the last matching selector wins.
+    pub(crate) fn element_color(
+        &self,
+        id: &str,
+        element_colors: &[(SvgSelector, Color)],
+    ) -> Option<Color> {
+        if id.is_empty() {
+            return None;
+        }
+        element_colors
+            .iter()
+            .rev()
+            .find(|(selector, _)| self.selected_ids(selector).any(|selected| selected == id))
+            .map(|(_, color)| *color)
+    }
+}
+
+impl FromStr for SvgAsset {
+    type Err = usvg::Error;
+
+    fn from_str(svg: &str) -> Result<Self, Self::Err> {
+        let (svg, classes) = index_classes(svg);
+        let tree = usvg::Tree::from_str(&svg, &usvg::Options::default())?;
+        Ok(Self::from_tree_with_classes(tree, classes))
+    }
+}
+
+/// Selects elements of an svg, used to recolor parts of an svg.
+///
+/// Colors apply to the selected element and everything inside of it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub enum SvgSelector {
+    /// Selects the element with this id.
+    Id(String),
+    /// Selects the elements with this class.
+    Class(String),
+}
+
+/// Recolors elements of the [`crate::prelude::WidgetRender::Svg`] of a widget.
+///
+/// Replaces the paths and fills of elements selected by id or class, and of everything inside of
+/// them. Later entries win over earlier ones and over the color of the svg render.
+///
+/// ```ignore
+/// commands.spawn((
+///     Element,
+///     WidgetRender::Svg { handle, color: None },
+///     SvgElementColors::default()
+///         .with(SvgSelector::Class("primary".into()), colors::PRIMARY)
+///         .with(SvgSelector::Id("star".into()), Color::WHITE),
+/// ));
+/// ```
+#[derive(Component, Reflect, Debug, Default, Clone, PartialEq)]
+pub struct SvgElementColors(pub Vec<(SvgSelector, Color)>);
+
+impl SvgElementColors {
+    /// Adds a color for the elements the selector selects.
+    pub fn with(mut self, selector: SvgSelector, color: Color) -> Self {
+        self.0.push((selector, color));
+        self
+    }
+}
+
+/// Gives every element with a class an id, so the elements can still be found after usvg drops
+/// the classes. Returns the svg and the ids of the elements of every class.
+fn index_classes(svg: &str) -> (String, HashMap<String, Vec<String>>) {
+    let mut classes = HashMap::<String, Vec<String>>::default();
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    // Invalid documents are left to usvg to report.
+    let Ok(document) = roxmltree::Document::parse_with_options(svg, options) else {
+        return (svg.to_string(), classes);
+    };
+
+    let mut inserts = Vec::new();
+    for node in document.descendants().filter(|node| node.is_element()) {
+        let Some(class) = node.attribute("class") else {
+            continue;
+        };
+        let id = match node.attribute("id") {
+            Some(id) => id.to_string(),
+            None => {
+                // An element with attributes always has whitespace after its name.
+                let start = node.range().start;
+                let Some(offset) = svg[start..].find(char::is_whitespace) else {
+                    continue;
+                };
+                let id = format!("woodpecker-class-{}", inserts.len());
+                inserts.push((start + offset, format!(" id=\"{id}\"")));
+                id
+            }
+        };
+        for class in class.split_whitespace() {
+            classes
+                .entry(class.to_string())
+                .or_default()
+                .push(id.clone());
+        }
+    }
+
+    let mut svg = svg.to_string();
+    for (position, attribute) in inserts.into_iter().rev() {
+        svg.insert_str(position, &attribute);
+    }
+    (svg, classes)
 }
 
 impl AssetLoader for SvgLoader {
@@ -54,19 +228,11 @@ impl AssetLoader for SvgLoader {
             match ext {
                 "svg" => {
                     let svg_str = std::str::from_utf8(&bytes)?;
-                    let svg =
-                        usvg::Tree::from_str(svg_str, &usvg::Options::default()).map_err(|_| {
-                            VectorLoaderError::Io(std::io::Error::new(
-                                std::io::ErrorKind::InvalidData,
-                                "Invalid svg file",
-                            ))
-                        })?;
-                    let width = svg.size().width();
-                    let height = svg.size().height();
-                    Ok(SvgAsset {
-                        tree: svg,
-                        width,
-                        height,
+                    svg_str.parse().map_err(|_| {
+                        VectorLoaderError::Io(std::io::Error::new(
+                            std::io::ErrorKind::InvalidData,
+                            "Invalid svg file",
+                        ))
                     })
                 }
                 ext => Err(VectorLoaderError::Io(std::io::Error::new(
@@ -82,41 +248,110 @@ impl AssetLoader for SvgLoader {
     }
 }
 
-#[derive(Resource, Default)]
+/// How many encoded svgs, one per asset and set of colors, are kept around.
+const SVG_CACHE_CAPACITY: usize = 256;
+
+/// Caches svgs encoded into vello scenes.
+///
+/// Entries are dropped when their asset is modified or removed, and the least recently used
+/// entries are dropped once the cache is full.
+#[derive(Resource)]
 pub struct SvgManager {
-    svg_cache: HashMap<u64, Arc<bevy_vello::vello::Scene>>,
+    svg_cache: LruCache<(AssetId<SvgAsset>, u64), Arc<bevy_vello::vello::Scene>>,
+}
+
+impl Default for SvgManager {
+    fn default() -> Self {
+        Self {
+            svg_cache: LruCache::new(SVG_CACHE_CAPACITY),
+        }
+    }
 }
 
 impl SvgManager {
-    pub fn get_cached(
+    pub(crate) fn get_cached(
         &mut self,
         asset_id: impl Into<AssetId<SvgAsset>>,
         svg_assets: &Assets<SvgAsset>,
         color: Option<Color>,
+        element_colors: &[(SvgSelector, Color)],
     ) -> Option<Arc<bevy_vello::vello::Scene>> {
         let asset_id: AssetId<SvgAsset> = asset_id.into();
         let svg = svg_assets.get(asset_id)?;
 
         let mut hasher = DefaultHasher::default();
-        asset_id.hash(&mut hasher);
         if let Some(color) = color {
             color.to_srgba().to_hex().hash(&mut hasher);
         }
-        let key: u64 = hasher.finish();
+        for (selector, color) in element_colors {
+            selector.hash(&mut hasher);
+            color.to_srgba().to_hex().hash(&mut hasher);
+        }
+        let key = (asset_id, hasher.finish());
 
         Some(
             self.svg_cache
-                .entry(key)
-                .or_insert_with(|| {
+                .get_or_insert_with(key, || {
+                    let to_peniko = |color: Color| {
+                        let c = color.to_srgba();
+                        bevy_vello::vello::peniko::Color::new([c.red, c.green, c.blue, c.alpha])
+                    };
                     Arc::new(crate::vello_svg::render_tree(
                         &svg.tree,
-                        color.map(|c| {
-                            let c = c.to_srgba();
-                            bevy_vello::vello::peniko::Color::new([c.red, c.green, c.blue, c.alpha])
-                        }),
+                        color.map(to_peniko),
+                        &|id| svg.element_color(id, element_colors).map(to_peniko),
                     ))
                 })
                 .clone(),
         )
     }
 }
+
+/// Drops cached scenes of modified or removed svgs so edited svgs are drawn again.
+///
+/// Svgs which were never drawn are skipped, otherwise only the widgets drawing the svg are
+/// re-encoded.
+pub(crate) fn invalidate_svg_cache(
+    mut svg_manager: ResMut<SvgManager>,
+    mut widget_renders: Query<&mut WidgetRender>,
+    mut event_reader: EventReader<AssetEvent<SvgAsset>>,
+) {
+    for event in event_reader.read() {
+        if let AssetEvent::Modified { id } | AssetEvent::Removed { id } = event {
+            if svg_manager.svg_cache.retain(|(asset_id, _)| asset_id != id) {
+                WidgetRender::mark_asset_users_changed(&mut widget_renders, *id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_classes_injects_ids() {
+        let svg = r#"<svg xmlns="http://www.w3.org/2000/svg">
+<rect class="box" width="1" height="1"/>
+<circle id="dot" class="box round" r="1"/>
+<path
+  class="round" d="M0 0 H1 V1 Z"/>
+<g><rect width="1" height="1"/></g>
+</svg>"#;
+        let (svg, classes) = index_classes(svg);
+
+        // Elements without an id get a generated one, existing ids are kept.
+        assert!(svg.contains(r#"<rect id="woodpecker-class-0" class="box""#));
+        assert!(svg.contains(r#"<circle id="dot" class="box round""#));
+        assert!(svg.contains("<path id=\"woodpecker-class-1\"\n  class=\"round\""));
+        assert!(svg.contains(r#"<g><rect width="1" height="1"/></g>"#));
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes["box"], ["woodpecker-class-0", "dot"]);
+        assert_eq!(classes["round"], ["dot", "woodpecker-class-1"]);
+
+        // The ids survive parsing, so the elements can be selected by class.
+        let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+        assert!(tree.node_by_id("woodpecker-class-0").is_some());
+        assert!(tree.node_by_id("woodpecker-class-1").is_some());
+    }
+}
@@ -0,0 +1,403 @@
+use std::{fmt::Write, path::PathBuf};
+
+use bevy::prelude::*;
+use bevy_vello::vello::{
+    kurbo::{Affine, Shape},
+    peniko::{self, Brush},
+};
+use image::{codecs::png::PngEncoder, ImageEncoder};
+
+use crate::{
+    display_list::{painted_path, tint_factors, DrawOp, UiRenderBackend},
+    svg::{SvgAsset, SvgSelector},
+};
+
+/// Exports the next rendered frame of a UI root as a standalone SVG file.
+///
+/// Quads, borders, text, SVGs, images, nine patches, shapes and layers are exported. Text is
+/// converted to outlined glyph paths and images are embedded as data URIs, so the file doesn't
+/// depend on any fonts or assets. [`crate::prelude::WidgetRender::Custom`],
+/// [`crate::prelude::WidgetRender::Lottie`] and [`crate::prelude::WidgetRender::RenderTarget`]
+/// have no CPU side representation and are skipped.
+///
+/// If the root isn't rendered this frame, for example because its camera isn't active yet, the
+/// export waits until it is. Exports of entities which aren't UI roots are dropped with an error.
+///
+/// ```ignore
+/// commands.send_event(ExportSvg::new("screenshot.svg"));
+/// ```
+#[derive(Event, Debug, Clone)]
+pub struct ExportSvg {
+    /// Where the SVG file is written.
+    pub path: PathBuf,
+    /// The UI root to export, the main root if `None`.
+    pub root: Option<Entity>,
+}
+
+impl ExportSvg {
+    /// Creates a new export of the main root.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            root: None,
+        }
+    }
+
+    /// Exports the given UI root instead of the main root.
+    pub fn with_root(mut self, root: Entity) -> Self {
+        self.root = Some(root);
+        self
+    }
+}
+
+/// A render backend which writes draw operations into a standalone SVG document.
+pub struct SvgBackend<'a> {
+    svg_assets: &'a Assets<SvgAsset>,
+    defs: String,
+    body: String,
+    clip_count: usize,
+    filter_count: usize,
+}
+
+impl<'a> SvgBackend<'a> {
+    /// Creates an empty SVG document.
+    pub fn new(svg_assets: &'a Assets<SvgAsset>) -> Self {
+        Self {
+            svg_assets,
+            defs: String::new(),
+            body: String::new(),
+            clip_count: 0,
+            filter_count: 0,
+        }
+    }
+
+    /// Finishes the document with the given size in pixels.
+    pub fn finish(self, size: Vec2) -> String {
+        let mut out = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            size.x, size.y
+        );
+        out.push('\n');
+        if !self.defs.is_empty() {
+            out.push_str("<defs>\n");
+            out.push_str(&self.defs);
+            out.push_str("</defs>\n");
+        }
+        out.push_str(&self.body);
+        out.push_str("</svg>\n");
+        out
+    }
+
+    fn path(&mut self, shape: &impl Shape, color: Color) {
+        let Some(fill) = fill_attributes(color) else {
+            return;
+        };
+        let _ = writeln!(
+            self.body,
+            r#"<path d="{}" {fill}/>"#,
+            shape.to_path(0.1).to_svg()
+        );
+    }
+
+    fn image(&mut self, image: &peniko::Image, transform: Affine, tint: Option<Color>) {
+        // Render targets only have data on the GPU.
+        if image.data.data().len() != (image.width * image.height * 4) as usize {
+            return;
+        }
+        let mut png = vec![];
+        if PngEncoder::new(&mut png)
+            .write_image(
+                image.data.data(),
+                image.width,
+                image.height,
+                image::ColorType::Rgba8,
+            )
+            .is_err()
+        {
+            return;
+        }
+        let rendering = match image.quality {
+            peniko::ImageQuality::Low => r#" image-rendering="pixelated""#,
+            peniko::ImageQuality::Medium | peniko::ImageQuality::High => "",
+        };
+        let filter = match tint {
+            Some(tint) => {
+                let id = self.filter_count;
+                self.filter_count += 1;
+                let [r, g, b] = tint_factors(tint);
+                let _ = writeln!(
+                    self.defs,
+                    r#"<filter id="tint{id}" color-interpolation-filters="sRGB"><feColorMatrix type="matrix" values="{r} 0 0 0 0 0 {g} 0 0 0 0 0 {b} 0 0 0 0 0 1 0"/></filter>"#
+                );
+                format!(r#" filter="url(#tint{id})""#)
+            }
+            None => String::new(),
+        };
+        let _ = writeln!(
+            self.body,
+            r#"<image width="{}" height="{}" transform="{}" preserveAspectRatio="none"{rendering}{filter} href="data:image/png;base64,{}"/>"#,
+            image.width,
+            image.height,
+            matrix(transform),
+            base64(&png)
+        );
+    }
+
+    fn svg(
+        &mut self,
+        handle: &Handle<SvgAsset>,
+        color: Option<Color>,
+        element_colors: &[(SvgSelector, Color)],
+        transform: Affine,
+    ) {
+        let Some(svg_asset) = self.svg_assets.get(handle) else {
+            return;
+        };
+        let mut source = svg_asset.tree.to_string(&usvg::WriteOptions::default());
+        // Recolor every painted fill and stroke the same way the renderer does.
+        let mut style = String::new();
+        if let Some(color) = color {
+            let color = css_color(color);
+            let _ = write!(
+                style,
+                r#"[fill]:not([fill="none"]){{fill:{color}}}[stroke]:not([stroke="none"]){{stroke:{color}}}"#
+            );
+        }
+        // Attribute selectors outweigh the rules above, later selectors win like in the renderer.
+        for (selector, color) in element_colors {
+            let color = css_color(*color);
+            for id in svg_asset.selected_ids(selector) {
+                let id = id
+                    .replace('\\', "\\\\")
+                    .replace('"', "\\\"")
+                    .replace('&', "&amp;")
+                    .replace('<', "&lt;");
+                for element in [format!(r#"[id="{id}"]"#), format!(r#"[id="{id}"] "#)] {
+                    let _ = write!(
+                        style,
+                        r#"{element}[fill]:not([fill="none"]){{fill:{color}}}{element}[stroke]:not([stroke="none"]){{stroke:{color}}}"#
+                    );
+                }
+            }
+        }
+        if !style.is_empty() {
+            let style = format!("<style>{style}</style>");
+            if let Some(end) = source
+                .find("<svg")
+                .and_then(|start| source[start..].find('>').map(|end| start + end + 1))
+            {
+                source.insert_str(end, &style);
+            }
+        }
+        let _ = writeln!(
+            self.body,
+            r#"<image width="{}" height="{}" transform="{}" href="data:image/svg+xml;base64,{}"/>"#,
+            svg_asset.width,
+            svg_asset.height,
+            matrix(transform),
+            base64(source.as_bytes())
+        );
+    }
+}
+
+impl UiRenderBackend for SvgBackend<'_> {
+    fn draw(&mut self, op: &DrawOp) {
+        match op {
+            DrawOp::Rect { rect, color } => self.path(rect, *color),
+            DrawOp::RoundedRect { rect, color } => self.path(rect, *color),
+            DrawOp::Border { outer, color, .. } => self.path(outer, *color),
+            DrawOp::GlyphRun(run) => {
+                let Brush::Solid(color) = run.brush else {
+                    return;
+                };
+                let [r, g, b, a] = color.components;
+                let Some(fill) = fill_attributes(Color::srgba(r, g, b, a)) else {
+                    return;
+                };
+                let path = run.shape();
+                if !path.is_empty() {
+                    let _ = writeln!(self.body, r#"<path d="{}" {fill}/>"#, path.to_svg());
+                }
+            }
+            DrawOp::Path {
+                path,
+                transform,
+                brush,
+                stroke,
+            } => {
+                let Brush::Solid(color) = brush else {
+                    return;
+                };
+                let [r, g, b, a] = color.components;
+                self.path(
+                    &painted_path(path, *transform, stroke.as_ref()),
+                    Color::srgba(r, g, b, a),
+                );
+            }
+            DrawOp::Image {
+                image,
+                transform,
+                tint,
+            } => self.image(image, *transform, *tint),
+            DrawOp::Svg {
+                handle,
+                color,
+                element_colors,
+                transform,
+            } => self.svg(handle, *color, element_colors, *transform),
+            // Custom renders and lottie animations only exist inside of vello.
+            DrawOp::Custom { .. } | DrawOp::Lottie { .. } => {}
+            DrawOp::PushLayer { clip, opacity } => {
+                let id = self.clip_count;
+                self.clip_count += 1;
+                let _ = writeln!(
+                    self.defs,
+                    r#"<clipPath id="clip{id}"><path d="{}"/></clipPath>"#,
+                    clip.to_path(0.1).to_svg()
+                );
+                let _ = writeln!(
+                    self.body,
+                    r#"<g clip-path="url(#clip{id})" opacity="{opacity}">"#
+                );
+            }
+            DrawOp::PopLayer => self.body.push_str("</g>\n"),
+        }
+    }
+}
+
+fn fill_attributes(color: Color) -> Option<String> {
+    let alpha = color.alpha();
+    if alpha <= 0.0 {
+        return None;
+    }
+    let color = css_color(color.with_alpha(1.0));
+    if alpha >= 1.0 {
+        Some(format!(r#"fill="{color}""#))
+    } else {
+        Some(format!(r#"fill="{color}" fill-opacity="{alpha}""#))
+    }
+}
+
+fn css_color(color: Color) -> String {
+    let [r, g, b, _] = color.to_srgba().to_u8_array();
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+fn matrix(transform: Affine) -> String {
+    let [a, b, c, d, e, f] = transform.as_coeffs();
+    format!("matrix({a} {b} {c} {d} {e} {f})")
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - i * 6)) as usize & 63] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bevy_vello::vello::{
+        self, kurbo,
+        peniko::{Blob, ImageFormat},
+    };
+    use skrifa::{FontRef, MetadataProvider};
+
+    use super::*;
+    use crate::display_list::GlyphRun;
+
+    #[test]
+    fn base64_encodes_padding() {
+        // The test vectors of RFC 4648.
+        for (input, output) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(base64(input.as_bytes()), output, "{input:?}");
+        }
+        assert_eq!(base64(&[0xff, 0xfe, 0xfd, 0x00]), "//79AA==");
+    }
+
+    #[test]
+    fn exports_display_list() {
+        let font_data = include_bytes!("embedded_assets/Poppins-Regular.ttf");
+        let glyph_id = FontRef::new(font_data).unwrap().charmap().map('I').unwrap();
+        let glyph_run = GlyphRun {
+            font: peniko::Font::new(Blob::new(Arc::new(font_data.to_vec())), 0),
+            font_size: 20.0,
+            normalized_coords: vec![],
+            transform: Affine::translate((0.0, 20.0)),
+            brush: Brush::Solid(peniko::Color::new([0.0, 0.0, 1.0, 1.0])),
+            stroke: None,
+            glyphs: vec![vello::Glyph {
+                id: glyph_id.to_u32(),
+                x: 0.0,
+                y: 0.0,
+            }],
+        };
+        let image = peniko::Image::new(
+            Blob::new(Arc::new([255u8; 4].to_vec())),
+            ImageFormat::Rgba8,
+            1,
+            1,
+        );
+
+        let svg_assets = Assets::<SvgAsset>::default();
+        let mut backend = SvgBackend::new(&svg_assets);
+        backend.draw_all(&[
+            DrawOp::Rect {
+                rect: kurbo::Rect::new(0.0, 0.0, 10.0, 10.0),
+                color: Color::srgb(1.0, 0.0, 0.0),
+            },
+            DrawOp::PushLayer {
+                clip: kurbo::RoundedRect::new(0.0, 0.0, 20.0, 20.0, 0.0),
+                opacity: 0.5,
+            },
+            DrawOp::GlyphRun(glyph_run),
+            DrawOp::PopLayer,
+            DrawOp::Image {
+                image,
+                transform: Affine::translate((10.0, 0.0)),
+                tint: Some(Color::srgb(1.0, 0.0, 0.5)),
+            },
+        ]);
+        let svg = backend.finish(Vec2::new(30.0, 20.0));
+
+        assert!(svg.starts_with(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="20" viewBox="0 0 30 20">"#
+        ));
+        assert!(svg.contains(r##"<path d="M0,0 L10,0 L10,10 L0,10 Z" fill="#ff0000"/>"##));
+        assert!(svg.contains(r#"<clipPath id="clip0">"#));
+        assert!(svg.contains(r#"<g clip-path="url(#clip0)" opacity="0.5">"#));
+        // The glyph is outlined inside of the layer.
+        let layer = &svg[svg.find("<g ").unwrap()..svg.find("</g>").unwrap()];
+        assert!(layer.contains(r##"fill="#0000ff"/>"##), "{layer}");
+        assert!(svg.contains(r#"<filter id="tint0""#));
+        assert!(svg.contains(r#"values="1 0 0 0 0 0 0 0 0 0 0 0 0.5 0 0 0 0 0 1 0""#));
+        assert!(svg.contains(
+            r#"transform="matrix(1 0 0 1 10 0)" preserveAspectRatio="none" filter="url(#tint0)" href="data:image/png;base64,"#
+        ));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+}
@@ -0,0 +1,390 @@
+//! A CPU render backend built on [tiny-skia](https://github.com/RazrFalcon/tiny-skia).
+//!
+//! Useful for rendering display lists where no GPU is available, for example on a server or in
+//! tests. Only available with the `software-render` feature.
+//!
+//! ```ignore
+//! let display_list = world.get::<DisplayList>(root).unwrap();
+//! let mut backend = TinySkiaBackend::new(display_list.size.as_uvec2(), svg_assets).unwrap();
+//! backend.draw_all(&display_list.ops);
+//! let pixmap = backend.into_pixmap();
+//! ```
+
+use bevy::prelude::*;
+use bevy_vello::vello::{
+    kurbo::{self, Affine, PathEl, Shape},
+    peniko::{self, Brush},
+};
+use tiny_skia::{
+    FillRule, FilterQuality, Mask, Paint, PathBuilder, Pixmap, PixmapPaint, Transform,
+};
+
+use crate::{
+    display_list::{painted_path, tint_factors, DrawOp, UiRenderBackend},
+    svg::{SvgAsset, SvgSelector},
+};
+
+/// A render backend which rasterizes draw operations on the CPU.
+///
+/// The output approximates vello's rather than matching it pixel for pixel:
+/// - Gradients are not supported. Paths filled with gradients are skipped, and gradients inside
+///   of svgs are only drawn if the svg is recolored, in which case they become a solid color.
+/// - [`DrawOp::PushLayer`] draws the group into its own pixmap, which is then composited with the
+///   layer's opacity through an anti-aliased clip mask. Group opacity and clip edges are close to
+///   vello's, but blending and coverage differ slightly.
+/// - [`DrawOp::Custom`] and [`DrawOp::Lottie`] only exist inside of vello and are skipped.
+pub struct TinySkiaBackend<'a> {
+    svg_assets: &'a Assets<SvgAsset>,
+    layers: Vec<Layer>,
+}
+
+struct Layer {
+    pixmap: Pixmap,
+    clip: Option<Mask>,
+    opacity: f32,
+}
+
+impl<'a> TinySkiaBackend<'a> {
+    /// Creates a transparent canvas of the given size in pixels.
+    ///
+    /// Returns `None` if either dimension is zero.
+    pub fn new(size: UVec2, svg_assets: &'a Assets<SvgAsset>) -> Option<Self> {
+        Some(Self {
+            svg_assets,
+            layers: vec![Layer {
+                pixmap: Pixmap::new(size.x, size.y)?,
+                clip: None,
+                opacity: 1.0,
+            }],
+        })
+    }
+
+    /// Finishes drawing and returns the premultiplied canvas.
+    ///
+    /// Layers which were never popped are closed first.
+    pub fn into_pixmap(mut self) -> Pixmap {
+        while self.layers.len() > 1 {
+            self.pop_layer();
+        }
+        self.layers.remove(0).pixmap
+    }
+
+    fn pixmap(&mut self) -> &mut Pixmap {
+        &mut self
+            .layers
+            .last_mut()
+            .expect("The base layer is never popped")
+            .pixmap
+    }
+
+    fn fill(&mut self, shape: &impl Shape, color: Color) {
+        let Some(path) = to_path(shape) else {
+            return;
+        };
+        let mut paint = Paint::default();
+        paint.set_color(to_tiny_skia_color(color));
+        self.pixmap().fill_path(
+            &path,
+            &paint,
+            FillRule::Winding,
+            Transform::identity(),
+            None,
+        );
+    }
+
+    fn image(&mut self, image: &peniko::Image, transform: Affine, tint: Option<Color>) {
+        let data = image.data.data();
+        // Render targets only have data on the GPU.
+        if data.len() != (image.width * image.height * 4) as usize {
+            return;
+        }
+        let Some(mut pixmap) = Pixmap::new(image.width, image.height) else {
+            return;
+        };
+        let [r, g, b] = tint.map(tint_factors).unwrap_or([1.0; 3]);
+        let tint = |channel: u8, factor: f32| (channel as f32 * factor).round() as u8;
+        for (pixel, rgba) in pixmap.pixels_mut().iter_mut().zip(data.chunks_exact(4)) {
+            *pixel = tiny_skia::ColorU8::from_rgba(
+                tint(rgba[0], r),
+                tint(rgba[1], g),
+                tint(rgba[2], b),
+                rgba[3],
+            )
+            .premultiply();
+        }
+        let paint = PixmapPaint {
+            opacity: image.alpha,
+            quality: match image.quality {
+                peniko::ImageQuality::Low => FilterQuality::Nearest,
+                peniko::ImageQuality::Medium => FilterQuality::Bilinear,
+                peniko::ImageQuality::High => FilterQuality::Bicubic,
+            },
+            ..Default::default()
+        };
+        self.pixmap()
+            .draw_pixmap(0, 0, pixmap.as_ref(), &paint, to_transform(transform), None);
+    }
+
+    fn svg(
+        &mut self,
+        handle: &Handle<SvgAsset>,
+        color: Option<Color>,
+        element_colors: &[(SvgSelector, Color)],
+        transform: Affine,
+    ) {
+        let Some(svg_asset) = self.svg_assets.get(handle) else {
+            return;
+        };
+        let transform = to_transform(transform);
+        let layer = self
+            .layers
+            .last_mut()
+            .expect("The base layer is never popped");
+        draw_svg_group(
+            &mut layer.pixmap,
+            svg_asset.tree.root(),
+            color,
+            &|id| svg_asset.element_color(id, element_colors),
+            transform,
+        );
+    }
+
+    fn push_layer(&mut self, clip: &kurbo::RoundedRect, opacity: f32) {
+        let (width, height) = {
+            let pixmap = self.pixmap();
+            (pixmap.width(), pixmap.height())
+        };
+        let mut mask = Mask::new(width, height);
+        if let (Some(mask), Some(path)) = (mask.as_mut(), to_path(clip)) {
+            mask.fill_path(&path, FillRule::Winding, true, Transform::identity());
+        }
+        self.layers.push(Layer {
+            pixmap: Pixmap::new(width, height).expect("Size of an existing pixmap"),
+            clip: mask,
+            opacity,
+        });
+    }
+
+    fn pop_layer(&mut self) {
+        if self.layers.len() < 2 {
+            return;
+        }
+        let Some(layer) = self.layers.pop() else {
+            return;
+        };
+        let paint = PixmapPaint {
+            opacity: layer.opacity,
+            ..Default::default()
+        };
+        self.pixmap().draw_pixmap(
+            0,
+            0,
+            layer.pixmap.as_ref(),
+            &paint,
+            Transform::identity(),
+            layer.clip.as_ref(),
+        );
+    }
+}
+
+impl UiRenderBackend for TinySkiaBackend<'_> {
+    fn draw(&mut self, op: &DrawOp) {
+        match op {
+            DrawOp::Rect { rect, color } => self.fill(rect, *color),
+            DrawOp::RoundedRect { rect, color } => self.fill(rect, *color),
+            DrawOp::Border { outer, color, .. } => self.fill(outer, *color),
+            DrawOp::GlyphRun(run) => {
+                let Brush::Solid(color) = run.brush else {
+                    return;
+                };
+                let [r, g, b, a] = color.components;
+                self.fill(&run.shape(), Color::srgba(r, g, b, a));
+            }
+            DrawOp::Path {
+                path,
+                transform,
+                brush,
+                stroke,
+            } => {
+                let Brush::Solid(color) = brush else {
+                    return;
+                };
+                let [r, g, b, a] = color.components;
+                self.fill(
+                    &painted_path(path, *transform, stroke.as_ref()),
+                    Color::srgba(r, g, b, a),
+                );
+            }
+            DrawOp::Image {
+                image,
+                transform,
+                tint,
+            } => self.image(image, *transform, *tint),
+            DrawOp::Svg {
+                handle,
+                color,
+                element_colors,
+                transform,
+            } => self.svg(handle, *color, element_colors, *transform),
+            // Custom renders and lottie animations only exist inside of vello.
+            DrawOp::Custom { .. } | DrawOp::Lottie { .. } => {}
+            DrawOp::PushLayer { clip, opacity } => self.push_layer(clip, *opacity),
+            DrawOp::PopLayer => self.pop_layer(),
+        }
+    }
+}
+
+fn draw_svg_group(
+    pixmap: &mut Pixmap,
+    group: &usvg::Group,
+    color: Option<Color>,
+    element_color: &dyn Fn(&str) -> Option<Color>,
+    transform: Transform,
+) {
+    for node in group.children() {
+        let color = element_color(node.id()).or(color);
+        match node {
+            usvg::Node::Group(group) => {
+                draw_svg_group(pixmap, group, color, element_color, transform)
+            }
+            usvg::Node::Path(path) => {
+                if !path.is_visible() {
+                    continue;
+                }
+                let path_transform = transform.pre_concat(path.abs_transform());
+                if let Some(fill) = path.fill() {
+                    if let Some(paint) = svg_paint(fill.paint(), fill.opacity().get(), color) {
+                        let rule = match fill.rule() {
+                            usvg::FillRule::NonZero => FillRule::Winding,
+                            usvg::FillRule::EvenOdd => FillRule::EvenOdd,
+                        };
+                        pixmap.fill_path(path.data(), &paint, rule, path_transform, None);
+                    }
+                }
+                if let Some(stroke) = path.stroke() {
+                    if let Some(paint) = svg_paint(stroke.paint(), stroke.opacity().get(), color) {
+                        pixmap.stroke_path(
+                            path.data(),
+                            &paint,
+                            &stroke.to_tiny_skia(),
+                            path_transform,
+                            None,
+                        );
+                    }
+                }
+            }
+            usvg::Node::Text(text) => {
+                draw_svg_group(pixmap, text.flattened(), color, element_color, transform)
+            }
+            usvg::Node::Image(_) => {}
+        }
+    }
+}
+
+fn svg_paint(paint: &usvg::Paint, opacity: f32, color: Option<Color>) -> Option<Paint<'static>> {
+    let color = match (color, paint) {
+        (Some(color), _) => color,
+        (None, usvg::Paint::Color(color)) => {
+            Color::srgba_u8(color.red, color.green, color.blue, 255)
+        }
+        _ => return None,
+    };
+    let mut paint = Paint::default();
+    paint.set_color(to_tiny_skia_color(
+        color.with_alpha(color.alpha() * opacity),
+    ));
+    Some(paint)
+}
+
+fn to_tiny_skia_color(color: Color) -> tiny_skia::Color {
+    let [r, g, b, a] = color.to_srgba().to_f32_array();
+    tiny_skia::Color::from_rgba(r, g, b, a).unwrap_or(tiny_skia::Color::TRANSPARENT)
+}
+
+fn to_transform(transform: Affine) -> Transform {
+    let [a, b, c, d, e, f] = transform.as_coeffs().map(|coeff| coeff as f32);
+    Transform::from_row(a, b, c, d, e, f)
+}
+
+fn to_path(shape: &impl Shape) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+    for element in shape.path_elements(0.1) {
+        match element {
+            PathEl::MoveTo(p) => builder.move_to(p.x as f32, p.y as f32),
+            PathEl::LineTo(p) => builder.line_to(p.x as f32, p.y as f32),
+            PathEl::QuadTo(c, p) => builder.quad_to(c.x as f32, c.y as f32, p.x as f32, p.y as f32),
+            PathEl::CurveTo(c0, c1, p) => builder.cubic_to(
+                c0.x as f32,
+                c0.y as f32,
+                c1.x as f32,
+                c1.y as f32,
+                p.x as f32,
+                p.y as f32,
+            ),
+            PathEl::ClosePath => builder.close(),
+        }
+    }
+    builder.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_vello::vello::peniko::{Blob, ImageFormat, ImageQuality};
+
+    use super::*;
+
+    fn pixel(pixmap: &Pixmap, x: u32, y: u32) -> [u8; 4] {
+        let pixel = pixmap.pixel(x, y).unwrap();
+        [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+    }
+
+    #[test]
+    fn rasterizes_display_list() {
+        let svg_assets = Assets::<SvgAsset>::default();
+        let image = peniko::Image::new(
+            Blob::new(std::sync::Arc::new([200u8, 200, 200, 255].repeat(4))),
+            ImageFormat::Rgba8,
+            2,
+            2,
+        )
+        .with_quality(ImageQuality::Low);
+
+        let mut backend = TinySkiaBackend::new(UVec2::new(30, 20), &svg_assets).unwrap();
+        backend.draw_all(&[
+            DrawOp::Rect {
+                rect: kurbo::Rect::new(0.0, 0.0, 10.0, 10.0),
+                color: Color::srgb(1.0, 0.0, 0.0),
+            },
+            DrawOp::RoundedRect {
+                rect: kurbo::RoundedRect::new(10.0, 0.0, 20.0, 10.0, 5.0),
+                color: Color::srgb(0.0, 1.0, 0.0),
+            },
+            DrawOp::Image {
+                image,
+                transform: Affine::translate((20.0, 0.0)) * Affine::scale(5.0),
+                tint: Some(Color::srgb(1.0, 0.0, 0.5)),
+            },
+            DrawOp::PushLayer {
+                clip: kurbo::RoundedRect::new(0.0, 10.0, 10.0, 20.0, 0.0),
+                opacity: 0.5,
+            },
+            DrawOp::Rect {
+                rect: kurbo::Rect::new(0.0, 10.0, 30.0, 20.0),
+                color: Color::srgb(0.0, 0.0, 1.0),
+            },
+            DrawOp::PopLayer,
+        ]);
+        let pixmap = backend.into_pixmap();
+
+        assert_eq!(pixel(&pixmap, 5, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&pixmap, 15, 5), [0, 255, 0, 255]);
+        // Outside of the rounded corner.
+        assert_eq!(pixel(&pixmap, 10, 0), [0, 0, 0, 0]);
+        assert_eq!(pixel(&pixmap, 25, 5), [200, 0, 100, 255]);
+        // Premultiplied blue at half opacity inside of the clip, nothing outside of it.
+        let [r, g, b, a] = pixel(&pixmap, 5, 15);
+        assert_eq!([r, g], [0, 0]);
+        assert!(a.abs_diff(128) <= 1 && b == a, "{:?}", [r, g, b, a]);
+        assert_eq!(pixel(&pixmap, 15, 15), [0, 0, 0, 0]);
+    }
+}
@@ -1,23 +1,33 @@
 use crate::{
     convert_render_target::RenderTargetImages,
+    display_list::{DisplayList, DrawOp, UiRenderBackend, VelloBackend},
     hook_helper::StateMarker,
     image::ImageManager,
     layout::system::ReflectedLayout,
+    lottie::{LottieAsset, LottieManager},
     prelude::*,
+    render_cache::{CachedSubtree, FragmentKey, RenderCache, SubtreeKey},
+    render_to_texture::{TextureTargetScene, WoodpeckerTextureTarget},
     svg::{SvgAsset, SvgManager},
+    svg_export::{ExportSvg, SvgBackend},
+    views::{main_view_camera, WoodpeckerViewRoot},
     DefaultFont,
 };
 use bevy::{ecs::system::SystemParam, prelude::*};
 use bevy_trait_query::One;
-use bevy_vello::{prelude::VelloFont, VelloScene};
+use bevy_vello::VelloScene;
 
 #[derive(SystemParam)]
 pub(crate) struct RenderSystemParam<'w, 's> {
     default_font: Res<'w, DefaultFont>,
     font_manager: ResMut<'w, FontManager>,
     svg_manager: ResMut<'w, SvgManager>,
+    lottie_manager: ResMut<'w, LottieManager>,
     image_manager: ResMut<'w, ImageManager>,
     render_targets: ResMut<'w, RenderTargetImages>,
+    render_cache: ResMut<'w, RenderCache>,
+    svg_exports: EventReader<'w, 's, ExportSvg>,
+    pending_svg_exports: Local<'s, Vec<ExportSvg>>,
     query: Query<
         'w,
         's,
@@ -31,293 +41,825 @@ pub(crate) struct RenderSystemParam<'w, 's> {
         (Without<StateMarker>, Without<PreviousWidget>),
     >,
     layout_query: Query<'w, 's, &'static WidgetLayout>,
+    changed_widgets: Query<
+        'w,
+        's,
+        Entity,
+        Or<(
+            Changed<WoodpeckerStyle>,
+            Changed<WidgetLayout>,
+            Changed<WidgetRender>,
+            Changed<Children>,
+            Changed<TextSelection>,
+            Changed<SelectableText>,
+            Changed<WoodpeckerTextureTarget>,
+            Changed<SvgElementColors>,
+        )>,
+    >,
+    removed_renders: RemovedComponents<'w, 's, WidgetRender>,
+    removed_svg_colors: RemovedComponents<'w, 's, SvgElementColors>,
+    removed_children: RemovedComponents<'w, 's, Children>,
+    parents: Query<'w, 's, &'static ChildOf>,
     vello_query: Query<'w, 's, &'static mut VelloScene>,
-    widget_render: Query<'w, 's, &'static WidgetRender>,
+    widget_render: Query<'w, 's, Ref<'static, WidgetRender>>,
     context: Res<'w, WoodpeckerContext>,
-    font_assets: Res<'w, Assets<VelloFont>>,
     image_assets: ResMut<'w, Assets<Image>>,
     svg_assets: Res<'w, Assets<SvgAsset>>,
+    lottie_assets: Res<'w, Assets<LottieAsset>>,
+    atlas_layouts: Res<'w, Assets<TextureAtlasLayout>>,
+    display_lists: Query<'w, 's, &'static mut DisplayList>,
+    text_selections: Query<'w, 's, (&'static SelectableText, Ref<'static, TextSelection>)>,
+    svg_colors: Query<'w, 's, Ref<'static, SvgElementColors>>,
     metrics: ResMut<'w, WidgetMetrics>,
-    camera_query: Query<'w, 's, &'static Camera, With<WoodpeckerView>>,
+    camera_query: Query<'w, 's, (Entity, &'static Camera), With<WoodpeckerView>>,
+    view_roots: Query<'w, 's, &'static WoodpeckerViewRoot>,
+    texture_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static WoodpeckerTextureTarget,
+            &'static mut TextureTargetScene,
+        ),
+    >,
 }
 
 // TODO: Document how renderer works
-pub(crate) fn run(renderer_system_param: RenderSystemParam) {
-    let RenderSystemParam {
-        default_font,
-        mut font_manager,
-        mut svg_manager,
-        mut image_manager,
-        mut render_targets,
-        mut query,
-        layout_query,
-        mut vello_query,
-        widget_render,
-        context,
-        font_assets,
-        mut image_assets,
-        svg_assets,
-        mut metrics,
-        camera_query,
-    } = renderer_system_param;
-
-    let Ok(mut vello_scene) = vello_query.single_mut() else {
-        error!("Woodpecker UI: No vello scene spawned!");
-        return;
-    };
-
-    let Ok(camera) = camera_query.single() else {
-        error!("Woodpecker UI: No camera found or multiple UI cameras found.");
-        return;
-    };
-
-    let camera_scale = Vec2::new(
-        camera.target_scaling_factor().unwrap_or(1.0),
-        camera.target_scaling_factor().unwrap_or(1.0),
-    );
-
-    let camera_size = camera
-        .physical_target_size()
-        .unwrap_or(UVec2::ZERO)
-        .as_vec2();
-
-    vello_scene.reset();
-
-    metrics.clear_quad_last_frame();
-
-    let root_node = context.get_root_widget();
-    let mut render_commands = vec![];
-    let mut order = 0;
-    // After layout computations update layouts and render scene.
-    // Needs to be done in the correct order..
-    // We also need to know if we are going back up the tree so we can pop the clipping and opacity layers.
-    traverse_render_tree(
-        root_node,
-        0,
-        &mut order,
-        &mut render_commands,
-        &mut query,
-        &default_font,
-        &mut font_manager,
-        &mut svg_manager,
-        &mut image_manager,
-        &mut render_targets,
-        &mut metrics,
-        &widget_render,
-        &mut vello_scene,
-        &font_assets,
-        &mut image_assets,
-        &svg_assets,
-        &layout_query,
-        root_node,
-        true,
-        camera_scale,
-        camera_size,
-    );
-
-    // Once tree is traversed we sort the commands
-    render_commands.sort_unstable_by(|a, b| a.z.cmp(&b.z).then_with(|| a.order.cmp(&b.order)));
+pub(crate) fn run(mut renderer_system_param: RenderSystemParam) {
+    renderer_system_param.metrics.clear_quad_last_frame();
+    renderer_system_param
+        .metrics
+        .clear_render_cache_last_frame();
+
+    let exports = renderer_system_param
+        .svg_exports
+        .read()
+        .cloned()
+        .collect::<Vec<_>>();
+    renderer_system_param.pending_svg_exports.extend(exports);
+
+    renderer_system_param.mark_dirty_widgets();
+
+    // A main root with a texture target is only rendered into its texture.
+    let root_node = renderer_system_param.context.get_root_widget();
+    if !renderer_system_param.texture_query.contains(root_node) {
+        renderer_system_param.render_canvas_root(root_node);
+    }
+
+    // Texture target roots are rendered into their own scene at the scale of the target.
+    let texture_roots = renderer_system_param
+        .texture_query
+        .iter()
+        .map(|(entity, target, _)| {
+            let size = renderer_system_param
+                .image_assets
+                .get(&target.image)
+                .map(|image| image.size().as_vec2())
+                .unwrap_or_default();
+            (entity, size, target.scale)
+        })
+        .collect::<Vec<_>>();
+    for (texture_root, size, scale) in texture_roots {
+        let Ok((_, _, mut texture_scene)) =
+            renderer_system_param.texture_query.get_mut(texture_root)
+        else {
+            continue;
+        };
+        let mut scene = std::mem::take(&mut texture_scene.0);
+        renderer_system_param.render_root(texture_root, &mut scene, Vec2::splat(scale), size);
+        if let Ok((_, _, mut texture_scene)) =
+            renderer_system_param.texture_query.get_mut(texture_root)
+        {
+            texture_scene.0 = scene;
+        }
+    }
+
+    renderer_system_param.retain_pending_svg_exports();
+    renderer_system_param.render_cache.end_frame();
+    renderer_system_param.metrics.commit_quad_frame();
+}
+
+/// What was encoded by [`RenderSystemParam::encode_commands`].
+#[derive(Default)]
+struct EncodedCommands {
+    /// False if any of the commands has to be re-encoded every frame.
+    cacheable: bool,
+    widgets: usize,
+    quads: usize,
+}
+
+impl RenderSystemParam<'_, '_> {
+    /// Marks every widget which changed since the last frame, and its ancestors, as dirty.
+    fn mark_dirty_widgets(&mut self) {
+        let RenderSystemParam {
+            render_cache,
+            changed_widgets,
+            removed_renders,
+            removed_svg_colors,
+            removed_children,
+            parents,
+            ..
+        } = self;
+        let parent = |entity| parents.get(entity).ok().map(ChildOf::parent);
+
+        for entity in removed_renders.read().chain(removed_svg_colors.read()) {
+            render_cache.remove(entity);
+            render_cache.mark_dirty(entity, parent);
+        }
+        for entity in removed_children.read() {
+            render_cache.mark_dirty(entity, parent);
+        }
+        for entity in changed_widgets.iter() {
+            render_cache.mark_dirty(entity, parent);
+        }
+    }
+
+    /// Keeps exports of roots which weren't rendered this frame around until they are, and drops
+    /// exports of entities which aren't UI roots.
+    fn retain_pending_svg_exports(&mut self) {
+        let main_root = self.context.get_root_widget();
+        let RenderSystemParam {
+            pending_svg_exports,
+            query,
+            texture_query,
+            ..
+        } = self;
+        pending_svg_exports.retain(|export| {
+            let root = export.root.unwrap_or(main_root);
+            if (root == main_root && query.contains(root)) || texture_query.contains(root) {
+                return true;
+            }
+            error!(
+                "Woodpecker UI: Can't export {} to svg, {root} is not a UI root.",
+                export.path.display()
+            );
+            false
+        });
+    }
+
+    /// Encodes the main root into the screen space vello canvas of the main view camera.
+    fn render_canvas_root(&mut self, root_node: Entity) {
+        let Some((_, camera, _)) = main_view_camera(
+            self.camera_query
+                .iter()
+                .map(|(entity, camera)| (entity, camera, ())),
+            &self.view_roots,
+        ) else {
+            error!("Woodpecker UI: No active UI camera found.");
+            return;
+        };
+
+        let camera_scale = Vec2::new(
+            camera.target_scaling_factor().unwrap_or(1.0),
+            camera.target_scaling_factor().unwrap_or(1.0),
+        );
+
+        let camera_size = camera
+            .physical_target_size()
+            .unwrap_or(UVec2::ZERO)
+            .as_vec2();
+
+        let Ok(mut vello_scene) = self.vello_query.single_mut() else {
+            error!("Woodpecker UI: No vello scene spawned!");
+            return;
+        };
+        let mut scene = std::mem::take(&mut *vello_scene);
+
+        self.render_root(root_node, &mut scene, camera_scale, camera_size);
+
+        if let Ok(mut vello_scene) = self.vello_query.single_mut() {
+            *vello_scene = scene;
+        }
+    }
+
+    /// Encodes the widget tree starting at `root_node` into the given scene.
+    ///
+    /// If nothing in the tree changed since the last frame the scene is left as is.
+    fn render_root(
+        &mut self,
+        root_node: Entity,
+        vello_scene: &mut VelloScene,
+        camera_scale: Vec2,
+        camera_size: Vec2,
+    ) {
+        // The draw operations are only kept around if something asked for them.
+        let main_root = self.context.get_root_widget();
+        let (exports, pending) = std::mem::take(&mut *self.pending_svg_exports)
+            .into_iter()
+            .partition::<Vec<_>, _>(|export| export.root.unwrap_or(main_root) == root_node);
+        *self.pending_svg_exports = pending;
+        let export_paths = exports
+            .into_iter()
+            .map(|export| export.path)
+            .collect::<Vec<_>>();
+        let wants_display_list = self.display_lists.contains(root_node);
+        let collect_ops = wants_display_list || !export_paths.is_empty();
+
+        let root_key = SubtreeKey {
+            parent_layout: WidgetLayout::default(),
+            parent_z: 0,
+            camera_scale,
+            camera_size,
+        };
+        if self
+            .render_cache
+            .subtree(root_node, &root_key, collect_ops)
+            .is_some()
+        {
+            let RenderSystemParam {
+                render_cache,
+                metrics,
+                svg_assets,
+                ..
+            } = self;
+            let Some(root) = render_cache.use_subtree(root_node) else {
+                return;
+            };
+            metrics.increase_render_cache_hits(root.widgets);
+            metrics.increase_quad_counts_by(root.quads);
+            if let Some(ops) = &root.ops {
+                export_svgs(&export_paths, ops, svg_assets, camera_size);
+            }
+            return;
+        }
+
+        let mut render_commands = vec![];
+        // After layout computations update layouts and render scene.
+        // Needs to be done in the correct order..
+        // We also need to know if we are going back up the tree so we can pop the clipping and opacity layers.
+        let mut walker =
+            RenderTreeWalker::new(self, root_node, camera_scale, camera_size, collect_ops);
+        walker.walk(root_node, 0, &mut render_commands, true);
+        if walker.relative_z {
+            // Relative z indices can move widgets in between the widgets of a cached subtree.
+            walker = RenderTreeWalker::new(self, root_node, camera_scale, camera_size, collect_ops);
+            walker.use_subtrees = false;
+            render_commands.clear();
+            walker.walk(root_node, 0, &mut render_commands, true);
+        }
+        let span = walker.order;
+
+        // Once tree is traversed we sort the commands
+        sort_render_commands(&mut render_commands);
+
+        // DEBUG OUTPUT
+        // for command in render_commands.iter() {
+        //     info!("{}-z:{}-order:{}", command.widget_render.to_string(), command.z, command.order);
+        // }
+
+        vello_scene.reset();
+        let mut display_ops = collect_ops.then(Vec::new);
+        let encoded = self.encode_commands(
+            render_commands,
+            vello_scene,
+            display_ops.as_mut(),
+            camera_scale,
+            camera_size,
+        );
+
+        let display_ops = display_ops.unwrap_or_default();
+        export_svgs(&export_paths, &display_ops, &self.svg_assets, camera_size);
+
+        if encoded.cacheable {
+            self.render_cache.insert_subtree(
+                root_node,
+                CachedSubtree {
+                    key: root_key,
+                    span,
+                    scene: vello_scene.clone(),
+                    ops: collect_ops.then(|| display_ops.clone()),
+                    widgets: encoded.widgets,
+                    quads: encoded.quads,
+                },
+            );
+        }
+
+        if let Ok(mut display_list) = self.display_lists.get_mut(root_node) {
+            display_list.ops = display_ops;
+            display_list.size = camera_size;
+        }
+    }
+
+    /// Encodes sorted render commands into the scene. Widgets and subtrees with a valid cached
+    /// fragment are appended as is, everything else is re-encoded and cached for the next frame.
+    fn encode_commands(
+        &mut self,
+        render_commands: Vec<RenderCommand>,
+        vello_scene: &mut VelloScene,
+        mut display_ops: Option<&mut Vec<DrawOp>>,
+        camera_scale: Vec2,
+        camera_size: Vec2,
+    ) -> EncodedCommands {
+        let mut encoded = EncodedCommands {
+            cacheable: true,
+            ..Default::default()
+        };
+        for command in render_commands {
+            // dbg!((command.widget_render.to_string(), command.z, command.order));
+            if let Some(subtree) = command.subtree {
+                let subtree = self.encode_subtree(
+                    subtree,
+                    vello_scene,
+                    display_ops.as_deref_mut(),
+                    camera_scale,
+                    camera_size,
+                );
+                encoded.cacheable &= subtree.cacheable;
+                encoded.widgets += subtree.widgets;
+                encoded.quads += subtree.quads;
+                continue;
+            }
+
+            let RenderSystemParam {
+                default_font,
+                font_manager,
+                svg_manager,
+                lottie_manager,
+                image_manager,
+                render_targets,
+                render_cache,
+                image_assets,
+                svg_assets,
+                lottie_assets,
+                atlas_layouts,
+                metrics,
+                text_selections,
+                svg_colors,
+                ..
+            } = self;
+
+            let cacheable = command.widget_render.is_cacheable(
+                image_assets,
+                svg_assets,
+                lottie_assets,
+                atlas_layouts,
+            );
+            // Layers are balanced inside of every subtree so they don't stop it from being cached.
+            encoded.cacheable &= cacheable
+                || matches!(
+                    command.widget_render,
+                    WidgetRender::Layer | WidgetRender::PopLayer
+                );
+            encoded.widgets += command.entity.is_some() as usize;
+            if matches!(command.widget_render, WidgetRender::Quad) {
+                encoded.quads += 1;
+            }
+
+            let entity = command.entity.filter(|_| cacheable);
+            let selection = command
+                .entity
+                .and_then(|entity| text_selections.get(entity).ok());
+            let svg_colors = command
+                .entity
+                .and_then(|entity| svg_colors.get(entity).ok());
+            let render_changed = command.render_changed
+                || selection
+                    .as_ref()
+                    .is_some_and(|(_, selection)| selection.is_changed())
+                || svg_colors
+                    .as_ref()
+                    .is_some_and(|colors| colors.is_changed());
+            let element_colors = svg_colors
+                .as_ref()
+                .map(|colors| colors.0.as_slice())
+                .unwrap_or_default();
+            let selection = selection
+                .filter(|(_, selection)| !selection.is_empty())
+                .map(|(selectable, selection)| (selection.range(), selectable.selection_color));
+            let key = FragmentKey {
+                layout: command.layout,
+                parent_layout: command.parent_layout,
+                styles: command.styles,
+                camera_scale,
+                camera_size,
+            };
+
+            if let Some(fragment) =
+                entity.and_then(|entity| render_cache.get(entity, &key, render_changed))
+            {
+                vello_scene.append(fragment, None);
+                metrics.increase_render_cache_hits(1);
+                if let Some(display_ops) = display_ops.as_deref_mut() {
+                    command.widget_render.draw_ops(
+                        display_ops,
+                        &command.layout,
+                        &command.parent_layout,
+                        default_font,
+                        image_assets,
+                        svg_assets,
+                        lottie_assets,
+                        atlas_layouts,
+                        font_manager,
+                        image_manager,
+                        render_targets,
+                        metrics,
+                        &command.styles,
+                        selection.clone(),
+                        element_colors,
+                        camera_scale,
+                        camera_size,
+                    );
+                } else if matches!(command.widget_render, WidgetRender::Quad) {
+                    metrics.increase_quad_counts();
+                }
+                continue;
+            }
+
+            let mut ops = vec![];
+            command.widget_render.draw_ops(
+                &mut ops,
+                &command.layout,
+                &command.parent_layout,
+                default_font,
+                image_assets,
+                svg_assets,
+                lottie_assets,
+                atlas_layouts,
+                font_manager,
+                image_manager,
+                render_targets,
+                metrics,
+                &command.styles,
+                selection,
+                element_colors,
+                camera_scale,
+                camera_size,
+            );
+
+            if let Some(entity) = entity {
+                let mut fragment = VelloScene::new();
+                VelloBackend::new(
+                    &mut fragment,
+                    svg_manager,
+                    svg_assets,
+                    lottie_manager,
+                    lottie_assets,
+                )
+                .draw_all(&ops);
+                metrics.increase_render_cache_misses();
+                let fragment = render_cache.insert(entity, key, fragment);
+                vello_scene.append(fragment, None);
+            } else {
+                VelloBackend::new(
+                    vello_scene,
+                    svg_manager,
+                    svg_assets,
+                    lottie_manager,
+                    lottie_assets,
+                )
+                .draw_all(&ops);
+            }
+
+            if let Some(display_ops) = display_ops.as_deref_mut() {
+                display_ops.append(&mut ops);
+            }
+        }
+        encoded
+    }
 
-    // DEBUG OUTPUT
-    // for command in render_commands.iter() {
-    //     let name = match command.widget_render {
-    //         WidgetRender::Quad => "Quad",
-    //         WidgetRender::Text { .. } => "Text",
-    //         WidgetRender::RichText { .. } => "RichText",
-    //         WidgetRender::Custom { .. } => "Custom",
-    //         WidgetRender::Layer => "Layer",
-    //         WidgetRender::PopLayer => "PopLayer",
-    //         WidgetRender::Image { .. } => "Image",
-    //         WidgetRender::RenderTarget { .. } => "RenderTarget",
-    //         WidgetRender::NinePatch { .. } => "NinePatch",
-    //         WidgetRender::Svg { .. } => "Svg",
-    //     };
-    //     info!("{}-z:{}-order:{}", name, command.z, command.order);
-    // }
-
-    // Now we can render with vello
-    for command in render_commands {
-        // dbg!((command.widget_render.to_string(), command.z, command.order));
-        command.widget_render.render(
-            &mut vello_scene,
-            &command.layout,
-            &command.parent_layout,
-            &default_font,
-            &font_assets,
-            &mut image_assets,
-            &svg_assets,
-            &mut font_manager,
-            &mut svg_manager,
-            &mut image_manager,
-            &mut render_targets,
-            &mut metrics,
-            &command.styles,
+    /// Appends a cached subtree, or encodes a clean subtree into a new cached fragment.
+    fn encode_subtree(
+        &mut self,
+        subtree: SubtreeCommand,
+        vello_scene: &mut VelloScene,
+        display_ops: Option<&mut Vec<DrawOp>>,
+        camera_scale: Vec2,
+        camera_size: Vec2,
+    ) -> EncodedCommands {
+        let Some(mut render_commands) = subtree.commands else {
+            let RenderSystemParam {
+                render_cache,
+                metrics,
+                ..
+            } = self;
+            let Some(cached) = render_cache.use_subtree(subtree.entity) else {
+                return EncodedCommands::default();
+            };
+            vello_scene.append(&cached.scene, None);
+            if let (Some(display_ops), Some(ops)) = (display_ops, &cached.ops) {
+                display_ops.extend(ops.iter().cloned());
+            }
+            metrics.increase_render_cache_hits(cached.widgets);
+            metrics.increase_quad_counts_by(cached.quads);
+            return EncodedCommands {
+                cacheable: true,
+                widgets: cached.widgets,
+                quads: cached.quads,
+            };
+        };
+
+        sort_render_commands(&mut render_commands);
+        let mut scene = VelloScene::new();
+        let mut ops = display_ops.is_some().then(Vec::new);
+        let encoded = self.encode_commands(
+            render_commands,
+            &mut scene,
+            ops.as_mut(),
             camera_scale,
             camera_size,
         );
+        vello_scene.append(&scene, None);
+        if let (Some(display_ops), Some(ops)) = (display_ops, &ops) {
+            display_ops.extend(ops.iter().cloned());
+        }
+        if encoded.cacheable {
+            self.render_cache.insert_subtree(
+                subtree.entity,
+                CachedSubtree {
+                    key: subtree.key,
+                    span: subtree.span,
+                    scene,
+                    ops,
+                    widgets: encoded.widgets,
+                    quads: encoded.quads,
+                },
+            );
+        }
+        encoded
+    }
+}
+
+/// Writes the draw operations of a root to every requested svg file.
+fn export_svgs(
+    export_paths: &[std::path::PathBuf],
+    display_ops: &[DrawOp],
+    svg_assets: &Assets<SvgAsset>,
+    camera_size: Vec2,
+) {
+    for path in export_paths {
+        let mut backend = SvgBackend::new(svg_assets);
+        backend.draw_all(display_ops);
+        if let Err(err) = std::fs::write(path, backend.finish(camera_size)) {
+            error!(
+                "Woodpecker UI: Failed to export svg to {}: {err}",
+                path.display()
+            );
+        }
     }
+}
 
-    metrics.commit_quad_frame();
+fn sort_render_commands(render_commands: &mut [RenderCommand]) {
+    render_commands.sort_unstable_by(|a, b| a.z.cmp(&b.z).then_with(|| a.order.cmp(&b.order)));
 }
 
-fn traverse_render_tree(
+/// Walks a widget tree and collects its render commands.
+///
+/// Clean subtrees are collected as a single command which appends their cached fragment, or
+/// encodes them into a new fragment if they weren't cached yet.
+struct RenderTreeWalker<'a, 'w, 's> {
     root_node: Entity,
-    parent_id: u32,
-    order_counter: &mut u32,
-    render_commands: &mut Vec<RenderCommand>,
-    query: &mut Query<
+    query: &'a Query<
+        'w,
+        's,
         (
             Entity,
-            One<&dyn Widget>,
-            &WoodpeckerStyle,
-            Option<&ChildOf>,
-            Option<&Children>,
+            One<&'static dyn Widget>,
+            &'static WoodpeckerStyle,
+            Option<&'static ChildOf>,
+            Option<&'static Children>,
         ),
         (Without<StateMarker>, Without<PreviousWidget>),
     >,
-    default_font: &DefaultFont,
-    font_manager: &mut FontManager,
-    svg_manager: &mut SvgManager,
-    image_manager: &mut ImageManager,
-    render_targets: &mut RenderTargetImages,
-    metrics: &mut WidgetMetrics,
-    widget_render: &Query<&WidgetRender>,
-    vello_scene: &mut VelloScene,
-    font_assets: &Assets<VelloFont>,
-    image_assets: &mut Assets<Image>,
-    svg_assets: &Assets<SvgAsset>,
-    layout_query: &Query<&WidgetLayout>,
-    current_node: Entity,
-    should_render: bool,
+    layout_query: &'a Query<'w, 's, &'static WidgetLayout>,
+    widget_render: &'a Query<'w, 's, Ref<'static, WidgetRender>>,
+    render_cache: &'a RenderCache,
     camera_scale: Vec2,
     camera_size: Vec2,
-) {
-    let Ok((entity, _, styles, parent, children)) = query.get_mut(current_node) else {
-        return;
-    };
-
-    let Ok(layout) = layout_query.get(entity) else {
-        return;
-    };
-
-    if matches!(styles.display, WidgetDisplay::None)
-        || matches!(styles.visibility, WidgetVisibility::Hidden)
-    {
-        return;
+    /// Cached subtrees must have draw operations if they are collected.
+    collect_ops: bool,
+    use_subtrees: bool,
+    building_subtree: bool,
+    order: u32,
+    /// Set when a widget with a relative z index was found.
+    relative_z: bool,
+    /// Set when a widget below the subtree being built has a global z index.
+    global_z: bool,
+}
+
+impl<'a, 'w, 's> RenderTreeWalker<'a, 'w, 's> {
+    fn new(
+        params: &'a RenderSystemParam<'w, 's>,
+        root_node: Entity,
+        camera_scale: Vec2,
+        camera_size: Vec2,
+        collect_ops: bool,
+    ) -> Self {
+        Self {
+            root_node,
+            query: &params.query,
+            layout_query: &params.layout_query,
+            widget_render: &params.widget_render,
+            render_cache: &params.render_cache,
+            camera_scale,
+            camera_size,
+            collect_ops,
+            use_subtrees: true,
+            building_subtree: false,
+            order: 0,
+            relative_z: false,
+            global_z: false,
+        }
     }
 
-    let z_index = styles.z_index;
-    let z = z_index
-        .map(|z| z.get_global())
-        .flatten()
-        .unwrap_or(parent_id);
-    let mut order = (*order_counter as i32
-        + styles
-            .z_index
-            .map(|z| z.get_relative())
+    fn walk(
+        &mut self,
+        current_node: Entity,
+        parent_id: u32,
+        render_commands: &mut Vec<RenderCommand>,
+        is_top: bool,
+    ) {
+        let Ok((entity, _, styles, parent, children)) = self.query.get(current_node) else {
+            return;
+        };
+
+        let Ok(layout) = self.layout_query.get(entity) else {
+            return;
+        };
+
+        if matches!(styles.display, WidgetDisplay::None)
+            || matches!(styles.visibility, WidgetVisibility::Hidden)
+        {
+            return;
+        }
+
+        let z_index = styles.z_index;
+        let z = z_index
+            .map(|z| z.get_global())
             .flatten()
-            .unwrap_or(0)) as u32;
-    *order_counter += 1;
-
-    let mut did_layer = false;
-    if let Ok(widget_render) = widget_render.get(entity) {
-        let parent_layout = parent.map(|parent| *layout_query.get(parent.parent()).unwrap());
-        if (parent_layout.is_some() || root_node == entity) && should_render {
-            if matches!(widget_render, WidgetRender::Layer) {
-                did_layer = true;
-            }
+            .unwrap_or(parent_id);
+        let parent_layout = parent
+            .and_then(|parent| self.layout_query.get(parent.parent()).ok())
+            .copied();
+
+        if self.use_subtrees
+            && !is_top
+            && self.try_subtree(entity, z, parent_id, parent_layout, render_commands)
+        {
+            return;
+        }
+
+        match z_index {
+            Some(WidgetZ::Relative(relative)) if relative != 0 => self.relative_z = true,
+            Some(WidgetZ::Global(_)) if !is_top => self.global_z = true,
+            _ => {}
+        }
+
+        let mut order = (self.order as i32
+            + styles
+                .z_index
+                .map(|z| z.get_relative())
+                .flatten()
+                .unwrap_or(0)) as u32;
+        self.order += 1;
+
+        let mut did_layer = false;
+        if let Ok(widget_render) = self.widget_render.get(entity) {
+            if parent_layout.is_some() || self.root_node == entity {
+                if matches!(*widget_render, WidgetRender::Layer) {
+                    did_layer = true;
+                }
+
+                if styles.opacity > 0.0 && styles.opacity < 1.0 && !did_layer {
+                    did_layer = true;
+                    render_commands.push(RenderCommand {
+                        z,
+                        order,
+                        widget_render: WidgetRender::Layer,
+                        layout: WidgetLayout(ReflectedLayout {
+                            location: Vec2::splat(0.0),
+                            size: Vec2::splat(10000.0),
+                            ..Default::default()
+                        }),
+                        styles: *styles,
+                        ..Default::default()
+                    });
+
+                    order = self.order;
+                    self.order += 1;
+                }
 
-            if styles.opacity > 0.0 && styles.opacity < 1.0 && !did_layer {
-                did_layer = true;
                 render_commands.push(RenderCommand {
                     z,
                     order,
-                    widget_render: WidgetRender::Layer,
-                    layout: WidgetLayout(ReflectedLayout {
-                        location: Vec2::splat(0.0),
-                        size: Vec2::splat(10000.0),
-                        ..Default::default()
-                    }),
+                    entity: Some(entity),
+                    render_changed: widget_render.is_changed(),
+                    layout: *layout,
+                    parent_layout: parent_layout.unwrap_or_default(),
+                    widget_render: (*widget_render).clone(),
                     styles: *styles,
                     ..Default::default()
                 });
-
-                order = *order_counter;
-                *order_counter += 1;
             }
+        }
+
+        for child in children.iter().flat_map(|children| children.iter()) {
+            self.walk(child, z, render_commands, false);
+        }
 
+        if did_layer {
+            let order = self.order;
+            self.order += 1;
             render_commands.push(RenderCommand {
                 z,
                 order,
-                layout: *layout,
-                parent_layout: parent_layout.unwrap_or_default(),
-                widget_render: widget_render.clone(),
-                styles: *styles,
+                widget_render: WidgetRender::PopLayer,
+                ..Default::default()
             });
         }
     }
 
-    let Some(children) = children.map(|c| c.iter().collect::<Vec<_>>()) else {
-        if did_layer {
-            let order = *order_counter;
-            *order_counter += 1;
-            // vello_scene.pop_layer();
+    /// Collects a clean subtree as a single command. Returns false if the subtree has to be
+    /// walked as usual.
+    fn try_subtree(
+        &mut self,
+        entity: Entity,
+        z: u32,
+        parent_id: u32,
+        parent_layout: Option<WidgetLayout>,
+        render_commands: &mut Vec<RenderCommand>,
+    ) -> bool {
+        let key = SubtreeKey {
+            parent_layout: parent_layout.unwrap_or_default(),
+            parent_z: parent_id,
+            camera_scale: self.camera_scale,
+            camera_size: self.camera_size,
+        };
+        if let Some(cached) = self.render_cache.subtree(entity, &key, self.collect_ops) {
             render_commands.push(RenderCommand {
                 z,
-                order,
-                widget_render: WidgetRender::PopLayer,
+                order: self.order,
+                subtree: Some(SubtreeCommand {
+                    entity,
+                    key,
+                    span: cached.span,
+                    commands: None,
+                }),
                 ..Default::default()
             });
+            self.order += cached.span;
+            return true;
         }
-        return;
-    };
 
-    for child in children.iter() {
-        traverse_render_tree(
-            root_node,
-            z,
-            order_counter,
-            render_commands,
-            query,
-            default_font,
-            font_manager,
-            svg_manager,
-            image_manager,
-            render_targets,
-            metrics,
-            widget_render,
-            vello_scene,
-            font_assets,
-            image_assets,
-            svg_assets,
-            layout_query,
-            *child,
-            should_render,
-            camera_scale,
-            camera_size,
-        );
-    }
+        // Only the outermost clean subtrees get their own fragment.
+        if self.building_subtree || self.render_cache.is_dirty(entity) {
+            return false;
+        }
 
-    if did_layer {
-        // vello_scene.pop_layer();
-        let order = *order_counter;
-        *order_counter += 1;
-        render_commands.push(RenderCommand {
-            z,
-            order,
-            widget_render: WidgetRender::PopLayer,
-            ..Default::default()
-        });
+        let start = self.order;
+        let relative_z = std::mem::take(&mut self.relative_z);
+        let global_z = std::mem::take(&mut self.global_z);
+        self.building_subtree = true;
+        let mut commands = vec![];
+        self.walk(entity, parent_id, &mut commands, true);
+        self.building_subtree = false;
+
+        // Widgets with a z index are sorted in between other widgets so their subtree can't be
+        // drawn as a single fragment.
+        let contained = !self.relative_z && !self.global_z;
+        self.relative_z |= relative_z;
+        self.global_z = global_z;
+        if contained {
+            render_commands.push(RenderCommand {
+                z,
+                order: start,
+                subtree: Some(SubtreeCommand {
+                    entity,
+                    key,
+                    span: self.order - start,
+                    commands: Some(commands),
+                }),
+                ..Default::default()
+            });
+        } else {
+            render_commands.append(&mut commands);
+        }
+        true
     }
 }
 
-struct RenderCommand {
+/// A widget subtree which is drawn from a single cached fragment.
+pub(crate) struct SubtreeCommand {
+    entity: Entity,
+    key: SubtreeKey,
+    /// How many draw orders the subtree takes up.
+    span: u32,
+    /// The commands of a subtree which still has to be encoded, none if it is cached.
+    commands: Option<Vec<RenderCommand>>,
+}
+
+pub(crate) struct RenderCommand {
     z: u32,
     order: u32,
-    layout: WidgetLayout,
-    parent_layout: WidgetLayout,
-    widget_render: WidgetRender,
-    styles: WoodpeckerStyle,
+    /// The widget entity this command was generated from, if any.
+    entity: Option<Entity>,
+    /// Indicates the widget's [`WidgetRender`] was modified since the last frame.
+    render_changed: bool,
+    /// Set if this command draws a whole subtree instead of a single widget.
+    subtree: Option<SubtreeCommand>,
+    pub(crate) layout: WidgetLayout,
+    pub(crate) parent_layout: WidgetLayout,
+    pub(crate) widget_render: WidgetRender,
+    pub(crate) styles: WoodpeckerStyle,
 }
 
 impl Default for RenderCommand {
@@ -325,6 +867,9 @@ impl Default for RenderCommand {
         Self {
             z: 0,
             order: 0,
+            entity: None,
+            render_changed: false,
+            subtree: None,
             layout: Default::default(),
             parent_layout: Default::default(),
             widget_render: Default::default(),
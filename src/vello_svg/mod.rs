@@ -14,15 +14,13 @@
 //! # Unsupported features
 //!
 //! Missing features include:
-//! - text
-//! - group opacity
-//! - mix-blend-modes
-//! - clipping
-//! - masking
-//! - filter effects
-//! - group background
+//! - text, unless fonts are added to the [`usvg::Options`] the tree is parsed with
+//! - filter effects, filtered groups are drawn without their filters
 //! - path shape-rendering
-//! - patterns
+//! - images inside of luminance masks, which are drawn as alpha masks
+//!
+//! Luminance masks are drawn as alpha masks with the luminance of each paint as its alpha, which
+//! differs from the SVG spec where semi-transparent parts of a mask overlap.
 
 mod render;
 
@@ -43,7 +41,7 @@ pub(crate) fn render(
     let opt = usvg::Options::default();
     let tree = usvg::Tree::from_str(svg, &opt)?;
     let mut scene = bevy_vello::vello::Scene::new();
-    append_tree(&mut scene, &tree, path_color);
+    append_tree(&mut scene, &tree, path_color, &|_| None);
     Ok(scene)
 }
 
@@ -53,7 +51,7 @@ pub(crate) fn render(
 pub(crate) fn append(scene: &mut bevy_vello::vello::Scene, svg: &str) -> Result<(), Error> {
     let opt = usvg::Options::default();
     let tree = usvg::Tree::from_str(svg, &opt)?;
-    append_tree(scene, &tree, None);
+    append_tree(scene, &tree, None, &|_| None);
     Ok(())
 }
 
@@ -67,16 +65,22 @@ pub(crate) fn append_with<F: FnMut(&mut bevy_vello::vello::Scene, &usvg::Node)>(
 ) -> Result<(), Error> {
     let opt = usvg::Options::default();
     let tree = usvg::Tree::from_str(svg, &opt)?;
-    append_tree_with(scene, &tree, None, error_handler);
+    append_tree_with(scene, &tree, None, &|_| None, error_handler);
     Ok(())
 }
 
 /// Render a [`Scene`] from a [`usvg::Tree`], with default error handling.
 ///
+/// `element_color` overrides `path_color` for an element, by id, and everything inside of it.
+///
 /// This will draw a red box over (some) unsupported elements.
-pub(crate) fn render_tree(svg: &usvg::Tree, path_color: Option<Color>) -> bevy_vello::vello::Scene {
+pub(crate) fn render_tree(
+    svg: &usvg::Tree,
+    path_color: Option<Color>,
+    element_color: &dyn Fn(&str) -> Option<Color>,
+) -> bevy_vello::vello::Scene {
     let mut scene = bevy_vello::vello::Scene::new();
-    append_tree(&mut scene, svg, path_color);
+    append_tree(&mut scene, svg, path_color, element_color);
     scene
 }
 
@@ -87,8 +91,15 @@ pub(crate) fn append_tree(
     scene: &mut bevy_vello::vello::Scene,
     svg: &usvg::Tree,
     color: Option<Color>,
+    element_color: &dyn Fn(&str) -> Option<Color>,
 ) {
-    append_tree_with(scene, svg, color, &mut util::default_error_handler);
+    append_tree_with(
+        scene,
+        svg,
+        color,
+        element_color,
+        &mut util::default_error_handler,
+    );
 }
 
 /// Append an [`usvg::Tree`] to a vello [`Scene`], with user-provided error handling logic.
@@ -98,7 +109,97 @@ pub(crate) fn append_tree_with<F: FnMut(&mut bevy_vello::vello::Scene, &usvg::No
     scene: &mut bevy_vello::vello::Scene,
     svg: &usvg::Tree,
     color: Option<Color>,
+    element_color: &dyn Fn(&str) -> Option<Color>,
     error_handler: &mut F,
 ) {
-    render::render_group(scene, svg.root(), color, Affine::IDENTITY, error_handler);
+    render::render_group(
+        scene,
+        svg.root(),
+        color,
+        element_color,
+        Affine::IDENTITY,
+        error_handler,
+    );
+}
+
+#[cfg(all(test, feature = "snapshot"))]
+mod tests {
+    use std::path::Path;
+
+    use bevy::math::UVec2;
+
+    use super::*;
+    use crate::snapshot::{assert_snapshot, diff_images, SnapshotRenderer, SnapshotTolerance};
+
+    /// Renders the tree with resvg, the reference renderer of usvg.
+    fn render_reference(tree: &usvg::Tree, size: UVec2) -> image::RgbaImage {
+        let mut pixmap = resvg::tiny_skia::Pixmap::new(size.x, size.y).unwrap();
+        resvg::render(tree, Default::default(), &mut pixmap.as_mut());
+        image::RgbaImage::from_fn(size.x, size.y, |x, y| {
+            let pixel = pixmap.pixel(x, y).unwrap().demultiply();
+            image::Rgba([pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()])
+        })
+    }
+
+    /// Renders every svg in `tests/svg` through vello's CPU path and compares it against its
+    /// golden image in `tests/svg/snapshots`.
+    ///
+    /// The goldens are only as good as the renderer that wrote them, so every render is also
+    /// compared against resvg. Anti-aliasing and pattern resampling differ slightly between the
+    /// two, so that comparison has a looser tolerance.
+    #[test]
+    fn svg_corpus() {
+        let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/svg");
+        let mut files = std::fs::read_dir(&corpus)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "svg"))
+            .collect::<Vec<_>>();
+        files.sort();
+        assert!(!files.is_empty(), "The svg corpus is empty");
+
+        let mut renderer = SnapshotRenderer::new().unwrap_or_else(|err| panic!("{err}"));
+        let mut failures = Vec::new();
+        for file in files {
+            let svg = std::fs::read_to_string(&file).unwrap();
+            let tree = usvg::Tree::from_str(&svg, &usvg::Options::default()).unwrap();
+            let mut unsupported = 0;
+            let mut scene = bevy_vello::vello::Scene::new();
+            append_tree_with(&mut scene, &tree, None, &|_| None, &mut |_, _| {
+                unsupported += 1
+            });
+            if unsupported > 0 {
+                failures.push(format!(
+                    "{} has {unsupported} unsupported elements",
+                    file.display()
+                ));
+                continue;
+            }
+
+            let size = UVec2::new(
+                tree.size().width().ceil() as u32,
+                tree.size().height().ceil() as u32,
+            );
+            let image = renderer.render_scene(&scene.into(), size).unwrap();
+            // Pattern tiles are resampled differently, so their seams make up most of the
+            // differences.
+            let (_, mismatched) = diff_images(&render_reference(&tree, size), &image, 40);
+            let mismatched = mismatched as f32 / (size.x * size.y) as f32;
+            if mismatched > 0.06 {
+                failures.push(format!(
+                    "{} differs from resvg in {:.1}% of pixels",
+                    file.display(),
+                    mismatched * 100.0
+                ));
+            }
+
+            let golden = corpus
+                .join("snapshots")
+                .join(file.with_extension("png").file_name().unwrap());
+            if let Err(error) = assert_snapshot(&image, golden, SnapshotTolerance::default()) {
+                failures.push(error.to_string());
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
 }
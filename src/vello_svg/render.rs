@@ -2,102 +2,71 @@
 // SPDX-License-Identifier: Apache-2.0 OR MIT
 
 use super::util;
-use bevy_vello::vello::kurbo::Affine;
-use bevy_vello::vello::peniko::{BlendMode, Color, Fill};
+use bevy_vello::vello::kurbo::{Affine, Rect, Shape};
+use bevy_vello::vello::peniko::{BlendMode, Color, Compose, Fill, Mix};
 use bevy_vello::vello::Scene;
 
+/// The most tiles a pattern is drawn with, larger patterns are passed to the error handler.
+const MAX_PATTERN_TILES: f64 = 4096.0;
+
+/// How paints are drawn.
+#[derive(Clone, Copy, PartialEq)]
+enum PaintMode {
+    /// Paints are drawn as they are.
+    Normal,
+    /// Fills are opaque and everything else is skipped, used to draw clip paths.
+    Clip,
+    /// Paints are drawn with their luminance as alpha, used to draw luminance masks.
+    Luminance,
+}
+
+struct Context<'a, F> {
+    element_color: &'a dyn Fn(&str) -> Option<Color>,
+    error_handler: &'a mut F,
+    mode: PaintMode,
+}
+
+impl<F> Context<'_, F> {
+    /// A context for drawing the content of a clip path or mask, where colors can't be replaced.
+    fn with_mode(&mut self, mode: PaintMode) -> Context<'_, F> {
+        Context {
+            element_color: &|_| None,
+            error_handler: &mut *self.error_handler,
+            mode,
+        }
+    }
+}
+
 pub(crate) fn render_group<F: FnMut(&mut Scene, &usvg::Node)>(
     scene: &mut Scene,
     group: &usvg::Group,
     path_color: Option<Color>,
+    element_color: &dyn Fn(&str) -> Option<Color>,
     transform: Affine,
     error_handler: &mut F,
+) {
+    let mut cx = Context {
+        element_color,
+        error_handler,
+        mode: PaintMode::Normal,
+    };
+    render_children(scene, group, path_color, transform, &mut cx);
+}
+
+fn render_children<F: FnMut(&mut Scene, &usvg::Node)>(
+    scene: &mut Scene,
+    group: &usvg::Group,
+    path_color: Option<Color>,
+    transform: Affine,
+    cx: &mut Context<F>,
 ) {
     for node in group.children() {
-        let transform = transform * util::to_affine(&node.abs_transform());
+        let path_color = (cx.element_color)(node.id()).or(path_color);
         match node {
-            usvg::Node::Group(g) => {
-                let mut pushed_clip = false;
-                if let Some(clip_path) = g.clip_path() {
-                    if let Some(usvg::Node::Path(clip_path)) = clip_path.root().children().first() {
-                        // support clip-path with a single path
-                        let local_path = util::to_bez_path(clip_path);
-                        scene.push_layer(
-                            BlendMode {
-                                mix: bevy_vello::vello::peniko::Mix::Clip,
-                                compose: bevy_vello::vello::peniko::Compose::SrcOver,
-                            },
-                            1.0,
-                            transform,
-                            &local_path,
-                        );
-                        pushed_clip = true;
-                    }
-                }
-
-                render_group(scene, g, path_color, Affine::IDENTITY, error_handler);
-
-                if pushed_clip {
-                    scene.pop_layer();
-                }
-            }
-            usvg::Node::Path(path) => {
-                if !path.is_visible() {
-                    continue;
-                }
-                let local_path = util::to_bez_path(path);
-
-                let do_fill = |scene: &mut Scene, error_handler: &mut F| {
-                    if let Some(fill) = &path.fill() {
-                        if let Some((brush, brush_transform)) =
-                            util::to_brush(fill.paint(), fill.opacity(), path_color)
-                        {
-                            scene.fill(
-                                match fill.rule() {
-                                    usvg::FillRule::NonZero => Fill::NonZero,
-                                    usvg::FillRule::EvenOdd => Fill::EvenOdd,
-                                },
-                                transform,
-                                &brush,
-                                Some(brush_transform),
-                                &local_path,
-                            );
-                        } else {
-                            error_handler(scene, node);
-                        }
-                    }
-                };
-                let do_stroke = |scene: &mut Scene, error_handler: &mut F| {
-                    if let Some(stroke) = &path.stroke() {
-                        if let Some((brush, brush_transform)) =
-                            util::to_brush(stroke.paint(), stroke.opacity(), path_color)
-                        {
-                            let conv_stroke = util::to_stroke(stroke);
-                            scene.stroke(
-                                &conv_stroke,
-                                transform,
-                                &brush,
-                                Some(brush_transform),
-                                &local_path,
-                            );
-                        } else {
-                            error_handler(scene, node);
-                        }
-                    }
-                };
-                match path.paint_order() {
-                    usvg::PaintOrder::FillAndStroke => {
-                        do_fill(scene, error_handler);
-                        do_stroke(scene, error_handler);
-                    }
-                    usvg::PaintOrder::StrokeAndFill => {
-                        do_stroke(scene, error_handler);
-                        do_fill(scene, error_handler);
-                    }
-                }
-            }
+            usvg::Node::Group(g) => render_child_group(scene, g, path_color, transform, cx),
+            usvg::Node::Path(path) => render_path(scene, node, path, path_color, transform, cx),
             usvg::Node::Image(img) => {
-                if !img.is_visible() {
+                if !img.is_visible() || cx.mode == PaintMode::Clip {
                     continue;
                 }
                 match img.kind() {
@@ -106,27 +75,309 @@ pub(crate) fn render_group<F: FnMut(&mut Scene, &usvg::Node)>(
                     | usvg::ImageKind::WEBP(_)
                     | usvg::ImageKind::GIF(_) => {
                         let Ok(decoded_image) = util::decode_raw_raster_image(img.kind()) else {
-                            error_handler(scene, node);
+                            (cx.error_handler)(scene, node);
                             continue;
                         };
                         let image = util::into_image(decoded_image);
-                        let image_ts = util::to_affine(&img.abs_transform());
-                        scene.draw_image(&image, image_ts);
+                        let scale = Affine::scale_non_uniform(
+                            img.size().width() as f64 / image.width as f64,
+                            img.size().height() as f64 / image.height as f64,
+                        );
+                        scene.draw_image(&image, transform * scale);
                     }
                     usvg::ImageKind::SVG(svg) => {
-                        render_group(scene, svg.root(), path_color, transform, error_handler);
+                        render_children(scene, svg.root(), path_color, transform, cx);
                     }
                 }
             }
             usvg::Node::Text(text) => {
-                render_group(
-                    scene,
-                    text.flattened(),
-                    path_color,
-                    transform,
-                    error_handler,
-                );
+                render_child_group(scene, text.flattened(), path_color, transform, cx);
             }
         }
     }
 }
+
+/// Draws a group, isolating it in a layer when it has an opacity, blend mode, clip path or mask.
+fn render_child_group<F: FnMut(&mut Scene, &usvg::Node)>(
+    scene: &mut Scene,
+    group: &usvg::Group,
+    path_color: Option<Color>,
+    transform: Affine,
+    cx: &mut Context<F>,
+) {
+    let transform = transform * util::to_affine(&group.transform());
+    // Only the shape of a clip path matters, so opacity, blending and masks are ignored in them.
+    let (opacity, mix, mask) = match cx.mode {
+        PaintMode::Clip => (1.0, Mix::Normal, None),
+        _ => (
+            group.opacity().get(),
+            util::to_mix(group.blend_mode()),
+            group.mask(),
+        ),
+    };
+    // A clip path of a single path clips directly instead of going through an alpha mask.
+    let simple_clip = group.clip_path().and_then(util::simple_clip_path);
+    let needs_layer = opacity < 1.0
+        || mix != Mix::Normal
+        || mask.is_some()
+        || (group.isolate() && cx.mode != PaintMode::Clip)
+        || (group.clip_path().is_some() && simple_clip.is_none());
+
+    let bounds = util::to_rect(&group.layer_bounding_box().to_rect());
+    if needs_layer {
+        scene.push_layer(
+            BlendMode::new(mix, Compose::SrcOver),
+            opacity,
+            transform,
+            &bounds,
+        );
+    }
+    if let Some((clip_transform, clip_path)) = &simple_clip {
+        scene.push_layer(Mix::Clip, 1.0, transform * *clip_transform, clip_path);
+    }
+
+    render_children(scene, group, path_color, transform, cx);
+
+    if simple_clip.is_some() {
+        scene.pop_layer();
+    } else if let Some(clip_path) = group.clip_path() {
+        apply_clip_path(scene, clip_path, transform, &bounds, cx);
+    }
+    if let Some(mask) = mask {
+        apply_mask(scene, mask, transform, &bounds, cx);
+    }
+    if needs_layer {
+        scene.pop_layer();
+    }
+}
+
+/// Keeps the parts of the current layer within `bounds` which are covered by the clip path.
+fn apply_clip_path<F: FnMut(&mut Scene, &usvg::Node)>(
+    scene: &mut Scene,
+    clip_path: &usvg::ClipPath,
+    transform: Affine,
+    bounds: &Rect,
+    cx: &mut Context<F>,
+) {
+    scene.push_layer(
+        BlendMode::new(Mix::Normal, Compose::DestIn),
+        1.0,
+        transform,
+        bounds,
+    );
+    render_children(
+        scene,
+        clip_path.root(),
+        None,
+        transform * util::to_affine(&clip_path.transform()),
+        &mut cx.with_mode(PaintMode::Clip),
+    );
+    scene.pop_layer();
+
+    // A clip path of a clip path intersects both of them.
+    if let Some(clip_path) = clip_path.clip_path() {
+        apply_clip_path(scene, clip_path, transform, bounds, cx);
+    }
+}
+
+/// Multiplies the current layer within `bounds` with the alpha or luminance of the mask.
+fn apply_mask<F: FnMut(&mut Scene, &usvg::Node)>(
+    scene: &mut Scene,
+    mask: &usvg::Mask,
+    transform: Affine,
+    bounds: &Rect,
+    cx: &mut Context<F>,
+) {
+    let mode = match mask.kind() {
+        usvg::MaskType::Luminance => PaintMode::Luminance,
+        usvg::MaskType::Alpha => PaintMode::Normal,
+    };
+    scene.push_layer(
+        BlendMode::new(Mix::Normal, Compose::DestIn),
+        1.0,
+        transform,
+        bounds,
+    );
+    scene.push_layer(
+        Mix::Clip,
+        1.0,
+        transform,
+        &util::to_rect(&mask.rect().to_rect()),
+    );
+    render_children(scene, mask.root(), None, transform, &mut cx.with_mode(mode));
+    scene.pop_layer();
+    scene.pop_layer();
+
+    if let Some(mask) = mask.mask() {
+        apply_mask(scene, mask, transform, bounds, cx);
+    }
+}
+
+fn render_path<F: FnMut(&mut Scene, &usvg::Node)>(
+    scene: &mut Scene,
+    node: &usvg::Node,
+    path: &usvg::Path,
+    path_color: Option<Color>,
+    transform: Affine,
+    cx: &mut Context<F>,
+) {
+    if !path.is_visible() {
+        return;
+    }
+    let local_path = util::to_bez_path(path);
+
+    let do_fill = |scene: &mut Scene, cx: &mut Context<F>| {
+        let Some(fill) = path.fill() else {
+            return;
+        };
+        let rule = match fill.rule() {
+            usvg::FillRule::NonZero => Fill::NonZero,
+            usvg::FillRule::EvenOdd => Fill::EvenOdd,
+        };
+        if cx.mode == PaintMode::Clip {
+            scene.fill(rule, transform, Color::BLACK, None, &local_path);
+            return;
+        }
+        if let (None, usvg::Paint::Pattern(pattern)) = (path_color, fill.paint()) {
+            let cover = |scene: &mut Scene| {
+                scene.fill(rule, transform, Color::BLACK, None, &local_path);
+            };
+            render_pattern(
+                scene,
+                node,
+                path,
+                pattern,
+                fill.opacity(),
+                transform,
+                cover,
+                cx,
+            );
+            return;
+        }
+        if let Some((brush, brush_transform)) =
+            util::to_brush(fill.paint(), fill.opacity(), path_color)
+        {
+            let brush = match cx.mode {
+                PaintMode::Luminance => util::map_brush_colors(brush, util::luminance_to_alpha),
+                _ => brush,
+            };
+            scene.fill(rule, transform, &brush, Some(brush_transform), &local_path);
+        } else {
+            (cx.error_handler)(scene, node);
+        }
+    };
+    let do_stroke = |scene: &mut Scene, cx: &mut Context<F>| {
+        let Some(stroke) = path.stroke() else {
+            return;
+        };
+        if cx.mode == PaintMode::Clip {
+            return;
+        }
+        let conv_stroke = util::to_stroke(stroke);
+        if let (None, usvg::Paint::Pattern(pattern)) = (path_color, stroke.paint()) {
+            let cover = |scene: &mut Scene| {
+                scene.stroke(&conv_stroke, transform, Color::BLACK, None, &local_path);
+            };
+            render_pattern(
+                scene,
+                node,
+                path,
+                pattern,
+                stroke.opacity(),
+                transform,
+                cover,
+                cx,
+            );
+            return;
+        }
+        if let Some((brush, brush_transform)) =
+            util::to_brush(stroke.paint(), stroke.opacity(), path_color)
+        {
+            let brush = match cx.mode {
+                PaintMode::Luminance => util::map_brush_colors(brush, util::luminance_to_alpha),
+                _ => brush,
+            };
+            scene.stroke(
+                &conv_stroke,
+                transform,
+                &brush,
+                Some(brush_transform),
+                &local_path,
+            );
+        } else {
+            (cx.error_handler)(scene, node);
+        }
+    };
+    match path.paint_order() {
+        usvg::PaintOrder::FillAndStroke => {
+            do_fill(scene, cx);
+            do_stroke(scene, cx);
+        }
+        usvg::PaintOrder::StrokeAndFill => {
+            do_stroke(scene, cx);
+            do_fill(scene, cx);
+        }
+    }
+}
+
+/// Paints a path with a pattern by drawing every tile which overlaps the path, and then only
+/// keeping what `cover` paints.
+#[allow(clippy::too_many_arguments)]
+fn render_pattern<F: FnMut(&mut Scene, &usvg::Node)>(
+    scene: &mut Scene,
+    node: &usvg::Node,
+    path: &usvg::Path,
+    pattern: &usvg::Pattern,
+    opacity: usvg::Opacity,
+    transform: Affine,
+    cover: impl FnOnce(&mut Scene),
+    cx: &mut Context<F>,
+) {
+    let pattern_transform = util::to_affine(&pattern.transform());
+    let tile = pattern.rect();
+    let bounds = util::to_rect(&path.stroke_bounding_box());
+    // The tiles covering the bounds of the path, in pattern space.
+    let pattern_bounds = (pattern_transform.inverse() * bounds.to_path(0.1)).bounding_box();
+    let (width, height) = (tile.width() as f64, tile.height() as f64);
+    let columns = ((pattern_bounds.x0 - tile.x() as f64) / width).floor()
+        ..((pattern_bounds.x1 - tile.x() as f64) / width).ceil();
+    let rows = ((pattern_bounds.y0 - tile.y() as f64) / height).floor()
+        ..((pattern_bounds.y1 - tile.y() as f64) / height).ceil();
+    if (columns.end - columns.start) * (rows.end - rows.start) > MAX_PATTERN_TILES {
+        (cx.error_handler)(scene, node);
+        return;
+    }
+
+    scene.push_layer(BlendMode::default(), opacity.get(), transform, &bounds);
+    let mut row = rows.start;
+    while row < rows.end {
+        let mut column = columns.start;
+        while column < columns.end {
+            let tile_transform = transform
+                * pattern_transform
+                * Affine::translate((
+                    tile.x() as f64 + column * width,
+                    tile.y() as f64 + row * height,
+                ));
+            scene.push_layer(
+                Mix::Clip,
+                1.0,
+                tile_transform,
+                &Rect::new(0.0, 0.0, width, height),
+            );
+            render_children(scene, pattern.root(), None, tile_transform, cx);
+            scene.pop_layer();
+            column += 1.0;
+        }
+        row += 1.0;
+    }
+    scene.push_layer(
+        BlendMode::new(Mix::Normal, Compose::DestIn),
+        1.0,
+        transform,
+        &bounds,
+    );
+    cover(scene);
+    scene.pop_layer();
+    scene.pop_layer();
+}
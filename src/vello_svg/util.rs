@@ -3,7 +3,7 @@
 
 use bevy_vello::vello::kurbo::{Affine, BezPath, Point, Rect, Stroke};
 use bevy_vello::vello::peniko::color::DynamicColor;
-use bevy_vello::vello::peniko::{Blob, Brush, Color, Fill, Image};
+use bevy_vello::vello::peniko::{Blob, Brush, Color, Extend, Fill, Image, Mix};
 use bevy_vello::vello::Scene;
 
 pub fn to_affine(ts: &usvg::Transform) -> Affine {
@@ -15,7 +15,84 @@ pub fn to_affine(ts: &usvg::Transform) -> Affine {
         tx,
         ty,
     } = ts;
-    Affine::new([sx, kx, ky, sy, tx, ty].map(|&x| f64::from(x)))
+    // usvg's `kx` skews x by y and `ky` skews y by x.
+    Affine::new([sx, ky, kx, sy, tx, ty].map(|&x| f64::from(x)))
+}
+
+pub fn to_rect(rect: &usvg::Rect) -> Rect {
+    Rect::new(
+        rect.left() as f64,
+        rect.top() as f64,
+        rect.right() as f64,
+        rect.bottom() as f64,
+    )
+}
+
+pub fn to_mix(blend_mode: usvg::BlendMode) -> Mix {
+    match blend_mode {
+        usvg::BlendMode::Normal => Mix::Normal,
+        usvg::BlendMode::Multiply => Mix::Multiply,
+        usvg::BlendMode::Screen => Mix::Screen,
+        usvg::BlendMode::Overlay => Mix::Overlay,
+        usvg::BlendMode::Darken => Mix::Darken,
+        usvg::BlendMode::Lighten => Mix::Lighten,
+        usvg::BlendMode::ColorDodge => Mix::ColorDodge,
+        usvg::BlendMode::ColorBurn => Mix::ColorBurn,
+        usvg::BlendMode::HardLight => Mix::HardLight,
+        usvg::BlendMode::SoftLight => Mix::SoftLight,
+        usvg::BlendMode::Difference => Mix::Difference,
+        usvg::BlendMode::Exclusion => Mix::Exclusion,
+        usvg::BlendMode::Hue => Mix::Hue,
+        usvg::BlendMode::Saturation => Mix::Saturation,
+        usvg::BlendMode::Color => Mix::Color,
+        usvg::BlendMode::Luminosity => Mix::Luminosity,
+    }
+}
+
+pub fn to_extend(spread_method: usvg::SpreadMethod) -> Extend {
+    match spread_method {
+        usvg::SpreadMethod::Pad => Extend::Pad,
+        usvg::SpreadMethod::Reflect => Extend::Reflect,
+        usvg::SpreadMethod::Repeat => Extend::Repeat,
+    }
+}
+
+/// Returns the transform and shape of a clip path made of a single non-zero filled path, which
+/// can be used as the clip of a layer.
+pub fn simple_clip_path(clip_path: &usvg::ClipPath) -> Option<(Affine, BezPath)> {
+    if clip_path.clip_path().is_some() {
+        return None;
+    }
+    let [usvg::Node::Path(path)] = clip_path.root().children() else {
+        return None;
+    };
+    let is_non_zero = path
+        .fill()
+        .is_some_and(|fill| fill.rule() == usvg::FillRule::NonZero);
+    (path.is_visible() && is_non_zero)
+        .then(|| (to_affine(&clip_path.transform()), to_bez_path(path)))
+}
+
+/// Replaces the alpha of a color with its luminance times its alpha, which turns a luminance
+/// mask into an alpha mask.
+pub fn luminance_to_alpha(color: Color) -> Color {
+    let [r, g, b, a] = color.components;
+    let luminance = 0.2125 * r + 0.7154 * g + 0.0721 * b;
+    Color::new([0.0, 0.0, 0.0, luminance * a])
+}
+
+/// Maps the colors of a solid or gradient brush.
+pub fn map_brush_colors(brush: Brush, map: impl Fn(Color) -> Color) -> Brush {
+    match brush {
+        Brush::Solid(color) => Brush::Solid(map(color)),
+        Brush::Gradient(mut gradient) => {
+            for stop in gradient.stops.iter_mut() {
+                stop.color = DynamicColor::from_alpha_color(map(stop.color.to_alpha_color()));
+            }
+            Brush::Gradient(gradient)
+        }
+        brush => brush,
+    }
 }
 
 pub fn to_stroke(stroke: &usvg::Stroke) -> Stroke {
@@ -150,8 +227,9 @@ pub fn to_brush(
             ]
             .map(f64::from);
             let transform = Affine::new(arr);
-            let gradient = bevy_vello::vello::peniko::Gradient::new_linear(start, end)
+            let mut gradient = bevy_vello::vello::peniko::Gradient::new_linear(start, end)
                 .with_stops(stops.as_slice());
+            gradient.extend = to_extend(gr.spread_method());
             Some((Brush::Gradient(gradient), transform))
         }
         usvg::Paint::RadialGradient(gr) => {
@@ -171,8 +249,9 @@ pub fn to_brush(
                 })
                 .collect();
 
-            let start_center = Point::new(gr.cx() as f64, gr.cy() as f64);
-            let end_center = Point::new(gr.fx() as f64, gr.fy() as f64);
+            // The gradient starts at the focal point and ends at the circle.
+            let start_center = Point::new(gr.fx() as f64, gr.fy() as f64);
+            let end_center = Point::new(gr.cx() as f64, gr.cy() as f64);
             let start_radius = 0_f32;
             let end_radius = gr.r().get();
             let arr = [
@@ -185,13 +264,14 @@ pub fn to_brush(
             ]
             .map(f64::from);
             let transform = Affine::new(arr);
-            let gradient = bevy_vello::vello::peniko::Gradient::new_two_point_radial(
+            let mut gradient = bevy_vello::vello::peniko::Gradient::new_two_point_radial(
                 start_center,
                 start_radius,
                 end_center,
                 end_radius,
             )
             .with_stops(stops.as_slice());
+            gradient.extend = to_extend(gr.spread_method());
             Some((Brush::Gradient(gradient), transform))
         }
         usvg::Paint::Pattern(_) => None,
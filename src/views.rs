@@ -0,0 +1,116 @@
+use bevy::{prelude::*, render::render_resource::Extent3d};
+
+use crate::render_to_texture::WoodpeckerTextureTarget;
+
+/// Draws the UI root it is attached to in the viewport of another [`crate::WoodpeckerView`] camera.
+///
+/// The root set with [`crate::context::WoodpeckerContext::set_root_widget`] is drawn by the first
+/// active view camera that no [`WoodpeckerViewRoot`] points at. Every other root is rendered into a
+/// texture the size of its camera's viewport, at the camera's scale factor, and shown on top of
+/// that camera. This is how secondary windows and split screen cameras get their own UI.
+///
+/// Cameras used this way should not see [`crate::RenderSettings::layer`], otherwise they will
+/// also draw the main UI canvas.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct WoodpeckerViewRoot {
+    /// The camera the UI root is drawn with.
+    pub camera: Entity,
+}
+
+impl WoodpeckerViewRoot {
+    /// Creates a new view root for the given camera.
+    pub fn new(camera: Entity) -> Self {
+        Self { camera }
+    }
+}
+
+/// The UI node displaying the texture of a [`WoodpeckerViewRoot`].
+#[derive(Component)]
+pub(crate) struct ViewRootDisplay {
+    root: Entity,
+}
+
+/// Returns the camera the main UI root is drawn with.
+///
+/// This is the active camera with the lowest order which isn't used by a [`WoodpeckerViewRoot`].
+pub(crate) fn main_view_camera<'a, T>(
+    cameras: impl Iterator<Item = (Entity, &'a Camera, T)>,
+    view_roots: &Query<&WoodpeckerViewRoot>,
+) -> Option<(Entity, &'a Camera, T)> {
+    cameras
+        .filter(|(entity, camera, _)| {
+            camera.is_active && !view_roots.iter().any(|root| root.camera == *entity)
+        })
+        .min_by_key(|(_, camera, _)| camera.order)
+}
+
+/// Keeps the texture of every view root in sync with the viewport of its camera.
+pub(crate) fn sync_view_roots(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut view_roots: Query<(
+        Entity,
+        &WoodpeckerViewRoot,
+        Option<&mut WoodpeckerTextureTarget>,
+    )>,
+    displays: Query<(Entity, &ViewRootDisplay)>,
+    cameras: Query<&Camera>,
+) {
+    // Clean up displays of roots which are gone.
+    for (display_entity, display) in displays.iter() {
+        if !view_roots.contains(display.root) {
+            commands.entity(display_entity).despawn();
+        }
+    }
+
+    for (entity, view_root, texture_target) in view_roots.iter_mut() {
+        let Ok(camera) = cameras.get(view_root.camera) else {
+            continue;
+        };
+        let Some(size) = camera.physical_viewport_size() else {
+            continue;
+        };
+        let scale = camera.target_scaling_factor().unwrap_or(1.0);
+
+        let Some(mut texture_target) = texture_target else {
+            // The image is sampled through an sRGB view, so the node shows the colors vello wrote.
+            let image = WoodpeckerTextureTarget::create_image(&mut images, size);
+            commands
+                .entity(entity)
+                .insert(WoodpeckerTextureTarget::new(image.clone()).with_scale(scale));
+            commands.spawn((
+                ViewRootDisplay { root: entity },
+                ImageNode::new(image),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                GlobalZIndex(i32::MAX),
+                UiTargetCamera(view_root.camera),
+                Pickable::IGNORE,
+                Name::new("WoodpeckerViewRootDisplay"),
+            ));
+            continue;
+        };
+
+        if texture_target.scale != scale {
+            texture_target.scale = scale;
+        }
+        if images
+            .get(&texture_target.image)
+            .is_some_and(|image| image.size() != size)
+        {
+            if let Some(image) = images.get_mut(&texture_target.image) {
+                image.resize(Extent3d {
+                    width: size.x.max(1),
+                    height: size.y.max(1),
+                    ..default()
+                });
+            }
+            // Lets the root know it needs to be laid out at the new size.
+            texture_target.set_changed();
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::{
     children::WidgetChildren,
     prelude::{Units, Widget, WoodpeckerStyle},
+    render_to_texture::WoodpeckerTextureTarget,
+    views::{main_view_camera, WoodpeckerViewRoot},
     CurrentWidget, WoodpeckerView,
 };
 use bevy::{prelude::*, render::camera::CameraProjection, window::PrimaryWindow};
@@ -12,9 +14,15 @@ use bevy::{prelude::*, render::camera::CameraProjection, window::PrimaryWindow};
 pub struct WoodpeckerApp;
 
 pub fn update(
+    entity: Res<CurrentWidget>,
     mut prev_size: Local<Vec2>,
     window_query: Query<(Entity, &Window), (Changed<Window>, With<PrimaryWindow>)>,
+    texture_query: Query<Ref<WoodpeckerTextureTarget>>,
 ) -> bool {
+    if let Ok(texture_target) = texture_query.get(**entity) {
+        return texture_target.is_changed();
+    }
+
     let should_update = window_query.iter().count() > 0;
 
     if !should_update {
@@ -35,14 +43,36 @@ pub fn update(
 
 pub fn render(
     entity: Res<CurrentWidget>,
-    mut query: Query<(&mut WidgetChildren, &mut WoodpeckerStyle)>,
-    primary_window: Single<&Window, With<PrimaryWindow>>,
-    camera_query: Query<(&Camera, &Projection), With<WoodpeckerView>>,
+    mut query: Query<(
+        &mut WidgetChildren,
+        &mut WoodpeckerStyle,
+        Option<&WoodpeckerTextureTarget>,
+    )>,
+    primary_window: Option<Single<&Window, With<PrimaryWindow>>>,
+    camera_query: Query<(Entity, &Camera, &Projection), With<WoodpeckerView>>,
+    view_roots: Query<&WoodpeckerViewRoot>,
     images: Res<Assets<Image>>,
 ) {
-    let (camera, proj) = camera_query.single().unwrap();
+    let Ok((mut children, mut styles, texture_target)) = query.get_mut(**entity) else {
+        return;
+    };
 
-    let Ok((mut children, mut styles)) = query.get_mut(**entity) else {
+    // Apps rendering into a texture are sized by the texture.
+    if let Some(texture_target) = texture_target {
+        let size = texture_target.logical_size(&images);
+        *styles = WoodpeckerStyle {
+            width: Units::Pixels(size.x),
+            height: Units::Pixels(size.y),
+            ..*styles
+        };
+        children.apply(entity.as_parent());
+        return;
+    }
+
+    let Some(primary_window) = primary_window else {
+        return;
+    };
+    let Some((_, camera, proj)) = main_view_camera(camera_query.iter(), &view_roots) else {
         return;
     };
 
@@ -0,0 +1,60 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// Draws vector shapes such as paths, circles, lines, arcs, polygons and bezier curves.
+///
+/// Shapes are positioned in the local coordinates of the widget, (0, 0) is its top left corner.
+/// The canvas has no size of its own, so give it a width and height.
+///
+/// ```ignore
+/// commands.spawn((
+///     Canvas::new()
+///         .with_shape(Shape::circle(Vec2::splat(32.0), 30.0).with_fill(Srgba::gray(0.2)))
+///         .with_shape(
+///             Shape::arc(Vec2::splat(32.0), 26.0, -FRAC_PI_2, PI)
+///                 .with_stroke(ShapeStroke::new(6.0, Color::WHITE)),
+///         ),
+///     WoodpeckerStyle {
+///         width: 64.0.into(),
+///         height: 64.0.into(),
+///         ..Default::default()
+///     },
+/// ));
+/// ```
+#[derive(Component, Widget, Reflect, PartialEq, Default, Clone)]
+#[auto_update(render)]
+#[props(Canvas)]
+#[require(WidgetChildren, WoodpeckerStyle, WidgetRender)]
+pub struct Canvas {
+    /// The shapes in painting order.
+    #[reflect(ignore)]
+    pub shapes: Vec<Shape>,
+}
+
+impl Canvas {
+    /// Creates an empty canvas.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a shape on top of the other shapes.
+    pub fn with_shape(mut self, shape: Shape) -> Self {
+        self.shapes.push(shape);
+        self
+    }
+}
+
+pub fn render(
+    current_widget: Res<CurrentWidget>,
+    mut query: Query<(&Canvas, &mut WidgetRender, &mut WidgetChildren)>,
+) {
+    let Ok((canvas, mut widget_render, mut children)) = query.get_mut(**current_widget) else {
+        return;
+    };
+
+    *widget_render = WidgetRender::Canvas {
+        shapes: canvas.shapes.clone(),
+    };
+
+    children.apply(current_widget.as_parent());
+}
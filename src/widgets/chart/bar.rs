@@ -0,0 +1,211 @@
+use bevy::prelude::*;
+
+use super::{
+    bar_path, cartesian_grid, cartesian_labels, cartesian_plot, chart_style, highlight,
+    render_chart, ChartHover, ChartLabel, ChartPlot, ChartPoint, ChartQuery, ChartSeries,
+    ChartState, ChartStyles, ChartTransition, ChartWidget,
+};
+use crate::{prelude::*, DefaultFont};
+
+/// Plots one or more series of values as grouped bars.
+///
+/// Every series has one value per label, hovering a bar shows a tooltip with its value.
+///
+/// ```ignore
+/// commands.spawn(
+///     BarChart::new(["Q1", "Q2", "Q3", "Q4"])
+///         .with_series(ChartSeries::new("Sales", Srgba::hex("a6e3a1").unwrap(), [4.0, 7.5, 6.0, 9.0])),
+/// );
+/// ```
+#[derive(Component, Widget, Reflect, PartialEq, Clone)]
+#[auto_update(render)]
+#[props(BarChart, ChartStyles, ChartHover, WidgetLayout)]
+#[require(WidgetChildren, WoodpeckerStyle = chart_style(), WidgetRender, ChartStyles, ChartHover, ChartState, Pickable)]
+pub struct BarChart {
+    /// The labels along the x axis.
+    pub labels: Vec<String>,
+    /// The plotted series.
+    pub series: Vec<ChartSeries>,
+    /// The preferred number of ticks on the y axis.
+    pub y_ticks: usize,
+    /// Shows the names of the series below the chart.
+    pub show_legend: bool,
+    /// How much of the space of a label the bars fill, from 0.0 to 1.0.
+    pub bar_width: f32,
+    /// The radius of the top corners of the bars.
+    pub bar_radius: f32,
+    /// How the chart animates to new data.
+    pub transition: ChartTransition,
+}
+
+impl Default for BarChart {
+    fn default() -> Self {
+        Self {
+            labels: vec![],
+            series: vec![],
+            y_ticks: 5,
+            show_legend: true,
+            bar_width: 0.8,
+            bar_radius: 3.0,
+            transition: ChartTransition::default(),
+        }
+    }
+}
+
+impl BarChart {
+    /// Creates a bar chart with the given x axis labels.
+    pub fn new(labels: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            labels: labels.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a series to the chart.
+    pub fn with_series(mut self, series: ChartSeries) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Sets how the chart animates to new data.
+    pub fn with_transition(mut self, transition: ChartTransition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// The width of the space of a label.
+    fn band(&self, plot: &ChartPlot) -> f32 {
+        plot.rect.width() / self.labels.len().max(1) as f32
+    }
+
+    /// The center x coordinate of a label.
+    fn x_of(&self, plot: &ChartPlot, index: usize) -> f32 {
+        plot.rect.min.x + self.band(plot) * (index as f32 + 0.5)
+    }
+
+    /// The rectangle of a bar.
+    fn bar(&self, plot: &ChartPlot, point: ChartPoint, value: f32) -> Rect {
+        let group = self.band(plot) * self.bar_width.clamp(0.0, 1.0);
+        let width = group / self.series.len().max(1) as f32;
+        let left = self.x_of(plot, point.index) - group / 2.0 + width * point.series as f32;
+        let base = plot.y_of(0.0);
+        let top = plot.y_of(value);
+        Rect::new(left, base.min(top), left + width, base.max(top))
+    }
+}
+
+impl ChartWidget for BarChart {
+    fn data(&self) -> Vec<Vec<f32>> {
+        self.series
+            .iter()
+            .map(|series| series.values.clone())
+            .collect()
+    }
+
+    fn transition(&self) -> ChartTransition {
+        self.transition
+    }
+
+    fn legend(&self) -> Vec<(String, Color)> {
+        if !self.show_legend {
+            return vec![];
+        }
+        self.series
+            .iter()
+            .map(|series| (series.name.clone(), series.color))
+            .collect()
+    }
+
+    fn plot(&self, size: Vec2, measure: &mut dyn FnMut(&str) -> Vec2) -> ChartPlot {
+        cartesian_plot(size, &self.series, self.y_ticks, measure)
+    }
+
+    fn labels(&self, plot: &ChartPlot, measure: &mut dyn FnMut(&str) -> Vec2) -> Vec<ChartLabel> {
+        cartesian_labels(
+            plot,
+            &self.labels,
+            |index| self.x_of(plot, index),
+            self.band(plot),
+            measure,
+        )
+    }
+
+    fn shapes(
+        &self,
+        plot: &ChartPlot,
+        values: &[Vec<f32>],
+        hover: Option<ChartPoint>,
+        styles: &ChartStyles,
+    ) -> Vec<Shape> {
+        let mut shapes = cartesian_grid(plot, styles);
+        for (series_index, (series, values)) in self.series.iter().zip(values).enumerate() {
+            for (index, value) in values.iter().enumerate() {
+                let point = ChartPoint {
+                    series: series_index,
+                    index,
+                };
+                let color = if hover == Some(point) {
+                    highlight(series.color)
+                } else {
+                    series.color
+                };
+                let rect = self.bar(plot, point, *value);
+                // Only bars above zero round their top corners.
+                let radius = if *value >= 0.0 { self.bar_radius } else { 0.0 };
+                shapes.push(Shape::path(bar_path(rect, radius)).with_fill(color));
+            }
+        }
+        shapes
+    }
+
+    fn hit(&self, plot: &ChartPlot, values: &[Vec<f32>], position: Vec2) -> Option<ChartPoint> {
+        if !plot.rect.contains(position) {
+            return None;
+        }
+        // Picks the nearest bar of the hovered label, so short bars are easy to hover.
+        let index = ((position.x - plot.rect.min.x) / self.band(plot).max(f32::EPSILON)) as usize;
+        values
+            .iter()
+            .enumerate()
+            .filter_map(|(series, values)| {
+                let point = ChartPoint { series, index };
+                let rect = self.bar(plot, point, *values.get(index)?);
+                let distance = (rect.center() - position).length();
+                let inside = rect.contains(position);
+                Some((point, if inside { 0.0 } else { distance }))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(point, _)| point)
+    }
+
+    fn tooltip(
+        &self,
+        plot: &ChartPlot,
+        values: &[Vec<f32>],
+        point: ChartPoint,
+    ) -> Option<(String, Vec2)> {
+        let series = self.series.get(point.series)?;
+        let value = *values.get(point.series)?.get(point.index)?;
+        let label = self.labels.get(point.index).cloned().unwrap_or_default();
+        let target = series.values.get(point.index).copied().unwrap_or(value);
+        let rect = self.bar(plot, point, value);
+        Some((
+            format!("{label}\n{}: {target}", series.name),
+            Vec2::new(rect.center().x, plot.y_of(value)),
+        ))
+    }
+}
+
+pub fn render(
+    current_widget: Res<CurrentWidget>,
+    default_font: Res<DefaultFont>,
+    mut font_manager: ResMut<FontManager>,
+    mut query: ChartQuery<BarChart>,
+) {
+    render_chart(
+        &current_widget,
+        &default_font,
+        &mut font_manager,
+        &mut query,
+    );
+}
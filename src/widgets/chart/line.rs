@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use bevy_vello::vello::kurbo;
+
+use super::{
+    cartesian_grid, cartesian_labels, cartesian_plot, chart_style, highlight, render_chart,
+    ChartHover, ChartLabel, ChartPlot, ChartPoint, ChartQuery, ChartSeries, ChartState,
+    ChartStyles, ChartTransition, ChartWidget,
+};
+use crate::{prelude::*, DefaultFont};
+
+/// Plots one or more series of values as lines.
+///
+/// Every series has one value per label, hovering shows a tooltip for the nearest value.
+///
+/// ```ignore
+/// commands.spawn(
+///     LineChart::new(["Mon", "Tue", "Wed"])
+///         .with_series(ChartSeries::new("Visitors", Srgba::hex("89b4fa").unwrap(), [12.0, 18.0, 9.0])),
+/// );
+/// ```
+#[derive(Component, Widget, Reflect, PartialEq, Clone)]
+#[auto_update(render)]
+#[props(LineChart, ChartStyles, ChartHover, WidgetLayout)]
+#[require(WidgetChildren, WoodpeckerStyle = chart_style(), WidgetRender, ChartStyles, ChartHover, ChartState, Pickable)]
+pub struct LineChart {
+    /// The labels along the x axis.
+    pub labels: Vec<String>,
+    /// The plotted series.
+    pub series: Vec<ChartSeries>,
+    /// The preferred number of ticks on the y axis.
+    pub y_ticks: usize,
+    /// Shows the names of the series below the chart.
+    pub show_legend: bool,
+    /// How the chart animates to new data.
+    pub transition: ChartTransition,
+}
+
+impl Default for LineChart {
+    fn default() -> Self {
+        Self {
+            labels: vec![],
+            series: vec![],
+            y_ticks: 5,
+            show_legend: true,
+            transition: ChartTransition::default(),
+        }
+    }
+}
+
+impl LineChart {
+    /// Creates a line chart with the given x axis labels.
+    pub fn new(labels: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            labels: labels.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Adds a series to the chart.
+    pub fn with_series(mut self, series: ChartSeries) -> Self {
+        self.series.push(series);
+        self
+    }
+
+    /// Sets how the chart animates to new data.
+    pub fn with_transition(mut self, transition: ChartTransition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// The x coordinate of a label.
+    fn x_of(&self, plot: &ChartPlot, index: usize) -> f32 {
+        let count = self.labels.len().max(2) - 1;
+        plot.rect.min.x + plot.rect.width() * index as f32 / count as f32
+    }
+}
+
+impl ChartWidget for LineChart {
+    fn data(&self) -> Vec<Vec<f32>> {
+        self.series
+            .iter()
+            .map(|series| series.values.clone())
+            .collect()
+    }
+
+    fn transition(&self) -> ChartTransition {
+        self.transition
+    }
+
+    fn legend(&self) -> Vec<(String, Color)> {
+        if !self.show_legend {
+            return vec![];
+        }
+        self.series
+            .iter()
+            .map(|series| (series.name.clone(), series.color))
+            .collect()
+    }
+
+    fn plot(&self, size: Vec2, measure: &mut dyn FnMut(&str) -> Vec2) -> ChartPlot {
+        let mut plot = cartesian_plot(size, &self.series, self.y_ticks, measure);
+        // Keeps the first and last labels inside of the chart.
+        let edge = |label: Option<&String>, measure: &mut dyn FnMut(&str) -> Vec2| {
+            label.map(|label| measure(label).x / 2.0).unwrap_or(0.0)
+        };
+        let first = edge(self.labels.first(), measure);
+        let last = edge(self.labels.last(), measure);
+        plot.rect.min.x = plot.rect.min.x.max(first);
+        plot.rect.max.x = (size.x - last).max(plot.rect.min.x);
+        plot
+    }
+
+    fn labels(&self, plot: &ChartPlot, measure: &mut dyn FnMut(&str) -> Vec2) -> Vec<ChartLabel> {
+        let spacing = plot.rect.width() / (self.labels.len().max(2) - 1) as f32;
+        let width = self
+            .labels
+            .iter()
+            .map(|label| measure(label).x)
+            .fold(spacing, f32::max);
+        cartesian_labels(
+            plot,
+            &self.labels,
+            |index| self.x_of(plot, index),
+            width,
+            measure,
+        )
+    }
+
+    fn shapes(
+        &self,
+        plot: &ChartPlot,
+        values: &[Vec<f32>],
+        hover: Option<ChartPoint>,
+        styles: &ChartStyles,
+    ) -> Vec<Shape> {
+        let mut shapes = cartesian_grid(plot, styles);
+        for (series_index, (series, values)) in self.series.iter().zip(values).enumerate() {
+            let points = values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| Vec2::new(self.x_of(plot, index), plot.y_of(*value)))
+                .collect::<Vec<_>>();
+            shapes.push(Shape::polyline(points.iter().copied()).with_stroke(
+                ShapeStroke::new(styles.line_width, series.color).with_join(kurbo::Join::Round),
+            ));
+            for (index, point) in points.into_iter().enumerate() {
+                let hovered = hover
+                    == Some(ChartPoint {
+                        series: series_index,
+                        index,
+                    });
+                let (radius, color) = if hovered {
+                    (styles.point_radius * 1.75, highlight(series.color))
+                } else {
+                    (styles.point_radius, series.color)
+                };
+                shapes.push(Shape::circle(point, radius).with_fill(color));
+            }
+        }
+        shapes
+    }
+
+    fn hit(&self, plot: &ChartPlot, values: &[Vec<f32>], position: Vec2) -> Option<ChartPoint> {
+        if self.labels.is_empty() {
+            return None;
+        }
+        let spacing = plot.rect.width() / (self.labels.len().max(2) - 1) as f32;
+        let slack = Vec2::new(spacing / 2.0, 0.0);
+        if !Rect::from_corners(plot.rect.min - slack, plot.rect.max + slack).contains(position) {
+            return None;
+        }
+        let index = ((position.x - plot.rect.min.x) / spacing.max(f32::EPSILON))
+            .round()
+            .clamp(0.0, (self.labels.len() - 1) as f32) as usize;
+        values
+            .iter()
+            .enumerate()
+            .filter_map(|(series, values)| {
+                let distance = (plot.y_of(*values.get(index)?) - position.y).abs();
+                Some((ChartPoint { series, index }, distance))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(point, _)| point)
+    }
+
+    fn tooltip(
+        &self,
+        plot: &ChartPlot,
+        values: &[Vec<f32>],
+        point: ChartPoint,
+    ) -> Option<(String, Vec2)> {
+        let series = self.series.get(point.series)?;
+        let value = *values.get(point.series)?.get(point.index)?;
+        let label = self.labels.get(point.index).cloned().unwrap_or_default();
+        let target = series.values.get(point.index).copied().unwrap_or(value);
+        Some((
+            format!("{label}\n{}: {target}", series.name),
+            Vec2::new(self.x_of(plot, point.index), plot.y_of(value)),
+        ))
+    }
+}
+
+pub fn render(
+    current_widget: Res<CurrentWidget>,
+    default_font: Res<DefaultFont>,
+    mut font_manager: ResMut<FontManager>,
+    mut query: ChartQuery<LineChart>,
+) {
+    render_chart(
+        &current_widget,
+        &default_font,
+        &mut font_manager,
+        &mut query,
+    );
+}
@@ -0,0 +1,610 @@
+use bevy::prelude::*;
+use bevy_vello::vello::kurbo::{self, Shape as _};
+
+use crate::{prelude::*, DefaultFont};
+
+mod bar;
+mod line;
+mod pie;
+
+pub use bar::BarChart;
+pub use line::LineChart;
+pub use pie::{PieChart, PieSlice};
+
+/// A named series of values of a [`LineChart`] or [`BarChart`].
+#[derive(Debug, Clone, Reflect, PartialEq)]
+pub struct ChartSeries {
+    /// The name shown in the legend and tooltips.
+    pub name: String,
+    /// The color of the line or bars.
+    pub color: Color,
+    /// One value per category of the chart.
+    pub values: Vec<f32>,
+}
+
+impl ChartSeries {
+    /// Creates a new series.
+    pub fn new(
+        name: impl Into<String>,
+        color: impl Into<Color>,
+        values: impl IntoIterator<Item = f32>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            color: color.into(),
+            values: values.into_iter().collect(),
+        }
+    }
+}
+
+/// How a chart animates to new data.
+#[derive(Debug, Clone, Copy, Reflect, PartialEq)]
+pub struct ChartTransition {
+    /// The easing of the animation.
+    pub easing: TransitionEasing,
+    /// How long the animation takes in seconds, 0.0 disables it.
+    pub duration: f32,
+}
+
+impl Default for ChartTransition {
+    fn default() -> Self {
+        Self {
+            easing: TransitionEasing::CubicOut,
+            duration: 0.4,
+        }
+    }
+}
+
+/// A data point of a chart.
+#[derive(Debug, Clone, Copy, Reflect, PartialEq)]
+pub struct ChartPoint {
+    /// The index of the series, or of the slice of a pie chart.
+    pub series: usize,
+    /// The index of the value in the series, always 0 for pie charts.
+    pub index: usize,
+}
+
+/// The data point under the pointer, which is shown in a tooltip.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect, PartialEq)]
+pub struct ChartHover(pub Option<ChartPoint>);
+
+/// A collection of chart styles.
+///
+/// Axis and legend labels use the font, font size and color of the widget style.
+#[derive(Component, Reflect, Clone, Copy, PartialEq)]
+pub struct ChartStyles {
+    /// The color of the axis lines.
+    pub axis_color: Color,
+    /// The color of the horizontal grid lines.
+    pub grid_color: Color,
+    /// The width of lines in pixels.
+    pub line_width: f32,
+    /// The radius of the points of line charts in pixels.
+    pub point_radius: f32,
+    /// The styles of the tooltip.
+    pub tooltip: WoodpeckerStyle,
+}
+
+impl Default for ChartStyles {
+    fn default() -> Self {
+        Self {
+            axis_color: Color::WHITE.with_alpha(0.6),
+            grid_color: Color::WHITE.with_alpha(0.1),
+            line_width: 2.0,
+            point_radius: 3.0,
+            tooltip: WoodpeckerStyle {
+                background_color: colors::DARK_BACKGROUND,
+                border_radius: Corner::all(4.0),
+                padding: Edge::all(6.0),
+                font_size: 14.0,
+                color: Color::WHITE,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// The animation state of a chart.
+#[derive(Component, Debug, Default, Reflect, Clone, PartialEq)]
+pub struct ChartState {
+    /// The values the animation started at.
+    from: Vec<Vec<f32>>,
+    /// The values the animation ends at.
+    to: Vec<Vec<f32>>,
+    /// Seconds since the animation started.
+    elapsed: f32,
+    #[reflect(ignore)]
+    plot: ChartPlot,
+}
+
+impl ChartState {
+    /// Returns true while the chart animates to new data.
+    pub fn is_animating(&self, transition: &ChartTransition) -> bool {
+        self.elapsed < transition.duration
+    }
+
+    /// The values shown at the current point of the animation.
+    fn values(&self, transition: &ChartTransition) -> Vec<Vec<f32>> {
+        let t = if transition.duration > 0.0 {
+            transition.easing.ease(self.elapsed / transition.duration)
+        } else {
+            1.0
+        };
+        self.to
+            .iter()
+            .enumerate()
+            .map(|(series, values)| {
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(index, value)| {
+                        // New values grow from zero.
+                        let from = self
+                            .from
+                            .get(series)
+                            .and_then(|values| values.get(index))
+                            .copied()
+                            .unwrap_or(0.0);
+                        from + (value - from) * t
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The area values are plotted in, in local coordinates of the chart.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct ChartPlot {
+    pub rect: Rect,
+    pub axis: ChartAxis,
+}
+
+impl ChartPlot {
+    /// The y coordinate of a value.
+    pub fn y_of(&self, value: f32) -> f32 {
+        let range = (self.axis.max - self.axis.min).max(f32::EPSILON);
+        self.rect.max.y - (value - self.axis.min) / range * self.rect.height()
+    }
+}
+
+/// The value range of the y axis of a chart.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct ChartAxis {
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+}
+
+impl ChartAxis {
+    /// Picks a range with round tick values that includes zero and every value.
+    pub fn new<'a>(values: impl Iterator<Item = &'a f32>, ticks: usize) -> Self {
+        let (min, max) = values.fold((0.0f32, 0.0f32), |(min, max), value| {
+            (min.min(*value), max.max(*value))
+        });
+        let max = if max - min <= f32::EPSILON {
+            min + 1.0
+        } else {
+            max
+        };
+        let raw_step = (max - min) / ticks.max(1) as f32;
+        let magnitude = 10f32.powf(raw_step.log10().floor());
+        let step = [1.0, 2.0, 5.0, 10.0]
+            .into_iter()
+            .map(|factor| factor * magnitude)
+            .find(|step| *step >= raw_step)
+            .unwrap_or(10.0 * magnitude);
+        Self {
+            min: (min / step).floor() * step,
+            max: (max / step).ceil() * step,
+            step,
+        }
+    }
+
+    /// The values of the ticks from the bottom to the top.
+    pub fn ticks(&self) -> impl Iterator<Item = f32> + '_ {
+        let count = ((self.max - self.min) / self.step).round() as usize;
+        (0..=count).map(|tick| self.min + tick as f32 * self.step)
+    }
+
+    /// Formats a tick value with as many decimals as the step needs.
+    pub fn format(&self, value: f32) -> String {
+        let decimals = (-self.step.log10().floor()).max(0.0) as usize;
+        format!("{value:.decimals$}")
+    }
+}
+
+/// A text label of a chart in local coordinates.
+pub(crate) struct ChartLabel {
+    pub text: String,
+    pub position: Vec2,
+    pub width: f32,
+    pub alignment: TextAlign,
+}
+
+/// Shared behavior of the chart widgets.
+pub(crate) trait ChartWidget: Component + Clone {
+    /// The values of the chart, one list per series.
+    fn data(&self) -> Vec<Vec<f32>>;
+
+    /// How the chart animates to new data.
+    fn transition(&self) -> ChartTransition;
+
+    /// The names and colors shown in the legend, if it's shown.
+    fn legend(&self) -> Vec<(String, Color)>;
+
+    /// Lays out the plot inside of the given size, `measure` returns the size of a label.
+    fn plot(&self, size: Vec2, measure: &mut dyn FnMut(&str) -> Vec2) -> ChartPlot;
+
+    /// The axis labels.
+    fn labels(&self, plot: &ChartPlot, measure: &mut dyn FnMut(&str) -> Vec2) -> Vec<ChartLabel>;
+
+    /// The shapes drawing the chart with the given values.
+    fn shapes(
+        &self,
+        plot: &ChartPlot,
+        values: &[Vec<f32>],
+        hover: Option<ChartPoint>,
+        styles: &ChartStyles,
+    ) -> Vec<Shape>;
+
+    /// The data point at a position in local coordinates.
+    fn hit(&self, plot: &ChartPlot, values: &[Vec<f32>], position: Vec2) -> Option<ChartPoint>;
+
+    /// The text of the tooltip of a data point and where it points at.
+    fn tooltip(
+        &self,
+        plot: &ChartPlot,
+        values: &[Vec<f32>],
+        point: ChartPoint,
+    ) -> Option<(String, Vec2)>;
+}
+
+pub(crate) type ChartQuery<'w, 's, T> = Query<
+    'w,
+    's,
+    (
+        &'static T,
+        &'static ChartStyles,
+        &'static WoodpeckerStyle,
+        Option<&'static WidgetLayout>,
+        &'static ChartHover,
+        &'static mut ChartState,
+        &'static mut WidgetRender,
+        &'static mut WidgetChildren,
+    ),
+>;
+
+/// The shared render function of the chart widgets.
+pub(crate) fn render_chart<T: ChartWidget>(
+    current_widget: &CurrentWidget,
+    default_font: &DefaultFont,
+    font_manager: &mut FontManager,
+    query: &mut ChartQuery<T>,
+) {
+    let Ok((chart, styles, style, layout, hover, mut state, mut widget_render, mut children)) =
+        query.get_mut(**current_widget)
+    else {
+        return;
+    };
+    let layout = layout.copied().unwrap_or_default();
+
+    let label_style = WoodpeckerStyle {
+        position: WidgetPosition::Absolute,
+        font: style.font,
+        font_size: style.font_size,
+        color: style.color,
+        text_wrap: TextWrap::None,
+        ..Default::default()
+    };
+    let line_height = style.font_size * 1.2;
+    let mut measure = |text: &str| {
+        font_manager
+            .measure(text, &label_style, &layout, default_font)
+            .unwrap_or(Vec2::new(0.0, line_height))
+    };
+
+    let legend = chart.legend();
+    let legend_height = if legend.is_empty() {
+        0.0
+    } else {
+        line_height + 12.0
+    };
+    state.plot = chart.plot(layout.size - Vec2::new(0.0, legend_height), &mut measure);
+
+    let data = chart.data();
+    let transition = chart.transition();
+    if data != state.to {
+        state.from = state.values(&transition);
+        state.to = data;
+        state.elapsed = 0.0;
+    }
+    let values = state.values(&transition);
+    *widget_render = WidgetRender::Canvas {
+        shapes: chart.shapes(&state.plot, &values, hover.0, styles),
+    };
+
+    for label in chart.labels(&state.plot, &mut measure) {
+        children.add::<Element>((
+            Element,
+            WoodpeckerStyle {
+                left: label.position.x.into(),
+                top: label.position.y.into(),
+                width: label.width.into(),
+                text_alignment: Some(label.alignment),
+                ..label_style
+            },
+            WidgetRender::Text {
+                content: label.text,
+            },
+        ));
+    }
+
+    if !legend.is_empty() {
+        let mut legend_children = WidgetChildren::default();
+        for (name, color) in legend {
+            legend_children.add::<Element>((
+                Element,
+                WoodpeckerStyle {
+                    align_items: Some(WidgetAlignItems::Center),
+                    margin: Edge::new(0.0, 12.0, 0.0, 0.0),
+                    ..Default::default()
+                },
+                WidgetChildren::default()
+                    .with_child::<Element>((
+                        Element,
+                        WoodpeckerStyle {
+                            width: 10.0.into(),
+                            height: 10.0.into(),
+                            margin: Edge::new(0.0, 6.0, 0.0, 0.0),
+                            background_color: color,
+                            border_radius: Corner::all(2.0),
+                            ..Default::default()
+                        },
+                        WidgetRender::Quad,
+                    ))
+                    .with_child::<Element>((
+                        Element,
+                        WoodpeckerStyle {
+                            position: WidgetPosition::Relative,
+                            ..label_style
+                        },
+                        WidgetRender::Text { content: name },
+                    )),
+            ));
+        }
+        children.add::<Element>((
+            Element,
+            WoodpeckerStyle {
+                position: WidgetPosition::Absolute,
+                left: 0.0.into(),
+                top: (layout.size.y - legend_height + 12.0).into(),
+                width: Units::Percentage(100.0),
+                justify_content: Some(WidgetAlignContent::Center),
+                flex_wrap: WidgetFlexWrap::Wrap,
+                ..Default::default()
+            },
+            legend_children,
+        ));
+    }
+
+    if let Some((text, anchor)) = hover
+        .0
+        .and_then(|point| chart.tooltip(&state.plot, &values, point))
+    {
+        let tooltip_style = WoodpeckerStyle {
+            text_wrap: TextWrap::None,
+            ..styles.tooltip
+        };
+        let padding = Vec2::new(
+            tooltip_style.padding.left.value_or(0.0) + tooltip_style.padding.right.value_or(0.0),
+            tooltip_style.padding.top.value_or(0.0) + tooltip_style.padding.bottom.value_or(0.0),
+        );
+        let size = font_manager
+            .measure(&text, &tooltip_style, &layout, default_font)
+            .unwrap_or_default()
+            + padding;
+        let left = (anchor.x - size.x / 2.0).clamp(0.0, (layout.size.x - size.x).max(0.0));
+        let above = anchor.y - size.y - 8.0;
+        let top = if above >= 0.0 { above } else { anchor.y + 8.0 };
+        children.add::<Element>((
+            Element,
+            WoodpeckerStyle {
+                position: WidgetPosition::Absolute,
+                left: left.into(),
+                top: top.into(),
+                ..tooltip_style
+            },
+            WidgetRender::Quad,
+            WidgetChildren::default().with_child::<Element>((
+                Element,
+                WoodpeckerStyle {
+                    font: tooltip_style.font.or(style.font),
+                    font_size: tooltip_style.font_size,
+                    color: tooltip_style.color,
+                    text_wrap: TextWrap::None,
+                    ..Default::default()
+                },
+                WidgetRender::Text { content: text },
+            )),
+        ));
+    }
+
+    children.apply(current_widget.as_parent());
+}
+
+/// Animates charts to new data.
+pub(crate) fn update_chart_animations<T: ChartWidget>(
+    time: Res<Time>,
+    mut query: Query<
+        (
+            &T,
+            &ChartStyles,
+            &ChartHover,
+            &mut ChartState,
+            &mut WidgetRender,
+        ),
+        Without<PreviousWidget>,
+    >,
+) {
+    for (chart, styles, hover, mut state, mut widget_render) in query.iter_mut() {
+        let transition = chart.transition();
+        if !state.is_animating(&transition) {
+            continue;
+        }
+        state.elapsed += time.delta_secs();
+        let values = state.values(&transition);
+        *widget_render = WidgetRender::Canvas {
+            shapes: chart.shapes(&state.plot, &values, hover.0, styles),
+        };
+    }
+}
+
+/// Picks the data point under the pointer.
+pub(crate) fn chart_pointer_move<T: ChartWidget>(
+    trigger: Trigger<Pointer<Move>>,
+    mut query: Query<(&T, &ChartState, &WidgetLayout, &mut ChartHover)>,
+) {
+    let Ok((chart, state, layout, mut hover)) = query.get_mut(trigger.target()) else {
+        return;
+    };
+    let Some(position) = trigger.hit.position else {
+        return;
+    };
+    let position = position.truncate() - layout.location;
+    let values = state.values(&chart.transition());
+    let point = chart.hit(&state.plot, &values, position);
+    if hover.0 != point {
+        hover.0 = point;
+    }
+}
+
+/// Hides the tooltip when the pointer leaves the chart.
+pub(crate) fn chart_pointer_out<T: ChartWidget>(
+    trigger: Trigger<Pointer<Out>>,
+    mut query: Query<&mut ChartHover, With<T>>,
+) {
+    let Ok(mut hover) = query.get_mut(trigger.target()) else {
+        return;
+    };
+    if hover.0.is_some() {
+        hover.0 = None;
+    }
+}
+
+/// Lays out the plot of a chart with a y axis, `categories` are labeled along the x axis.
+pub(crate) fn cartesian_plot(
+    size: Vec2,
+    series: &[ChartSeries],
+    y_ticks: usize,
+    measure: &mut dyn FnMut(&str) -> Vec2,
+) -> ChartPlot {
+    let axis = ChartAxis::new(
+        series.iter().flat_map(|series| series.values.iter()),
+        y_ticks,
+    );
+    let label_width = axis
+        .ticks()
+        .map(|tick| measure(&axis.format(tick)).x)
+        .fold(0.0, f32::max);
+    let label_height = measure("0").y;
+    ChartPlot {
+        rect: Rect::new(
+            label_width + 8.0,
+            label_height / 2.0,
+            (size.x - 8.0).max(label_width + 8.0),
+            (size.y - label_height - 8.0).max(label_height / 2.0),
+        ),
+        axis,
+    }
+}
+
+/// The y axis labels of a chart and the category labels under the given x positions.
+pub(crate) fn cartesian_labels(
+    plot: &ChartPlot,
+    categories: &[String],
+    category_x: impl Fn(usize) -> f32,
+    category_width: f32,
+    measure: &mut dyn FnMut(&str) -> Vec2,
+) -> Vec<ChartLabel> {
+    let mut labels = plot
+        .axis
+        .ticks()
+        .map(|tick| {
+            let text = plot.axis.format(tick);
+            let size = measure(&text);
+            ChartLabel {
+                text,
+                position: Vec2::new(0.0, plot.y_of(tick) - size.y / 2.0),
+                width: plot.rect.min.x - 8.0,
+                alignment: TextAlign::Right,
+            }
+        })
+        .collect::<Vec<_>>();
+    labels.extend(
+        categories
+            .iter()
+            .enumerate()
+            .map(|(index, category)| ChartLabel {
+                text: category.clone(),
+                position: Vec2::new(
+                    category_x(index) - category_width / 2.0,
+                    plot.rect.max.y + 6.0,
+                ),
+                width: category_width,
+                alignment: TextAlign::Center,
+            }),
+    );
+    labels
+}
+
+/// The grid lines and axes of a chart with a y axis.
+pub(crate) fn cartesian_grid(plot: &ChartPlot, styles: &ChartStyles) -> Vec<Shape> {
+    let rect = plot.rect;
+    let mut shapes = plot
+        .axis
+        .ticks()
+        .map(|tick| {
+            let y = plot.y_of(tick);
+            Shape::line(Vec2::new(rect.min.x, y), Vec2::new(rect.max.x, y))
+                .with_stroke(ShapeStroke::new(1.0, styles.grid_color))
+        })
+        .collect::<Vec<_>>();
+    shapes.push(
+        Shape::polyline([
+            rect.min,
+            Vec2::new(rect.min.x, rect.max.y),
+            Vec2::new(rect.max.x, rect.max.y),
+        ])
+        .with_stroke(ShapeStroke::new(1.0, styles.axis_color)),
+    );
+    shapes
+}
+
+/// A lighter version of a color, used for hovered data points.
+pub(crate) fn highlight(color: Color) -> Color {
+    color.mix(&Color::WHITE, 0.3)
+}
+
+/// A rectangle path with rounded top corners, used for bars.
+pub(crate) fn bar_path(rect: Rect, radius: f32) -> kurbo::BezPath {
+    let radius = radius.min(rect.width() / 2.0).min(rect.height()) as f64;
+    kurbo::RoundedRect::new(
+        rect.min.x as f64,
+        rect.min.y as f64,
+        rect.max.x as f64,
+        rect.max.y as f64,
+        kurbo::RoundedRectRadii::new(radius, radius, 0.0, 0.0),
+    )
+    .to_path(0.1)
+}
+
+/// The default style of the chart widgets.
+pub(crate) fn chart_style() -> WoodpeckerStyle {
+    WoodpeckerStyle {
+        width: Units::Percentage(100.0),
+        height: 300.0.into(),
+        font_size: 14.0,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,257 @@
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+use bevy::prelude::*;
+use bevy_vello::vello::kurbo;
+
+use super::{
+    chart_style, highlight, render_chart, ChartHover, ChartLabel, ChartPlot, ChartPoint,
+    ChartQuery, ChartState, ChartStyles, ChartTransition, ChartWidget,
+};
+use crate::{prelude::*, DefaultFont};
+
+/// How far a hovered slice moves out of the pie in pixels.
+const HOVER_OFFSET: f32 = 6.0;
+
+/// A slice of a [`PieChart`].
+#[derive(Debug, Clone, Reflect, PartialEq)]
+pub struct PieSlice {
+    /// The label shown in the legend and tooltip.
+    pub label: String,
+    /// The value of the slice, the size of a slice is its share of the sum of all values.
+    pub value: f32,
+    /// The color of the slice.
+    pub color: Color,
+}
+
+impl PieSlice {
+    /// Creates a new slice.
+    pub fn new(label: impl Into<String>, value: f32, color: impl Into<Color>) -> Self {
+        Self {
+            label: label.into(),
+            value,
+            color: color.into(),
+        }
+    }
+}
+
+/// Plots the shares of values as slices of a pie, or of a donut.
+///
+/// Slices start at the top and go clockwise, hovering a slice shows a tooltip with its share.
+///
+/// ```ignore
+/// commands.spawn(
+///     PieChart::new()
+///         .with_slice(PieSlice::new("Rust", 70.0, Srgba::hex("fab387").unwrap()))
+///         .with_slice(PieSlice::new("WGSL", 30.0, Srgba::hex("89b4fa").unwrap())),
+/// );
+/// ```
+#[derive(Component, Widget, Reflect, PartialEq, Clone)]
+#[auto_update(render)]
+#[props(PieChart, ChartStyles, ChartHover, WidgetLayout)]
+#[require(WidgetChildren, WoodpeckerStyle = chart_style(), WidgetRender, ChartStyles, ChartHover, ChartState, Pickable)]
+pub struct PieChart {
+    /// The slices in clockwise order.
+    pub slices: Vec<PieSlice>,
+    /// The radius of the hole in the middle as a fraction of the radius, 0.0 draws a full pie.
+    pub inner_radius: f32,
+    /// Shows the labels of the slices below the chart.
+    pub show_legend: bool,
+    /// How the chart animates to new data.
+    pub transition: ChartTransition,
+}
+
+impl Default for PieChart {
+    fn default() -> Self {
+        Self {
+            slices: vec![],
+            inner_radius: 0.0,
+            show_legend: true,
+            transition: ChartTransition::default(),
+        }
+    }
+}
+
+impl PieChart {
+    /// Creates an empty pie chart.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a slice to the chart.
+    pub fn with_slice(mut self, slice: PieSlice) -> Self {
+        self.slices.push(slice);
+        self
+    }
+
+    /// Sets the radius of the hole in the middle as a fraction of the radius.
+    pub fn with_inner_radius(mut self, inner_radius: f32) -> Self {
+        self.inner_radius = inner_radius;
+        self
+    }
+
+    /// Sets how the chart animates to new data.
+    pub fn with_transition(mut self, transition: ChartTransition) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    /// The center and radius of the pie.
+    fn circle(plot: &ChartPlot) -> (Vec2, f32) {
+        let radius = (plot.rect.width().min(plot.rect.height()) / 2.0 - HOVER_OFFSET).max(0.0);
+        (plot.rect.center(), radius)
+    }
+
+    /// The start and sweep angles of every slice.
+    fn angles(values: &[Vec<f32>]) -> Vec<(f32, f32)> {
+        let values = values
+            .iter()
+            .map(|values| values.first().copied().unwrap_or(0.0).max(0.0))
+            .collect::<Vec<_>>();
+        let total = values.iter().sum::<f32>().max(f32::EPSILON);
+        let mut start = -FRAC_PI_2;
+        values
+            .into_iter()
+            .map(|value| {
+                let sweep = value / total * TAU;
+                start += sweep;
+                (start - sweep, sweep)
+            })
+            .collect()
+    }
+}
+
+impl ChartWidget for PieChart {
+    fn data(&self) -> Vec<Vec<f32>> {
+        self.slices.iter().map(|slice| vec![slice.value]).collect()
+    }
+
+    fn transition(&self) -> ChartTransition {
+        self.transition
+    }
+
+    fn legend(&self) -> Vec<(String, Color)> {
+        if !self.show_legend {
+            return vec![];
+        }
+        self.slices
+            .iter()
+            .map(|slice| (slice.label.clone(), slice.color))
+            .collect()
+    }
+
+    fn plot(&self, size: Vec2, _measure: &mut dyn FnMut(&str) -> Vec2) -> ChartPlot {
+        ChartPlot {
+            rect: Rect::from_corners(Vec2::ZERO, size.max(Vec2::ZERO)),
+            ..Default::default()
+        }
+    }
+
+    fn labels(&self, _plot: &ChartPlot, _measure: &mut dyn FnMut(&str) -> Vec2) -> Vec<ChartLabel> {
+        vec![]
+    }
+
+    fn shapes(
+        &self,
+        plot: &ChartPlot,
+        values: &[Vec<f32>],
+        hover: Option<ChartPoint>,
+        _styles: &ChartStyles,
+    ) -> Vec<Shape> {
+        let (center, radius) = Self::circle(plot);
+        let inner = radius * self.inner_radius.clamp(0.0, 1.0);
+        self.slices
+            .iter()
+            .zip(Self::angles(values))
+            .enumerate()
+            .filter(|(_, (_, (_, sweep)))| *sweep > 0.0)
+            .map(|(index, (slice, (start, sweep)))| {
+                let hovered = hover
+                    == Some(ChartPoint {
+                        series: index,
+                        index: 0,
+                    });
+                let (center, color) = if hovered {
+                    let direction = Vec2::from_angle(start + sweep / 2.0);
+                    (center + direction * HOVER_OFFSET, highlight(slice.color))
+                } else {
+                    (center, slice.color)
+                };
+                Shape::path(slice_path(center, inner, radius, start, sweep)).with_fill(color)
+            })
+            .collect()
+    }
+
+    fn hit(&self, plot: &ChartPlot, values: &[Vec<f32>], position: Vec2) -> Option<ChartPoint> {
+        let (center, radius) = Self::circle(plot);
+        let offset = position - center;
+        let distance = offset.length();
+        if distance > radius || distance < radius * self.inner_radius.clamp(0.0, 1.0) {
+            return None;
+        }
+        let angle = (offset.to_angle() + FRAC_PI_2).rem_euclid(TAU) - FRAC_PI_2;
+        Self::angles(values)
+            .into_iter()
+            .position(|(start, sweep)| sweep > 0.0 && angle >= start && angle < start + sweep)
+            .map(|series| ChartPoint { series, index: 0 })
+    }
+
+    fn tooltip(
+        &self,
+        plot: &ChartPlot,
+        values: &[Vec<f32>],
+        point: ChartPoint,
+    ) -> Option<(String, Vec2)> {
+        let slice = self.slices.get(point.series)?;
+        let (start, sweep) = *Self::angles(values).get(point.series)?;
+        let (center, radius) = Self::circle(plot);
+        let share = sweep / TAU * 100.0;
+        Some((
+            format!("{}: {} ({share:.1}%)", slice.label, slice.value),
+            center + Vec2::from_angle(start + sweep / 2.0) * radius * 0.75,
+        ))
+    }
+}
+
+/// A slice of a pie, or of a donut when `inner` isn't zero.
+fn slice_path(center: Vec2, inner: f32, outer: f32, start: f32, sweep: f32) -> kurbo::BezPath {
+    let center = kurbo::Point::new(center.x as f64, center.y as f64);
+    let arc = |radius: f32, start: f32, sweep: f32| {
+        kurbo::Arc::new(
+            center,
+            kurbo::Vec2::new(radius as f64, radius as f64),
+            start as f64,
+            sweep as f64,
+            0.0,
+        )
+    };
+    let point =
+        |radius: f32, angle: f32| center + kurbo::Vec2::from_angle(angle as f64) * radius as f64;
+
+    let mut path = kurbo::BezPath::new();
+    if inner > 0.0 {
+        path.move_to(point(outer, start));
+        path.extend(arc(outer, start, sweep).append_iter(0.1));
+        path.line_to(point(inner, start + sweep));
+        path.extend(arc(inner, start + sweep, -sweep).append_iter(0.1));
+    } else {
+        path.move_to(center);
+        path.line_to(point(outer, start));
+        path.extend(arc(outer, start, sweep).append_iter(0.1));
+    }
+    path.close_path();
+    path
+}
+
+pub fn render(
+    current_widget: Res<CurrentWidget>,
+    default_font: Res<DefaultFont>,
+    mut font_manager: ResMut<FontManager>,
+    mut query: ChartQuery<PieChart>,
+) {
+    render_chart(
+        &current_widget,
+        &default_font,
+        &mut font_manager,
+        &mut query,
+    );
+}
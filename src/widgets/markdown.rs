@@ -0,0 +1,655 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy_vello::prelude::VelloFont;
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Options, Parser, Tag, TagEnd};
+
+use super::colors;
+
+/// The styles used to display markdown.
+#[derive(Component, Reflect, Debug, Clone, PartialEq)]
+pub struct MarkdownTheme {
+    /// The font of the text, the default font is used if none is set.
+    pub font: Option<AssetId<VelloFont>>,
+    /// The font of inline code and code blocks, the text font is used if none is set.
+    pub code_font: Option<AssetId<VelloFont>>,
+    /// The size of paragraph text in pixels.
+    pub font_size: f32,
+    /// The sizes of headings from level one to six in pixels.
+    pub heading_sizes: [f32; 6],
+    /// The colors of headings from level one to six.
+    pub heading_colors: [Color; 6],
+    /// The font weight of headings.
+    pub heading_weight: FontWeight,
+    /// The color of paragraph text.
+    pub text_color: Color,
+    /// The color of links.
+    pub link_color: Color,
+    /// The color of inline code.
+    pub code_color: Color,
+    /// The background color of inline code and code blocks.
+    pub code_background: Color,
+    /// The syntax highlighting theme of code blocks, see [`highlight`].
+    pub code_theme: String,
+    /// The color of text inside of block quotes.
+    pub quote_color: Color,
+    /// The color of the bar next to block quotes.
+    pub quote_border_color: Color,
+    /// The color of horizontal rules.
+    pub rule_color: Color,
+    /// The space between blocks in pixels.
+    pub block_spacing: f32,
+    /// The indentation of list items and block quotes in pixels.
+    pub indent: f32,
+}
+
+impl Default for MarkdownTheme {
+    fn default() -> Self {
+        Self {
+            font: None,
+            code_font: None,
+            font_size: 16.0,
+            heading_sizes: [32.0, 26.0, 22.0, 19.0, 17.0, 16.0],
+            heading_colors: [Color::WHITE; 6],
+            heading_weight: FontWeight::BOLD,
+            text_color: Srgba::rgb(0.85, 0.85, 0.85).into(),
+            link_color: colors::PRIMARY,
+            code_color: colors::PRIMARY_LIGHT,
+            code_background: colors::DARK_BACKGROUND,
+            code_theme: "dracula".into(),
+            quote_color: Srgba::rgb(0.65, 0.65, 0.7).into(),
+            quote_border_color: colors::BACKGROUND_LIGHT,
+            rule_color: colors::BACKGROUND_LIGHT,
+            block_spacing: 12.0,
+            indent: 24.0,
+        }
+    }
+}
+
+/// Displays [CommonMark](https://commonmark.org/) markdown.
+///
+/// Headings, emphasis, strikethrough, lists, code blocks, links, block quotes, horizontal rules
+/// and images are supported. Code blocks are highlighted with [`highlight`] and images are loaded
+/// through the [`AssetServer`]. An image on its own in a paragraph is shown as a block, images
+/// within text are placed inline and scaled to fit the height of the text. Clicking a link
+/// triggers a [`TextLinkClick`] with the url as the payload which bubbles up to the markdown
+/// widget.
+///
+/// ```ignore
+/// commands.spawn((
+///     Markdown::new("# Hello\nSome *markdown* text."),
+///     MarkdownTheme::default(),
+/// )).observe(|trigger: Trigger<TextLinkClick>| info!("Open {}", trigger.link.payload));
+/// ```
+#[derive(Component, Widget, Reflect, Default, PartialEq, Clone)]
+#[auto_update(render)]
+#[props(Markdown, MarkdownTheme)]
+#[require(WidgetChildren, WoodpeckerStyle = Markdown::default_style(), MarkdownTheme)]
+pub struct Markdown {
+    /// The markdown source.
+    pub content: String,
+}
+
+impl Markdown {
+    /// Creates a new markdown widget from the markdown source.
+    pub fn new(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+        }
+    }
+
+    fn default_style() -> WoodpeckerStyle {
+        WoodpeckerStyle {
+            flex_direction: WidgetFlexDirection::Column,
+            width: Units::Percentage(100.0),
+            ..Default::default()
+        }
+    }
+}
+
+pub fn render(
+    current_widget: Res<CurrentWidget>,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(
+        &Markdown,
+        &MarkdownTheme,
+        &mut WoodpeckerStyle,
+        &mut WidgetChildren,
+    )>,
+) {
+    let Ok((markdown, theme, mut styles, mut children)) = query.get_mut(**current_widget) else {
+        return;
+    };
+
+    styles.gap = (
+        Units::Pixels(theme.block_spacing),
+        Units::Pixels(theme.block_spacing),
+    );
+
+    let blocks = parse_markdown(&markdown.content, theme, &|url| asset_server.load(url));
+    *children = block_children(blocks, theme, theme.text_color);
+
+    children.apply(current_widget.as_parent());
+}
+
+/// A block of parsed markdown.
+#[derive(Debug)]
+enum Block {
+    /// A paragraph or a heading with its zero based level.
+    Text {
+        heading: Option<usize>,
+        content: RichText,
+    },
+    Quote(Vec<Block>),
+    List {
+        start: Option<u64>,
+        items: Vec<Vec<Block>>,
+    },
+    Code {
+        language: String,
+        code: String,
+    },
+    Image(Handle<Image>),
+    Rule,
+}
+
+enum Container {
+    Root,
+    Quote,
+    List(Option<u64>, Vec<Vec<Block>>),
+    Item,
+}
+
+/// Turns markdown events into blocks of rich text.
+struct MarkdownParser<'a> {
+    theme: &'a MarkdownTheme,
+    load_image: &'a dyn Fn(String) -> Handle<Image>,
+    containers: Vec<(Container, Vec<Block>)>,
+    text: Option<(Option<usize>, RichText)>,
+    code: Option<(String, String)>,
+    strong: usize,
+    emphasis: usize,
+    strikethrough: Vec<usize>,
+    links: Vec<(String, usize)>,
+    image_depth: usize,
+}
+
+impl<'a> MarkdownParser<'a> {
+    fn new(theme: &'a MarkdownTheme, load_image: &'a dyn Fn(String) -> Handle<Image>) -> Self {
+        Self {
+            theme,
+            load_image,
+            containers: vec![(Container::Root, vec![])],
+            text: None,
+            code: None,
+            strong: 0,
+            emphasis: 0,
+            strikethrough: vec![],
+            links: vec![],
+            image_depth: 0,
+        }
+    }
+
+    fn push_block(&mut self, block: Block) {
+        if let Some((_, blocks)) = self.containers.last_mut() {
+            blocks.push(block);
+        }
+    }
+
+    /// Finishes the current paragraph or heading.
+    fn flush_text(&mut self) {
+        let Some((heading, content)) = self.text.take() else {
+            return;
+        };
+        let content = match heading {
+            Some(_) => {
+                let len = content.text.len();
+                content.with_style(
+                    0..len,
+                    TextSpanStyle::default().with_font_weight(self.theme.heading_weight),
+                )
+            }
+            None => content,
+        };
+        // A paragraph with nothing but an image is shown as an image block.
+        if let (None, [item]) = (heading, content.inline.as_slice()) {
+            if let InlineContent::Image(handle) = &item.content {
+                if content.text.trim().is_empty() && content.links().is_empty() {
+                    self.push_block(Block::Image(handle.clone()));
+                    return;
+                }
+            }
+        }
+        if !content.text.is_empty() || !content.inline.is_empty() {
+            self.push_block(Block::Text { heading, content });
+        }
+    }
+
+    fn text_mut(&mut self) -> &mut RichText {
+        &mut self.text.get_or_insert_with(|| (None, RichText::new())).1
+    }
+
+    fn push_text(&mut self, text: &str) {
+        let mut style = TextSpanStyle::default();
+        if self.strong > 0 {
+            style.font_weight = Some(FontWeight::BOLD);
+        }
+        if self.emphasis > 0 {
+            style.font_style = Some(FontStyle::Italic);
+        }
+        if !self.links.is_empty() {
+            style.color = Some(self.theme.link_color);
+        }
+        let rich_text = std::mem::take(self.text_mut());
+        *self.text_mut() = if style == TextSpanStyle::default() {
+            rich_text.with_text(text)
+        } else {
+            rich_text.with_styled_text(text, style)
+        };
+    }
+
+    fn push_inline_code(&mut self, code: &str) {
+        let mut style = TextSpanStyle::default().with_color(self.theme.code_color);
+        style.font = self.theme.code_font;
+        let rich_text = std::mem::take(self.text_mut());
+        let start = rich_text.text.len();
+        *self.text_mut() = rich_text.with_styled_text(code, style).with_decoration(
+            start..start + code.len(),
+            TextDecoration::NONE.with_highlight(self.theme.code_background),
+        );
+    }
+
+    /// Places an image after the current text, as tall as the text.
+    fn push_image(&mut self, url: String) {
+        let handle = (self.load_image)(url);
+        let font_size = self
+            .text
+            .as_ref()
+            .and_then(|(heading, _)| *heading)
+            .map(|level| self.theme.heading_sizes[level])
+            .unwrap_or(self.theme.font_size);
+        let rich_text = std::mem::take(self.text_mut());
+        *self.text_mut() = rich_text.with_inline_image(handle, Vec2::splat(font_size));
+    }
+
+    fn current_index(&self) -> usize {
+        self.text
+            .as_ref()
+            .map(|(_, rich_text)| rich_text.text.len())
+            .unwrap_or(0)
+    }
+
+    fn start(&mut self, tag: Tag) {
+        match tag {
+            Tag::Paragraph => {
+                self.flush_text();
+                self.text = Some((None, RichText::new()));
+            }
+            Tag::Heading { level, .. } => {
+                self.flush_text();
+                self.text = Some((Some(heading_index(level)), RichText::new()));
+            }
+            Tag::BlockQuote(_) => {
+                self.flush_text();
+                self.containers.push((Container::Quote, vec![]));
+            }
+            Tag::CodeBlock(kind) => {
+                self.flush_text();
+                let language = match kind {
+                    CodeBlockKind::Fenced(info) => {
+                        info.split_whitespace().next().unwrap_or("").to_string()
+                    }
+                    CodeBlockKind::Indented => String::new(),
+                };
+                self.code = Some((language, String::new()));
+            }
+            Tag::List(start) => {
+                self.flush_text();
+                self.containers
+                    .push((Container::List(start, vec![]), vec![]));
+            }
+            Tag::Item => {
+                self.flush_text();
+                self.containers.push((Container::Item, vec![]));
+            }
+            Tag::Emphasis => self.emphasis += 1,
+            Tag::Strong => self.strong += 1,
+            Tag::Strikethrough => self.strikethrough.push(self.current_index()),
+            Tag::Link { dest_url, .. } => {
+                let start = self.current_index();
+                self.links.push((dest_url.to_string(), start));
+            }
+            Tag::Image { dest_url, .. } => {
+                self.push_image(dest_url.to_string());
+                self.image_depth += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, tag: TagEnd) {
+        match tag {
+            TagEnd::Paragraph | TagEnd::Heading(_) => self.flush_text(),
+            TagEnd::BlockQuote(_) => {
+                self.flush_text();
+                if let Some((Container::Quote, blocks)) = self.containers.pop() {
+                    self.push_block(Block::Quote(blocks));
+                }
+            }
+            TagEnd::CodeBlock => {
+                if let Some((language, mut code)) = self.code.take() {
+                    if code.ends_with('\n') {
+                        code.pop();
+                    }
+                    self.push_block(Block::Code { language, code });
+                }
+            }
+            TagEnd::List(_) => {
+                self.flush_text();
+                if let Some((Container::List(start, items), _)) = self.containers.pop() {
+                    self.push_block(Block::List { start, items });
+                }
+            }
+            TagEnd::Item => {
+                self.flush_text();
+                if let Some((Container::Item, blocks)) = self.containers.pop() {
+                    if let Some((Container::List(_, items), _)) = self.containers.last_mut() {
+                        items.push(blocks);
+                    }
+                }
+            }
+            TagEnd::Emphasis => self.emphasis = self.emphasis.saturating_sub(1),
+            TagEnd::Strong => self.strong = self.strong.saturating_sub(1),
+            TagEnd::Strikethrough => {
+                if let Some(start) = self.strikethrough.pop() {
+                    let end = self.current_index();
+                    let rich_text = std::mem::take(self.text_mut());
+                    *self.text_mut() = rich_text.with_decoration(
+                        start..end,
+                        TextDecoration::NONE.with_strikethrough(TextDecorationLine::DEFAULT),
+                    );
+                }
+            }
+            TagEnd::Link => {
+                if let Some((url, start)) = self.links.pop() {
+                    let end = self.current_index();
+                    // Links around nothing but images have no text to click on.
+                    if start == end {
+                        return;
+                    }
+                    let rich_text = std::mem::take(self.text_mut());
+                    *self.text_mut() = rich_text.with_link(start..end, url).with_decoration(
+                        start..end,
+                        TextDecoration::NONE.with_underline(TextDecorationLine::DEFAULT),
+                    );
+                }
+            }
+            TagEnd::Image => self.image_depth = self.image_depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    fn event(&mut self, event: Event) {
+        match event {
+            Event::Start(tag) => self.start(tag),
+            Event::End(tag) => self.end(tag),
+            // Alt text isn't displayed.
+            Event::Text(_) | Event::Code(_) if self.image_depth > 0 => {}
+            Event::Text(text) => match &mut self.code {
+                Some((_, code)) => code.push_str(&text),
+                None => self.push_text(&text),
+            },
+            Event::Code(code) => self.push_inline_code(&code),
+            Event::SoftBreak => self.push_text(" "),
+            Event::HardBreak => self.push_text("\n"),
+            Event::Rule => {
+                self.flush_text();
+                self.push_block(Block::Rule);
+            }
+            _ => {}
+        }
+    }
+
+    fn finish(mut self) -> Vec<Block> {
+        self.flush_text();
+        self.containers
+            .drain(..)
+            .next()
+            .map(|(_, blocks)| blocks)
+            .unwrap_or_default()
+    }
+}
+
+fn heading_index(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 0,
+        HeadingLevel::H2 => 1,
+        HeadingLevel::H3 => 2,
+        HeadingLevel::H4 => 3,
+        HeadingLevel::H5 => 4,
+        HeadingLevel::H6 => 5,
+    }
+}
+
+fn parse_markdown(
+    content: &str,
+    theme: &MarkdownTheme,
+    load_image: &dyn Fn(String) -> Handle<Image>,
+) -> Vec<Block> {
+    let mut parser = MarkdownParser::new(theme, load_image);
+    for event in Parser::new_ext(content, Options::ENABLE_STRIKETHROUGH) {
+        parser.event(event);
+    }
+    parser.finish()
+}
+
+fn column_style(theme: &MarkdownTheme) -> WoodpeckerStyle {
+    WoodpeckerStyle {
+        flex_direction: WidgetFlexDirection::Column,
+        width: Units::Percentage(100.0),
+        gap: (
+            Units::Pixels(theme.block_spacing),
+            Units::Pixels(theme.block_spacing),
+        ),
+        ..Default::default()
+    }
+}
+
+fn block_children(blocks: Vec<Block>, theme: &MarkdownTheme, text_color: Color) -> WidgetChildren {
+    let mut children = WidgetChildren::default();
+    for block in blocks {
+        match block {
+            Block::Text { heading, content } => {
+                let styles = WoodpeckerStyle {
+                    font: theme.font,
+                    font_size: heading
+                        .map(|level| theme.heading_sizes[level])
+                        .unwrap_or(theme.font_size),
+                    color: heading
+                        .map(|level| theme.heading_colors[level])
+                        .unwrap_or(text_color),
+                    width: Units::Percentage(100.0),
+                    ..Default::default()
+                };
+                if content.links().is_empty() {
+                    children.add::<Element>((Element, styles, WidgetRender::RichText { content }));
+                } else {
+                    children.add::<Element>((
+                        Element,
+                        styles,
+                        WidgetRender::RichText { content },
+                        Pickable::default(),
+                    ));
+                }
+            }
+            Block::Quote(blocks) => {
+                children.add::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        padding: Edge::new(0.0, 0.0, 0.0, theme.indent - 4.0),
+                        border: Edge::new(0.0, 0.0, 0.0, 4.0),
+                        border_color: theme.quote_border_color,
+                        ..column_style(theme)
+                    },
+                    WidgetRender::Quad,
+                    block_children(blocks, theme, theme.quote_color),
+                ));
+            }
+            Block::List { start, items } => {
+                let mut list_children = WidgetChildren::default();
+                for (i, item) in items.into_iter().enumerate() {
+                    let marker = match start {
+                        Some(start) => format!("{}.", start + i as u64),
+                        None => "•".to_string(),
+                    };
+                    list_children.add::<Element>((
+                        Element,
+                        WoodpeckerStyle {
+                            flex_direction: WidgetFlexDirection::Row,
+                            width: Units::Percentage(100.0),
+                            ..Default::default()
+                        },
+                        WidgetChildren::default()
+                            .with_child::<Element>((
+                                Element,
+                                WoodpeckerStyle {
+                                    font: theme.font,
+                                    font_size: theme.font_size,
+                                    color: text_color,
+                                    width: Units::Pixels(theme.indent),
+                                    flex_shrink: 0.0,
+                                    ..Default::default()
+                                },
+                                WidgetRender::Text { content: marker },
+                            ))
+                            .with_child::<Element>((
+                                Element,
+                                WoodpeckerStyle {
+                                    flex_grow: 1.0,
+                                    flex_shrink: 1.0,
+                                    ..column_style(theme)
+                                },
+                                block_children(item, theme, text_color),
+                            )),
+                    ));
+                }
+                children.add::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        gap: (Units::Pixels(4.0), Units::Pixels(4.0)),
+                        ..column_style(theme)
+                    },
+                    list_children,
+                ));
+            }
+            Block::Code { language, code } => {
+                let highlighted = highlight(&language, &code, &theme.code_theme);
+                children.add::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        background_color: theme.code_background,
+                        border_radius: Corner::all(4.0),
+                        padding: Edge::all(10.0),
+                        ..column_style(theme)
+                    },
+                    WidgetRender::Quad,
+                    WidgetChildren::default().with_child::<Element>((
+                        Element,
+                        WoodpeckerStyle {
+                            font: theme.code_font.or(theme.font),
+                            font_size: theme.font_size,
+                            color: text_color,
+                            width: Units::Percentage(100.0),
+                            ..Default::default()
+                        },
+                        WidgetRender::RichText {
+                            content: RichText::from_hightlighted(&code, highlighted),
+                        },
+                    )),
+                ));
+            }
+            Block::Image(handle) => {
+                children.add::<Element>((
+                    Element,
+                    WoodpeckerStyle::default(),
+                    WidgetRender::Image { handle },
+                ));
+            }
+            Block::Rule => {
+                children.add::<Element>((
+                    Element,
+                    WoodpeckerStyle {
+                        width: Units::Percentage(100.0),
+                        height: Units::Pixels(1.0),
+                        background_color: theme.rule_color,
+                        ..Default::default()
+                    },
+                    WidgetRender::Quad,
+                ));
+            }
+        }
+    }
+    children
+}
+
+#[test]
+fn test_markdown_blocks() {
+    let theme = MarkdownTheme::default();
+    let blocks = parse_markdown(
+        "# Title\n\nSome **bold** and [a link](https://example.com).\n\n- one\n- two\n\n> quoted\n\n```rust\nfn main() {}\n```\n",
+        &theme,
+        &|_| Handle::default(),
+    );
+    assert_eq!(blocks.len(), 5);
+    let Block::Text {
+        heading: Some(0),
+        content,
+    } = &blocks[0]
+    else {
+        panic!("Expected a heading");
+    };
+    assert_eq!(content.text, "Title");
+    let Block::Text {
+        heading: None,
+        content,
+    } = &blocks[1]
+    else {
+        panic!("Expected a paragraph");
+    };
+    assert_eq!(content.text, "Some bold and a link.");
+    assert_eq!(content.links()[0].payload, "https://example.com");
+    assert_eq!(content.links()[0].range, 14..20);
+    assert!(matches!(&blocks[2], Block::List { start: None, items } if items.len() == 2));
+    assert!(matches!(&blocks[3], Block::Quote(quote) if quote.len() == 1));
+    assert!(
+        matches!(&blocks[4], Block::Code { language, code } if language == "rust" && code == "fn main() {}")
+    );
+}
+
+#[test]
+fn test_markdown_images() {
+    let theme = MarkdownTheme::default();
+    let blocks = parse_markdown(
+        "![alt](block.png)\n\nAn ![icon](icon.png) inline and [![linked](linked.png)](https://example.com).\n",
+        &theme,
+        &|_| Handle::default(),
+    );
+    assert_eq!(blocks.len(), 2);
+    assert!(matches!(&blocks[0], Block::Image(_)));
+    let Block::Text {
+        heading: None,
+        content,
+    } = &blocks[1]
+    else {
+        panic!("Expected a paragraph");
+    };
+    assert_eq!(content.text, "An  inline and .");
+    assert_eq!(
+        content
+            .inline
+            .iter()
+            .map(|item| (item.index, item.size))
+            .collect::<Vec<_>>(),
+        vec![(3, Vec2::splat(16.0)), (15, Vec2::splat(16.0))]
+    );
+    assert!(content.links().is_empty());
+}
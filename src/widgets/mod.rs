@@ -4,6 +4,8 @@ use bevy::prelude::*;
 
 mod app;
 mod button;
+mod canvas;
+mod chart;
 mod checkbox;
 mod clip;
 mod color_picker;
@@ -12,6 +14,7 @@ pub mod colors;
 mod dropdown;
 mod element;
 mod icon_button;
+mod markdown;
 mod modal;
 mod scroll;
 mod slider;
@@ -19,6 +22,7 @@ mod tab;
 mod text_box;
 mod toggle;
 mod transition;
+mod typewriter;
 mod window;
 mod windowing_context;
 
@@ -28,6 +32,11 @@ pub use syntax_highlighting::highlight;
 pub use app::WoodpeckerApp;
 // use bevy_mod_picking::prelude::EventListenerPlugin;
 pub use button::{ButtonStyles, WButton};
+pub use canvas::Canvas;
+pub use chart::{
+    BarChart, ChartHover, ChartPoint, ChartSeries, ChartState, ChartStyles, ChartTransition,
+    LineChart, PieChart, PieSlice,
+};
 pub use checkbox::{
     Checkbox, CheckboxChanged, CheckboxState, CheckboxStyles, CheckboxWidgetStyles,
 };
@@ -36,6 +45,7 @@ pub use color_picker::{ColorPicker, ColorPickerChanged};
 pub use dropdown::{Dropdown, DropdownChanged, DropdownStyles};
 pub use element::Element;
 pub use icon_button::{IconButton, IconButtonStyles};
+pub use markdown::{Markdown, MarkdownTheme};
 pub use modal::{Modal, ModalStyles, TitleChildren};
 pub use scroll::content::ScrollContent;
 pub use scroll::scroll_bar::ScrollBar;
@@ -46,6 +56,9 @@ pub use tab::*;
 pub use text_box::{ApplyHighlighting, TextBox, TextBoxState, TextChanged, TextboxStyles};
 pub use toggle::{Toggle, ToggleChanged, ToggleState, ToggleStyles, ToggleWidgetStyles};
 pub use transition::*;
+pub use typewriter::{
+    Typewriter, TypewriterCharacter, TypewriterEffect, TypewriterFinished, TypewriterState,
+};
 pub use window::{WindowState, WoodpeckerWindow};
 pub use windowing_context::{WindowingContext, WindowingContextProvider};
 
@@ -63,6 +76,10 @@ impl Plugin for WoodpeckerUIWidgetPlugin {
             .register_widget::<WoodpeckerApp>()
             .register_widget::<Element>()
             .register_widget::<WButton>()
+            .register_widget::<Canvas>()
+            .register_widget::<LineChart>()
+            .register_widget::<BarChart>()
+            .register_widget::<PieChart>()
             .register_widget::<Clip>()
             .register_widget::<TextBox>()
             .register_widget::<Modal>()
@@ -81,11 +98,24 @@ impl Plugin for WoodpeckerUIWidgetPlugin {
             .register_widget::<TabContent>()
             .register_widget::<Checkbox>()
             .register_widget::<ColorPicker>()
+            .register_widget::<Markdown>()
+            .register_widget::<Typewriter>()
+            .add_observer(typewriter::skip_on_click)
+            .add_observer(chart::chart_pointer_move::<LineChart>)
+            .add_observer(chart::chart_pointer_move::<BarChart>)
+            .add_observer(chart::chart_pointer_move::<PieChart>)
+            .add_observer(chart::chart_pointer_out::<LineChart>)
+            .add_observer(chart::chart_pointer_out::<BarChart>)
+            .add_observer(chart::chart_pointer_out::<PieChart>)
             .add_systems(
                 Update,
                 (
                     text_box::cursor_animation_system,
                     transition::update_transitions,
+                    typewriter::update_typewriters,
+                    chart::update_chart_animations::<LineChart>,
+                    chart::update_chart_animations::<BarChart>,
+                    chart::update_chart_animations::<PieChart>,
                 ),
             );
     }
@@ -4,11 +4,11 @@ use bevy_vello::vello::{
     kurbo::{Affine, Vec2},
     peniko::Brush,
 };
-use parley::{FontFamily, StyleProperty};
+use parley::StyleProperty;
 use web_time::Instant;
 
 use crate::{
-    keyboard_input::{WidgetKeyboardButtonEvent, WidgetPasteEvent},
+    keyboard_input::{copy_to_clipboard, WidgetKeyboardButtonEvent, WidgetPasteEvent},
     picking_backend::compute_letterboxed_transform,
     prelude::*,
     DefaultFont,
@@ -226,13 +226,10 @@ pub fn render(
             .map(|lh| styles.normal.font_size / lh)
             .unwrap_or(1.2),
     ));
-    text_styles.insert(StyleProperty::FontStack(parley::FontStack::Single(
-        FontFamily::Named(
-            font_manager
-                .get_family(styles.normal.font.as_ref().unwrap_or(&default_font.0.id()))
-                .into(),
-        ),
-    )));
+    text_styles.insert(StyleProperty::FontStack(
+        font_manager.font_stack_or_default(styles.normal.font.as_ref(), &default_font.0.id()),
+    ));
+    text_styles.insert(StyleProperty::Locale(font_manager.locale()));
 
     let state_entity = hook_helper.use_state(
         &mut commands,
@@ -999,37 +996,7 @@ pub fn textbox_handle_keyboard_events(
             return;
         };
         if let Some(text) = state.engine.selected_text() {
-            #[cfg(not(target_arch = "wasm32"))]
-            if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                match clipboard.set_text(text) {
-                    Ok(_) => {}
-                    Err(err) => error!("{err}"),
-                }
-            }
-            #[cfg(target_arch = "wasm32")]
-            {
-                let Some(clipboard) =
-                    web_sys::window().and_then(|window| Some(window.navigator().clipboard()))
-                else {
-                    warn!("no clipboard");
-                    return;
-                };
-                let promise = clipboard.write_text(text);
-                let future = wasm_bindgen_futures::JsFuture::from(promise);
-
-                let (sender, receiver) = futures_channel::oneshot::channel::<String>();
-
-                let pool = bevy::tasks::TaskPool::new();
-                pool.spawn(async move {
-                    let Ok(text) = future.await else {
-                        return;
-                    };
-                    let Some(text) = text.as_string() else {
-                        return;
-                    };
-                    let _ = sender.send(text);
-                });
-            }
+            copy_to_clipboard(text.to_string());
         }
     }
     if trigger.code == KeyCode::Delete {
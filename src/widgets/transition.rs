@@ -73,6 +73,15 @@ pub enum TransitionEasing {
 }
 
 impl TransitionEasing {
+    /// Eases a progress between 0.0 and 1.0.
+    pub fn ease(&self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        match self.try_into_easing_function() {
+            Some(easing) => Ease::calc(x, easing),
+            None => x,
+        }
+    }
+
     fn try_into_easing_function(&self) -> Option<EaseFunction> {
         match self {
             TransitionEasing::QuadraticIn => Some(EaseFunction::QuadraticIn),
@@ -165,11 +174,7 @@ impl Transition {
         // as Milliseconds
         let elapsed_time = self.start.elapsed().as_secs_f32() * 1000.0;
         if (elapsed_time < self.timeout) && self.playing {
-            let mut x = if let Some(easing) = self.easing.try_into_easing_function() {
-                Ease::calc((elapsed_time / self.timeout).clamp(0.0, 1.0), easing)
-            } else {
-                (elapsed_time / self.timeout).clamp(0.0, 1.0)
-            };
+            let mut x = self.easing.ease(elapsed_time / self.timeout);
             if self.reversing {
                 x = 1.0 - x;
             }
@@ -217,6 +222,6 @@ impl Transition {
 pub(crate) fn update_transitions(mut query: Query<(&mut Transition, &mut WoodpeckerStyle)>) {
     for (mut transition, mut styles) in query.iter_mut() {
         let new_styles = transition.update();
-        *styles = new_styles;
+        styles.set_if_neq(new_styles);
     }
 }
@@ -0,0 +1,271 @@
+use std::f32::consts::PI;
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// How each character of a [`Typewriter`] appears.
+#[derive(Debug, Default, Clone, Copy, Reflect, PartialEq)]
+pub enum TypewriterEffect {
+    /// Characters appear at once.
+    #[default]
+    None,
+    /// Characters fade in.
+    Fade,
+    /// Characters jump up and fall back into place.
+    Bounce {
+        /// How high characters jump in pixels.
+        height: f32,
+    },
+}
+
+/// Reveals rich text character by character, for example for dialogue boxes.
+///
+/// The whole text is laid out up front and characters which aren't shown yet are hidden, so
+/// lines don't rewrap as characters appear. [`TypewriterCharacter`] is triggered for every shown
+/// character and [`TypewriterFinished`] once all of the text is shown.
+///
+/// ```ignore
+/// commands
+///     .spawn((
+///         Typewriter {
+///             effect: TypewriterEffect::Bounce { height: 4.0 },
+///             ..Typewriter::new(RichText::new().with_text("Hello ").with_bold_text("traveler!"))
+///         },
+///         WoodpeckerStyle {
+///             font_size: 24.0,
+///             ..Default::default()
+///         },
+///     ))
+///     .observe(|trigger: Trigger<TypewriterCharacter>| {
+///         // Play a voice blip.
+///     });
+/// ```
+#[derive(Component, Widget, Reflect, PartialEq, Clone)]
+#[auto_update(render)]
+#[props(Typewriter)]
+#[require(
+    WidgetChildren,
+    WoodpeckerStyle,
+    WidgetRender,
+    TypewriterState,
+    Pickable
+)]
+pub struct Typewriter {
+    /// The text which is revealed.
+    pub content: RichText,
+    /// How many characters are shown per second.
+    pub characters_per_second: f32,
+    /// The extra pause in seconds after a period, exclamation or question mark.
+    pub sentence_pause: f32,
+    /// The extra pause in seconds after a comma, semicolon or colon.
+    pub comma_pause: f32,
+    /// How each character appears.
+    pub effect: TypewriterEffect,
+    /// How long the effect of a character takes in seconds.
+    pub effect_duration: f32,
+    /// Shows all of the text at once when the widget is clicked.
+    pub skip_on_click: bool,
+}
+
+impl Default for Typewriter {
+    fn default() -> Self {
+        Self {
+            content: RichText::new(),
+            characters_per_second: 30.0,
+            sentence_pause: 0.4,
+            comma_pause: 0.15,
+            effect: TypewriterEffect::None,
+            effect_duration: 0.2,
+            skip_on_click: true,
+        }
+    }
+}
+
+impl Typewriter {
+    /// Creates a new typewriter which reveals the given text.
+    pub fn new(content: RichText) -> Self {
+        Self {
+            content,
+            ..Default::default()
+        }
+    }
+
+    /// The pause in seconds after a character, `next` is the character after it.
+    fn pause_after(&self, character: char, next: Option<char>) -> f32 {
+        // Only pause at the end of a word so numbers like 1.5 don't stall.
+        if !next.is_some_and(char::is_whitespace) {
+            return 0.0;
+        }
+        match character {
+            '.' | '!' | '?' | '…' => self.sentence_pause,
+            ',' | ';' | ':' => self.comma_pause,
+            _ => 0.0,
+        }
+    }
+}
+
+/// The progress of a [`Typewriter`].
+///
+/// The state is reset when the typewriter changes.
+#[derive(Component, Debug, Default, Reflect, Clone, PartialEq)]
+pub struct TypewriterState {
+    /// The number of characters shown.
+    pub revealed: usize,
+    /// Seconds since the typewriter started.
+    pub elapsed: f32,
+    /// The time the next character is shown at.
+    next_reveal: f32,
+    /// When every shown character appeared.
+    reveal_times: Vec<f32>,
+    skip: bool,
+    finished: bool,
+    /// True once the text is shown and no effect is running.
+    settled: bool,
+}
+
+impl TypewriterState {
+    /// Shows all of the text at once.
+    pub fn skip(&mut self) {
+        self.skip = true;
+    }
+
+    /// Starts revealing the text again.
+    pub fn restart(&mut self) {
+        *self = TypewriterState::default();
+    }
+
+    /// Returns true once all of the text is shown.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// Triggered on a [`Typewriter`] when a character is shown, for example to play a voice blip.
+///
+/// Not triggered for the characters shown at once by skipping.
+/// The event bubbles up to the ancestors of the widget.
+#[derive(Event, Debug, Clone)]
+#[event(traversal = &'static ChildOf, auto_propagate)]
+pub struct TypewriterCharacter {
+    /// The shown character.
+    pub character: char,
+    /// The byte index of the character in the text.
+    pub index: usize,
+}
+
+/// Triggered on a [`Typewriter`] once all of its text is shown.
+///
+/// The event bubbles up to the ancestors of the widget.
+#[derive(Event, Debug, Clone)]
+#[event(traversal = &'static ChildOf, auto_propagate)]
+pub struct TypewriterFinished;
+
+pub fn render(
+    current_widget: Res<CurrentWidget>,
+    mut query: Query<(
+        &Typewriter,
+        &mut TypewriterState,
+        &mut WidgetRender,
+        &mut WidgetChildren,
+    )>,
+) {
+    let Ok((typewriter, mut state, mut widget_render, mut children)) =
+        query.get_mut(**current_widget)
+    else {
+        return;
+    };
+
+    state.restart();
+    let text_len = typewriter.content.text().len();
+    *widget_render = WidgetRender::RichText {
+        content: typewriter
+            .content
+            .clone()
+            .with_glyph_effect(0..text_len, Vec2::ZERO, 0.0),
+    };
+
+    children.apply(current_widget.as_parent());
+}
+
+/// Reveals the characters of typewriters and animates their effects.
+pub(crate) fn update_typewriters(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<
+        (Entity, &Typewriter, &mut TypewriterState, &mut WidgetRender),
+        Without<PreviousWidget>,
+    >,
+) {
+    for (entity, typewriter, mut state, mut widget_render) in query.iter_mut() {
+        if state.settled {
+            continue;
+        }
+        let state = state.as_mut();
+        state.elapsed += time.delta_secs();
+
+        let text = typewriter.content.text();
+        let characters = text.char_indices().collect::<Vec<_>>();
+        if state.skip {
+            state.revealed = characters.len();
+            state.reveal_times.resize(characters.len(), f32::MIN);
+        }
+        while state.revealed < characters.len() && state.elapsed >= state.next_reveal {
+            let (index, character) = characters[state.revealed];
+            state.reveal_times.push(state.next_reveal);
+            state.revealed += 1;
+            state.next_reveal += 1.0 / typewriter.characters_per_second.max(f32::EPSILON)
+                + typewriter.pause_after(
+                    character,
+                    characters.get(state.revealed).map(|(_, next)| *next),
+                );
+            commands.trigger_targets(TypewriterCharacter { character, index }, entity);
+        }
+        if !state.finished && state.revealed == characters.len() {
+            state.finished = true;
+            commands.trigger_targets(TypewriterFinished, entity);
+        }
+
+        let mut content = typewriter.content.clone();
+        if let Some((hidden_start, _)) = characters.get(state.revealed) {
+            content = content.with_glyph_effect(*hidden_start..text.len(), Vec2::ZERO, 0.0);
+        }
+        let mut animating = false;
+        if typewriter.effect != TypewriterEffect::None && typewriter.effect_duration > 0.0 {
+            // Characters are shown in order, so only the last few are still animating.
+            for ((index, character), reveal_time) in
+                characters.iter().zip(state.reveal_times.iter()).rev()
+            {
+                let t = ((state.elapsed - reveal_time) / typewriter.effect_duration).max(0.0);
+                if t >= 1.0 {
+                    break;
+                }
+                animating = true;
+                let (offset, alpha) = match typewriter.effect {
+                    TypewriterEffect::Fade => (Vec2::ZERO, t),
+                    TypewriterEffect::Bounce { height } => {
+                        (Vec2::new(0.0, -height * (t * PI).sin()), 1.0)
+                    }
+                    TypewriterEffect::None => (Vec2::ZERO, 1.0),
+                };
+                content =
+                    content.with_glyph_effect(*index..index + character.len_utf8(), offset, alpha);
+            }
+        }
+        state.settled = state.finished && !animating;
+
+        *widget_render = WidgetRender::RichText { content };
+    }
+}
+
+/// Skips to the end of a typewriter when it's clicked.
+pub(crate) fn skip_on_click(
+    trigger: Trigger<Pointer<Click>>,
+    mut query: Query<(&Typewriter, &mut TypewriterState)>,
+) {
+    let Ok((typewriter, mut state)) = query.get_mut(trigger.target()) else {
+        return;
+    };
+    if typewriter.skip_on_click && !state.finished {
+        state.skip();
+    }
+}
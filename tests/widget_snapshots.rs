@@ -0,0 +1,126 @@
+//! Golden image snapshots of built-in widgets, rendered with the CPU path.
+//!
+//! Run with `cargo test --features snapshot --test widget_snapshots`. Set
+//! `WOODPECKER_UPDATE_SNAPSHOTS=1` to write new golden files after an intended visual change.
+#![cfg(feature = "snapshot")]
+
+use std::time::{Duration, Instant};
+
+use bevy::{prelude::*, state::app::StatesPlugin};
+use woodpecker_ui::{prelude::*, snapshot::*, DefaultFont};
+
+/// Mounts a root rendering into a texture of the given size and runs frames until every asset
+/// it uses is loaded.
+pub fn mount(size: UVec2, children: WidgetChildren) -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        StatesPlugin,
+        bevy::input::InputPlugin,
+        bevy::picking::DefaultPickingPlugins,
+        WindowPlugin {
+            primary_window: None,
+            ..Default::default()
+        },
+    ))
+    .init_asset::<Image>()
+    .init_asset::<Mesh>()
+    .init_asset::<Shader>()
+    .init_asset::<TextureAtlasLayout>()
+    .add_plugins(WoodpeckerUIPlugin::default());
+
+    let image = WoodpeckerTextureTarget::create_image(
+        &mut app.world_mut().resource_mut::<Assets<Image>>(),
+        size,
+    );
+    let root = app
+        .world_mut()
+        .spawn((
+            WoodpeckerApp,
+            WoodpeckerStyle {
+                width: Units::Percentage(100.0),
+                height: Units::Percentage(100.0),
+                padding: Edge::all(10.0),
+                ..Default::default()
+            },
+            WoodpeckerTextureTarget::new(image),
+            children,
+        ))
+        .id();
+    app.world_mut()
+        .resource_mut::<WoodpeckerContext>()
+        .set_root_widget(root);
+
+    let start = Instant::now();
+    let mut loaded_frames = 0;
+    while loaded_frames < 5 {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "Timed out loading assets"
+        );
+        app.update();
+        if assets_loaded(app.world_mut()) {
+            loaded_frames += 1;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    (app, root)
+}
+
+/// Returns true once the default font and every svg drawn by a widget are loaded.
+fn assets_loaded(world: &mut World) -> bool {
+    let svgs = world
+        .query::<&WidgetRender>()
+        .iter(world)
+        .filter_map(|render| match render {
+            WidgetRender::Svg { handle, .. } => Some(handle.id().untyped()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let asset_server = world.resource::<AssetServer>();
+    asset_server.is_loaded_with_dependencies(&world.resource::<DefaultFont>().0)
+        && svgs
+            .into_iter()
+            .all(|id| asset_server.is_loaded_with_dependencies(id))
+}
+
+pub fn snapshot(app: &App, root: Entity, name: &str) {
+    let mut renderer = SnapshotRenderer::new().unwrap_or_else(|err| panic!("{err}"));
+    let image = renderer.render_root(app.world(), root).unwrap();
+    assert_snapshot(
+        &image,
+        format!("tests/snapshots/{name}.png"),
+        SnapshotTolerance::default(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn checkbox() {
+    let (app, root) = mount(
+        UVec2::new(64, 64),
+        WidgetChildren::default().with_child::<Checkbox>(Checkbox),
+    );
+    snapshot(&app, root, "checkbox");
+}
+
+#[test]
+fn toggle() {
+    let (app, root) = mount(
+        UVec2::new(80, 48),
+        WidgetChildren::default().with_child::<Toggle>(Toggle),
+    );
+    snapshot(&app, root, "toggle");
+}
+
+#[test]
+fn color_picker() {
+    let (app, root) = mount(
+        UVec2::new(340, 300),
+        WidgetChildren::default().with_child::<ColorPicker>(ColorPicker {
+            initial_color: Srgba::RED.into(),
+        }),
+    );
+    snapshot(&app, root, "color_picker");
+}